@@ -0,0 +1,13 @@
+CREATE TABLE payout_entries (
+                                booking_id TEXT PRIMARY KEY NOT NULL,
+                                tenant_id TEXT NOT NULL,
+                                amount INTEGER NOT NULL,
+                                status TEXT NOT NULL DEFAULT 'PAID', -- PAID
+                                payment_reference TEXT,
+                                paid_at TIMESTAMPTZ,
+                                created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                                FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE CASCADE,
+                                FOREIGN KEY (booking_id) REFERENCES bookings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_payout_entries_tenant ON payout_entries(tenant_id);
@@ -0,0 +1,7 @@
+ALTER TABLE bookings ADD COLUMN checked_in_at TIMESTAMPTZ;
+
+ALTER TABLE tenants ADD COLUMN show_label_id TEXT REFERENCES booking_labels(id) ON DELETE SET NULL;
+ALTER TABLE tenants ADD COLUMN noshow_label_id TEXT REFERENCES booking_labels(id) ON DELETE SET NULL;
+
+UPDATE tenants SET show_label_id = (SELECT id FROM booking_labels WHERE booking_labels.tenant_id = tenants.id AND name = 'Show' LIMIT 1);
+UPDATE tenants SET noshow_label_id = (SELECT id FROM booking_labels WHERE booking_labels.tenant_id = tenants.id AND name = 'Noshow' LIMIT 1);
@@ -0,0 +1,12 @@
+CREATE TABLE audit_logs (
+                            id TEXT PRIMARY KEY NOT NULL,
+                            tenant_id TEXT NOT NULL,
+                            actor TEXT NOT NULL,
+                            action TEXT NOT NULL,
+                            subject TEXT NOT NULL,
+                            details JSONB NOT NULL,
+                            created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                            FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_audit_logs_tenant ON audit_logs(tenant_id, created_at);
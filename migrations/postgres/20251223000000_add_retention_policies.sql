@@ -0,0 +1,10 @@
+-- Per-tenant data retention; NULL disables the respective rule
+CREATE TABLE retention_policies (
+                                    tenant_id TEXT PRIMARY KEY NOT NULL,
+                                    anonymize_after_months INTEGER,
+                                    mail_logs_after_months INTEGER,
+                                    invitees_after_months INTEGER,
+                                    last_run_at TIMESTAMPTZ,
+                                    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                                    FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE CASCADE
+);
@@ -0,0 +1,17 @@
+-- Optional expiry and a booking quota per invitation token
+ALTER TABLE invitees ADD COLUMN expires_at TIMESTAMPTZ;
+ALTER TABLE invitees ADD COLUMN max_uses INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE invitees ADD COLUMN use_count INTEGER NOT NULL DEFAULT 0;
+
+UPDATE invitees SET use_count = 1 WHERE status = 'USED';
+
+-- Further events a token is valid for, e.g. all studies of a recruitment wave
+CREATE TABLE invitee_events (
+                                invitee_id TEXT NOT NULL,
+                                event_id TEXT NOT NULL,
+                                PRIMARY KEY (invitee_id, event_id),
+                                FOREIGN KEY (invitee_id) REFERENCES invitees(id) ON DELETE CASCADE,
+                                FOREIGN KEY (event_id) REFERENCES events(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_invitee_events_event ON invitee_events(event_id);
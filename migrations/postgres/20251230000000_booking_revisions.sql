@@ -0,0 +1,2 @@
+-- Revision of the details sent to the participant; the calendar entry's SEQUENCE
+ALTER TABLE bookings ADD COLUMN revision INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+-- Booking a stored response holds, so erasing its participant can drop the response
+ALTER TABLE idempotency_keys ADD COLUMN booking_id TEXT;
+
+CREATE INDEX idx_idempotency_keys_booking_id ON idempotency_keys(booking_id);
+
+UPDATE idempotency_keys SET booking_id = response_body::jsonb->>'id'
+WHERE status_code BETWEEN 200 AND 299;
@@ -0,0 +1,12 @@
+-- Purposes a participant agreed to when booking, kept as proof of consent
+CREATE TABLE consent_records (
+                                 id TEXT PRIMARY KEY NOT NULL,
+                                 tenant_id TEXT NOT NULL,
+                                 booking_id TEXT NOT NULL,
+                                 purpose TEXT NOT NULL,
+                                 granted_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                                 FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE CASCADE,
+                                 FOREIGN KEY (booking_id) REFERENCES bookings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_consent_records_booking ON consent_records(booking_id);
@@ -0,0 +1,13 @@
+CREATE TABLE bank_details (
+                              booking_id TEXT PRIMARY KEY NOT NULL,
+                              tenant_id TEXT NOT NULL,
+                              account_holder TEXT NOT NULL,
+                              iban_encrypted TEXT NOT NULL,
+                              bic_encrypted TEXT,
+                              iban_last4 TEXT NOT NULL,
+                              updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                              FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE CASCADE,
+                              FOREIGN KEY (booking_id) REFERENCES bookings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_bank_details_tenant ON bank_details(tenant_id);
@@ -0,0 +1,38 @@
+CREATE TABLE participants (
+                              id TEXT PRIMARY KEY NOT NULL,
+                              tenant_id TEXT NOT NULL,
+                              email TEXT NOT NULL,
+                              name TEXT NOT NULL,
+                              notes TEXT,
+                              created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                              updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                              FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE CASCADE
+);
+
+-- Every normalized email a participant has booked with; merged duplicates keep resolving to the survivor
+CREATE TABLE participant_emails (
+                                    tenant_id TEXT NOT NULL,
+                                    email TEXT NOT NULL,
+                                    participant_id TEXT NOT NULL,
+                                    PRIMARY KEY (tenant_id, email),
+                                    FOREIGN KEY (participant_id) REFERENCES participants(id) ON DELETE CASCADE
+);
+
+ALTER TABLE bookings ADD COLUMN participant_id TEXT REFERENCES participants(id) ON DELETE SET NULL;
+CREATE INDEX idx_bookings_participant ON bookings(participant_id);
+CREATE INDEX idx_participants_tenant ON participants(tenant_id);
+
+-- Backfill from existing bookings
+INSERT INTO participants (id, tenant_id, email, name, created_at, updated_at)
+SELECT lower(hex(randomblob(4)) || '-' || hex(randomblob(2)) || '-' || hex(randomblob(2)) || '-' || hex(randomblob(2)) || '-' || hex(randomblob(6))),
+       tenant_id, LOWER(TRIM(customer_email)), MAX(customer_name), MIN(created_at), MAX(created_at)
+FROM bookings
+GROUP BY tenant_id, LOWER(TRIM(customer_email));
+
+INSERT INTO participant_emails (tenant_id, email, participant_id)
+SELECT tenant_id, email, id FROM participants;
+
+UPDATE bookings SET participant_id = (
+    SELECT participant_id FROM participant_emails pe
+    WHERE pe.tenant_id = bookings.tenant_id AND pe.email = LOWER(TRIM(bookings.customer_email))
+);
@@ -0,0 +1,6 @@
+-- No-show policy: block self-service booking after too many no-shows (NULL threshold = disabled)
+ALTER TABLE tenants ADD COLUMN noshow_block_threshold INTEGER;
+ALTER TABLE tenants ADD COLUMN noshow_window_months INTEGER NOT NULL DEFAULT 12;
+ALTER TABLE tenants ADD COLUMN noshow_block_months INTEGER NOT NULL DEFAULT 6;
+
+ALTER TABLE participants ADD COLUMN noshow_exempt BOOLEAN NOT NULL DEFAULT FALSE;
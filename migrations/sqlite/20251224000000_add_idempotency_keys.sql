@@ -0,0 +1,12 @@
+-- Idempotency-Key reservations of public booking requests; the response is NULL while in progress
+CREATE TABLE idempotency_keys (
+                                  scope TEXT NOT NULL,
+                                  idempotency_key TEXT NOT NULL,
+                                  request_hash TEXT NOT NULL,
+                                  status_code INTEGER,
+                                  response_body TEXT,
+                                  created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                                  PRIMARY KEY (scope, idempotency_key)
+);
+
+CREATE INDEX idx_idempotency_keys_created_at ON idempotency_keys(created_at);
@@ -0,0 +1,6 @@
+-- How many active bookings one participant may hold: NONE, EMAIL (per event), INVITEE or SERIES
+ALTER TABLE events ADD COLUMN booking_limit TEXT NOT NULL DEFAULT 'NONE';
+-- Events sharing a series key count together for the SERIES limit
+ALTER TABLE events ADD COLUMN series_key TEXT;
+
+CREATE INDEX idx_bookings_event_participant ON bookings(event_id, participant_id);
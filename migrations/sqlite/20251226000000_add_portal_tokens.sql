@@ -0,0 +1,12 @@
+-- Short-lived magic links giving a participant access to all of their bookings
+CREATE TABLE portal_tokens (
+                               token TEXT PRIMARY KEY NOT NULL,
+                               tenant_id TEXT NOT NULL,
+                               participant_id TEXT NOT NULL,
+                               expires_at TIMESTAMPTZ NOT NULL,
+                               created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                               FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE CASCADE,
+                               FOREIGN KEY (participant_id) REFERENCES participants(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_portal_tokens_participant ON portal_tokens(tenant_id, participant_id);
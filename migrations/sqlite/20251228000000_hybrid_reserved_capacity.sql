@@ -0,0 +1,4 @@
+-- HYBRID events hold back seats of every slot for token holders
+ALTER TABLE events ADD COLUMN reserved_capacity INTEGER NOT NULL DEFAULT 0;
+-- Unclaimed reserved seats open to the public this many hours before the slot starts
+ALTER TABLE events ADD COLUMN reserved_release_hours INTEGER NOT NULL DEFAULT 48;
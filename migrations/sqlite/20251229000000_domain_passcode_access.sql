@@ -0,0 +1,5 @@
+-- DOMAIN events: comma-separated email domains allowed to book, and domains always refused
+ALTER TABLE events ADD COLUMN allowed_domains TEXT;
+ALTER TABLE events ADD COLUMN denied_domains TEXT;
+-- PASSCODE events: shared code required to view and book
+ALTER TABLE events ADD COLUMN passcode TEXT;
@@ -0,0 +1,7 @@
+-- Booking a stored response holds, so erasing its participant can drop the response
+ALTER TABLE idempotency_keys ADD COLUMN booking_id TEXT;
+
+CREATE INDEX idx_idempotency_keys_booking_id ON idempotency_keys(booking_id);
+
+UPDATE idempotency_keys SET booking_id = json_extract(response_body, '$.id')
+WHERE status_code BETWEEN 200 AND 299 AND json_valid(response_body);
@@ -1,6 +1,7 @@
-use crate::domain::models::event::WeekdayConfig;
+use crate::domain::models::{booking::BookingOverrides, event::WeekdayConfig};
+use crate::domain::services::session_pattern::SessionPattern;
 use chrono::{DateTime, Utc, NaiveDate};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize)]
 pub struct CreateTenantRequest {
@@ -14,6 +15,11 @@ pub struct UpdateTenantRequest {
     pub name: Option<String>,
     pub logo_url: Option<String>,
     pub ai_api_key: Option<String>,
+    pub show_label_id: Option<String>,
+    pub noshow_label_id: Option<String>,
+    pub noshow_block_threshold: Option<i32>,
+    pub noshow_window_months: Option<i32>,
+    pub noshow_block_months: Option<i32>,
 }
 
 #[derive(Deserialize)]
@@ -46,6 +52,13 @@ pub struct CreateEventRequest {
     pub schedule_type: Option<String>,
     pub allow_customer_cancel: Option<bool>,
     pub allow_customer_reschedule: Option<bool>,
+    pub booking_limit: Option<String>,
+    pub series_key: Option<String>,
+    pub reserved_capacity: Option<i32>,
+    pub reserved_release_hours: Option<i32>,
+    pub allowed_domains: Option<Vec<String>>,
+    pub denied_domains: Option<Vec<String>>,
+    pub passcode: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -72,20 +85,34 @@ pub struct UpdateEventRequest {
     pub schedule_type: Option<String>,
     pub allow_customer_cancel: Option<bool>,
     pub allow_customer_reschedule: Option<bool>,
+    pub booking_limit: Option<String>,
+    pub series_key: Option<String>,
+    pub reserved_capacity: Option<i32>,
+    pub reserved_release_hours: Option<i32>,
+    pub allowed_domains: Option<Vec<String>>,
+    pub denied_domains: Option<Vec<String>>,
+    pub passcode: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct CreateInviteeRequest {
     pub email: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub max_uses: Option<i32>,
+    /// Further events the token is valid for; required for tenant-wide tokens.
+    pub event_slugs: Option<Vec<String>>,
 }
 
 #[derive(Deserialize)]
 pub struct UpdateInviteeRequest {
-    pub status: String,
+    pub status: Option<String>,
     pub email: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub max_uses: Option<i32>,
+    pub event_slugs: Option<Vec<String>>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 pub struct CreateBookingRequest {
     pub date: String,
     pub time: String,
@@ -93,9 +120,25 @@ pub struct CreateBookingRequest {
     pub email: String,
     pub notes: Option<String>,
     pub token: Option<String>,
+    pub passcode: Option<String>,
+    /// Zone `date` and `time` are given in, the event's when missing.
+    pub timezone: Option<String>,
+    /// Purposes the participant agreed to in the booking form, e.g. the privacy notice.
+    #[serde(default)]
+    pub consents: Vec<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
+pub struct AdminBookingRequest {
+    #[serde(flatten)]
+    pub booking: CreateBookingRequest,
+    #[serde(default)]
+    pub overrides: BookingOverrides,
+    #[serde(default)]
+    pub suppress_confirmation: bool,
+}
+
+#[derive(Deserialize, Serialize)]
 pub struct RescheduleBookingRequest {
     pub date: String,
     pub time: String,
@@ -151,4 +194,124 @@ pub struct UpdateSessionRequest {
     pub max_participants: Option<i32>,
     pub location: Option<String>,
     pub host_name: Option<String>,
-}
\ No newline at end of file
+}
+
+#[derive(Deserialize)]
+pub struct CancelSessionRequest {
+    /// Shown to the participants in the cancellation email.
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BulkCreateSessionsRequest {
+    #[serde(flatten)]
+    pub pattern: SessionPattern,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Sessions picked by id, or all sessions starting on the local days `start_date` to `end_date`.
+#[derive(Deserialize)]
+pub struct SessionSelection {
+    pub session_ids: Option<Vec<String>>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+#[derive(Deserialize)]
+pub struct BulkDeleteSessionsRequest {
+    #[serde(flatten)]
+    pub selection: SessionSelection,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Deserialize)]
+pub struct BulkShiftSessionsRequest {
+    #[serde(flatten)]
+    pub selection: SessionSelection,
+    pub shift_minutes: i64,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Deserialize)]
+pub struct PayoutQuery {
+    pub start: Option<NaiveDate>,
+    pub end: Option<NaiveDate>,
+    pub event_id: Option<String>,
+    pub status: Option<String>,
+    pub group_by: Option<String>,
+    pub format: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SlotSearchQuery {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct MarkPayoutsPaidRequest {
+    pub booking_ids: Vec<String>,
+    pub payment_reference: String,
+    pub paid_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct BankDetailsRequest {
+    pub account_holder: String,
+    pub iban: String,
+    pub bic: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SepaExportRequest {
+    pub booking_ids: Vec<String>,
+    pub debtor_name: String,
+    pub debtor_iban: String,
+    pub debtor_bic: Option<String>,
+    pub execution_date: Option<NaiveDate>,
+}
+
+#[derive(Deserialize)]
+pub struct CheckInRequest {
+    pub code: String,
+}
+
+#[derive(Deserialize)]
+pub struct ParticipantQuery {
+    pub search: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateParticipantRequest {
+    pub name: Option<String>,
+    pub notes: Option<String>,
+    pub noshow_exempt: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct MergeParticipantsRequest {
+    pub source_ids: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct DataSubjectRequest {
+    pub email: String,
+    pub format: Option<String>,
+}
+
+/// Replaces the whole policy; omitted rules are disabled.
+#[derive(Deserialize)]
+pub struct UpdateRetentionPolicyRequest {
+    pub anonymize_after_months: Option<i32>,
+    pub mail_logs_after_months: Option<i32>,
+    pub invitees_after_months: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct PortalLinkRequest {
+    pub email: String,
+}
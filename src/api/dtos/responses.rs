@@ -1,4 +1,9 @@
 use serde::Serialize;
+use chrono::{DateTime, Utc};
+use crate::domain::models::{booking::Booking, participant::Participant, session::EventSession};
+use crate::domain::services::availability::SlotDetail;
+use crate::domain::services::participant::ParticipantStats;
+use crate::domain::services::payout::PayoutSummary;
 
 #[derive(Serialize)]
 pub struct TenantCreatedResponse {
@@ -10,5 +15,83 @@ pub struct TenantCreatedResponse {
 #[derive(Serialize)]
 pub struct SlotsResponse {
     pub date: String,
+    /// Zone of `date` and of the offsets in `slots`, the viewer's when one was requested.
+    pub timezone: String,
+    /// Start times of the bookable slots.
     pub slots: Vec<String>,
-}
\ No newline at end of file
+    /// Every slot of the day with its seats, location and host, fully booked ones included.
+    pub details: Vec<SlotDetail>,
+}
+
+#[derive(Serialize)]
+pub struct SlotSearchResult {
+    pub event_slug: String,
+    pub title_en: String,
+    pub title_de: String,
+    pub location: String,
+    pub payout: String,
+    pub timezone: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct PayoutReportResponse {
+    pub group_by: String,
+    pub totals: PayoutSummary,
+    pub groups: Vec<PayoutSummary>,
+}
+
+#[derive(Serialize)]
+pub struct ParticipantSummaryResponse {
+    #[serde(flatten)]
+    pub participant: Participant,
+    pub stats: ParticipantStats,
+}
+
+#[derive(Serialize)]
+pub struct ParticipantDetailResponse {
+    #[serde(flatten)]
+    pub participant: Participant,
+    pub emails: Vec<String>,
+    pub stats: ParticipantStats,
+    pub bookings: Vec<Booking>,
+}
+
+#[derive(Serialize)]
+pub struct PortalBookingResponse {
+    #[serde(flatten)]
+    pub booking: Booking,
+    pub event_title: String,
+    pub event_slug: String,
+    pub timezone: String,
+    pub can_cancel: bool,
+    pub can_reschedule: bool,
+}
+
+#[derive(Serialize)]
+pub struct PortalResponse {
+    pub name: String,
+    pub email: String,
+    pub expires_at: DateTime<Utc>,
+    pub upcoming: Vec<PortalBookingResponse>,
+    pub past: Vec<PortalBookingResponse>,
+}
+
+/// Outcome of a bulk session operation, or its preview when `dry_run` is set.
+#[derive(Serialize)]
+pub struct BulkSessionsResponse {
+    pub dry_run: bool,
+    /// Sessions created, moved or deleted.
+    pub sessions: Vec<EventSession>,
+    /// Selected sessions left alone because they have bookings.
+    pub skipped: Vec<EventSession>,
+    /// Sessions that would overlap another session, nothing is written while there are any.
+    pub conflicts: Vec<EventSession>,
+}
+
+#[derive(Serialize)]
+pub struct SessionCancelledResponse {
+    pub session: EventSession,
+    pub cancelled_bookings: Vec<Booking>,
+}
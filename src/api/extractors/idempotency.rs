@@ -0,0 +1,104 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::FromRequestParts,
+    http::{header, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use crate::state::AppState;
+use crate::domain::models::{booking::Booking, idempotency::IdempotencyRecord};
+use crate::domain::services::idempotency::MAX_KEY_LEN;
+use crate::error::AppError;
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Optional `Idempotency-Key` header, scoped to the method and path it was sent to.
+pub struct IdempotencyKey {
+    key: Option<String>,
+    scope: String,
+}
+
+impl FromRequestParts<Arc<AppState>> for IdempotencyKey {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let key = match parts.headers.get(IDEMPOTENCY_KEY_HEADER) {
+            Some(value) => {
+                let key = value.to_str()
+                    .map_err(|_| AppError::Validation("Invalid Idempotency-Key header".into()))?
+                    .trim();
+                if key.is_empty() || key.len() > MAX_KEY_LEN {
+                    return Err(AppError::Validation(format!("Idempotency-Key must be 1 to {} characters", MAX_KEY_LEN)));
+                }
+                Some(key.to_string())
+            }
+            None => None,
+        };
+
+        Ok(IdempotencyKey { key, scope: format!("{} {}", parts.method, parts.uri.path()) })
+    }
+}
+
+impl IdempotencyKey {
+    /// Runs `handler` once per key. Repeating the request returns the stored response,
+    /// reusing the key for a different request is rejected. Server errors are not
+    /// stored, so the request can be retried with the same key. A stored booking is
+    /// recorded with its id, so erasing its participant drops the response as well.
+    pub async fn run<F>(self, state: &AppState, request_hash: String, handler: F) -> Result<Response, AppError>
+    where
+        F: Future<Output = Result<Booking, AppError>>,
+    {
+        let Some(key) = self.key else {
+            return handler.await.map(|booking| Json(booking).into_response());
+        };
+
+        let record = IdempotencyRecord::new(self.scope, key, request_hash);
+        if let Some(existing) = state.idempotency_repo.reserve(&record).await? {
+            return replay(existing, &record.request_hash);
+        }
+
+        let result = handler.await;
+        let booking_id = result.as_ref().ok().map(|booking| booking.id.clone());
+        let response = result.map(Json).into_response();
+        let status = response.status();
+        if status.is_server_error() {
+            if let Err(e) = state.idempotency_repo.release(&record.scope, &record.idempotency_key).await {
+                error!("Failed to release idempotency key: {:?}", e);
+            }
+            return Ok(response);
+        }
+
+        let (parts, body) = response.into_parts();
+        let bytes = to_bytes(body, usize::MAX).await
+            .map_err(|e| AppError::InternalWithMsg(format!("Failed to buffer response: {}", e)))?;
+        let body = String::from_utf8_lossy(&bytes);
+        if let Err(e) = state.idempotency_repo.complete(&record.scope, &record.idempotency_key, status.as_u16() as i32, &body, booking_id.as_deref()).await {
+            error!("Failed to store idempotent response: {:?}", e);
+        }
+
+        Ok(Response::from_parts(parts, Body::from(bytes)))
+    }
+}
+
+fn replay(existing: IdempotencyRecord, request_hash: &str) -> Result<Response, AppError> {
+    if existing.request_hash != request_hash {
+        return Err(AppError::Conflict("Idempotency-Key was already used for a different request".into()));
+    }
+    let (Some(status_code), Some(body)) = (existing.status_code, existing.response_body) else {
+        return Err(AppError::Conflict("A request with this Idempotency-Key is still being processed".into()));
+    };
+
+    let status = u16::try_from(status_code).ok()
+        .and_then(|code| StatusCode::from_u16(code).ok())
+        .ok_or_else(|| AppError::InternalWithMsg(format!("Invalid stored status code {}", status_code)))?;
+    info!("Replaying idempotent response ({})", status);
+
+    Ok((
+        status,
+        [(header::CONTENT_TYPE, "application/json"), (header::HeaderName::from_static(IDEMPOTENT_REPLAYED_HEADER), "true")],
+        body,
+    ).into_response())
+}
@@ -1,3 +1,4 @@
 pub mod auth;
 pub mod tenant;
-pub mod maybe_auth;
\ No newline at end of file
+pub mod maybe_auth;
+pub mod idempotency;
\ No newline at end of file
@@ -1,26 +1,113 @@
 use axum::{extract::{State, Path}, response::IntoResponse, Json};
 use crate::state::AppState;
-use crate::api::extractors::{auth::AuthUser, tenant::TenantId};
-use crate::api::dtos::requests::{CreateBookingRequest, UpdateBookingRequest};
-use crate::domain::models::booking::{Booking, NewBookingParams};
+use crate::api::extractors::{auth::AuthUser, tenant::TenantId, idempotency::IdempotencyKey};
+use crate::api::dtos::requests::{AdminBookingRequest, CreateBookingRequest, UpdateBookingRequest};
+use crate::domain::models::audit::AuditLog;
+use crate::domain::models::booking::{Booking, BookingLimit, BookingOutcome, BookingOverrides, BookingWrites, NewBookingParams};
+use crate::domain::models::consent::ConsentRecord;
 use crate::domain::models::job::Job;
-use crate::domain::services::availability::calculate_slots;
+use crate::domain::models::participant::normalize_email;
+use crate::api::handlers::invitee::find_valid_invitee;
+use crate::domain::models::event::Event;
+use crate::domain::services::availability::{availability_span, calculate_range, calculate_slot_details, calculate_slots, free_slots, nearest_slots, window_date, Audience, ALTERNATIVE_SEARCH_DAYS, MAX_ALTERNATIVES};
+use crate::domain::services::{access, idempotency, participant::{self, NoShowPolicy}, privacy};
+use crate::domain::services::local_time::{self, Overlap};
 use crate::error::AppError;
 use std::sync::Arc;
-use chrono::{NaiveDate, NaiveTime, Utc, TimeZone, Duration};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Duration};
 use chrono_tz::Tz;
+use serde_json::json;
 use tracing::{info, warn};
 
+/// Repeated attempts within this window do not resend the existing booking's link.
+const DUPLICATE_NOTICE_COOLDOWN_HOURS: i64 = 1;
+
+/// Free slots of the event nearest to a taken one, searched on the same day and the following days.
+pub(crate) async fn alternative_slots(state: &AppState, event: &Event, date: NaiveDate, requested: DateTime<Utc>, audience: Audience) -> Result<Vec<String>, AppError> {
+    let end_date = date + Duration::days(ALTERNATIVE_SEARCH_DAYS);
+    let (range_start_utc, range_end_utc) = availability_span(event, date, end_date);
+
+    let all_bookings = state.booking_repo.list_by_range(&event.id, range_start_utc, range_end_utc).await?;
+    let overrides = if event.schedule_type == "MANUAL" {
+        vec![]
+    } else {
+        state.event_override_repo.list_by_range(&event.id, date, end_date).await?
+    };
+    let manual_sessions = if event.schedule_type == "MANUAL" {
+        Some(state.session_repo.list_by_range(&event.id, range_start_utc, range_end_utc).await?)
+    } else {
+        None
+    };
+
+    let slots: Vec<String> = calculate_range(event, date, end_date, &all_bookings, &overrides, manual_sessions.as_deref(), audience)
+        .into_iter()
+        .flat_map(|day| day.free_slots())
+        .collect();
+
+    Ok(nearest_slots(&slots, requested, MAX_ALTERNATIVES))
+}
+
 pub async fn create_booking(
     State(state): State<Arc<AppState>>,
     TenantId(tenant_id): TenantId,
     Path((_, slug)): Path<(String, String)>,
+    idempotency_key: IdempotencyKey,
     Json(payload): Json<CreateBookingRequest>,
 ) -> Result<impl IntoResponse, AppError> {
+    let request_hash = idempotency::request_hash(&payload);
+    let handler = book(&state, tenant_id, slug, payload, BookingContext::customer());
+    idempotency_key.run(&state, request_hash, handler).await
+}
+
+/// Books a participant by phone or in person. Overrides and suppressed
+/// confirmations are recorded in the audit log.
+pub async fn create_booking_on_behalf(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    user: AuthUser,
+    Path((_, slug)): Path<(String, String)>,
+    idempotency_key: IdempotencyKey,
+    Json(payload): Json<AdminBookingRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let request_hash = idempotency::request_hash(&payload);
+    let context = BookingContext {
+        staff: Some(user.0.username),
+        overrides: payload.overrides,
+        send_confirmation: !payload.suppress_confirmation,
+    };
+    let handler = book(&state, tenant_id, slug, payload.booking, context);
+    idempotency_key.run(&state, request_hash, handler).await
+}
+
+/// Who books and which event rules apply.
+struct BookingContext {
+    /// Username of the staff member booking on behalf of the participant, audited with the booking.
+    staff: Option<String>,
+    /// Rules to skip; customers always get the defaults.
+    overrides: BookingOverrides,
+    send_confirmation: bool,
+}
+
+impl BookingContext {
+    fn customer() -> Self {
+        Self { staff: None, overrides: BookingOverrides::default(), send_confirmation: true }
+    }
+}
+
+/// The booking pipeline shared by the public and the admin endpoint.
+async fn book(
+    state: &AppState,
+    tenant_id: String,
+    slug: String,
+    payload: CreateBookingRequest,
+    context: BookingContext,
+) -> Result<Booking, AppError> {
+    let overrides = context.overrides;
     info!("create_booking: Starting for slug {}", slug);
 
-    let event = state.event_repo.find_by_slug(&tenant_id, &slug).await?
+    let mut event = state.event_repo.find_by_slug(&tenant_id, &slug).await?
         .ok_or(AppError::NotFound("Event not found".into()))?;
+    overrides.apply(&mut event);
 
     info!("create_booking: Event found: {}", event.id);
 
@@ -29,47 +116,55 @@ pub async fn create_booking(
     let mut invitee_id = None;
     let mut token_to_burn = None;
 
-    match event.access_mode.as_str() {
-        "CLOSED" => return Err(AppError::Forbidden("Event is closed".into())),
-        "RESTRICTED" => {
-            let token = payload.token.as_ref()
-                .ok_or(AppError::Forbidden("Token required for restricted event".into()))?;
-
-            let invitee = state.invitee_repo.find_by_token(token).await?
-                .ok_or(AppError::Forbidden("Invalid token".into()))?;
-
-            if invitee.event_id != event.id {
-                return Err(AppError::Forbidden("Token invalid for this event".into()));
-            }
-            if invitee.status != "ACTIVE" {
-                return Err(AppError::Conflict("Token already used".into()));
-            }
-
-            invitee_id = Some(invitee.id);
-            token_to_burn = Some(token.clone());
-        },
-        "OPEN" => {}
-        _ => return Err(AppError::Internal),
+    if !overrides.access_mode {
+        match event.access_mode.as_str() {
+            "CLOSED" => return Err(AppError::Forbidden("Event is closed".into())),
+            "RESTRICTED" | "HYBRID" => {
+                if let Some(token) = &payload.token {
+                    let invitee = find_valid_invitee(state, token, &event.id).await?;
+                    invitee_id = Some(invitee.id);
+                    token_to_burn = Some(token.clone());
+                } else if event.access_mode == "RESTRICTED" {
+                    return Err(AppError::Forbidden("Token required for restricted event".into()));
+                }
+            },
+            "DOMAIN" => access::check_email_domain(&event, &payload.email)?,
+            "PASSCODE" => access::check_passcode(&event, payload.passcode.as_deref())?,
+            "OPEN" => {}
+            _ => return Err(AppError::Internal),
+        }
     }
 
-    let date = NaiveDate::parse_from_str(&payload.date, "%Y-%m-%d")
+    let mut date = NaiveDate::parse_from_str(&payload.date, "%Y-%m-%d")
         .map_err(|_| AppError::Validation("Invalid date format".into()))?;
+    let consent_purposes = privacy::consent_purposes(&payload.consents)?;
+
+    let viewer_tz = payload.timezone.as_deref()
+        .map(|name| name.parse::<Tz>().map_err(|_| AppError::Validation("Invalid timezone".into())))
+        .transpose()?;
 
-    let time = if payload.time.contains('T') {
-        let dt = chrono::DateTime::parse_from_rfc3339(&payload.time)
-            .map_err(|_| AppError::Validation("Invalid ISO time format".into()))?;
-        dt.with_timezone(&tz).time()
+    // A full timestamp also addresses slots of windows running past midnight
+    let start_time = if payload.time.contains('T') {
+        chrono::DateTime::parse_from_rfc3339(&payload.time)
+            .map_err(|_| AppError::Validation("Invalid ISO time format".into()))?
+            .with_timezone(&Utc)
     } else {
-        NaiveTime::parse_from_str(&payload.time, "%H:%M")
-            .map_err(|_| AppError::Validation("Invalid time format (HH:MM)".into()))?
-    };
+        let time = NaiveTime::parse_from_str(&payload.time, "%H:%M")
+            .map_err(|_| AppError::Validation("Invalid time format (HH:MM)".into()))?;
 
-    let naive_dt = date.and_time(time);
+        local_time::resolve(&viewer_tz.unwrap_or(tz), date.and_time(time), Overlap::Earliest)
+    };
 
-    let start_time = tz.from_local_datetime(&naive_dt)
-        .single()
-        .ok_or(AppError::Validation("Invalid local time (ambiguous or skipped due to DST)".into()))?
-        .with_timezone(&Utc);
+    // The viewer's date may differ from the event day whose window offers the slot
+    if viewer_tz.is_some() {
+        let previous = start_time.with_timezone(&tz).date_naive() - Duration::days(1);
+        let previous_override = if event.schedule_type == "MANUAL" {
+            None
+        } else {
+            state.event_override_repo.find_by_date(&event.id, previous).await?
+        };
+        date = window_date(&event, start_time, previous_override.as_ref());
+    }
 
     let mut end_time = start_time + Duration::minutes(event.duration_min as i64);
 
@@ -82,13 +177,8 @@ pub async fn create_booking(
 
     info!("create_booking: Checking availability for {} (UTC: {})", date, start_time);
 
-    let day_start_tz = tz.from_local_datetime(&date.and_hms_opt(0,0,0).unwrap()).single().unwrap();
-    let day_end_tz = tz.from_local_datetime(&date.and_hms_opt(23,59,59).unwrap()).single().unwrap();
-
-    let day_start_utc = day_start_tz.with_timezone(&Utc);
-    let day_end_utc = day_end_tz.with_timezone(&Utc);
-
-    let existing_bookings = state.booking_repo.list_by_range(&event.id, day_start_utc, day_end_utc).await?;
+    let (span_start_utc, span_end_utc) = availability_span(&event, date, date);
+    let existing_bookings = state.booking_repo.list_by_range(&event.id, span_start_utc, span_end_utc).await?;
 
     let override_rule = if event.schedule_type == "MANUAL" {
         None
@@ -102,7 +192,7 @@ pub async fn create_booking(
     }
 
     let manual_sessions = if event.schedule_type == "MANUAL" {
-        let sessions = state.session_repo.list_by_range(&event.id, day_start_utc, day_end_utc).await?;
+        let sessions = state.session_repo.list_by_range(&event.id, span_start_utc, span_end_utc).await?;
         if let Some(session) = sessions.iter().find(|s| s.start_time == start_time) {
             end_time = session.end_time;
         } else {
@@ -113,20 +203,41 @@ pub async fn create_booking(
         None
     };
 
-    let valid_slots_utc = calculate_slots(&event, date, &existing_bookings, override_rule.as_ref(), manual_sessions.as_deref());
+    let audience = if invitee_id.is_some() { Audience::Invited } else { Audience::Public };
+    let counted_bookings: &[Booking] = if overrides.capacity { &[] } else { &existing_bookings };
+    let details = calculate_slot_details(&event, date, counted_bookings, override_rule.as_ref(), manual_sessions.as_deref(), audience);
 
-    let requested_iso = start_time.to_rfc3339();
+    let Some(slot) = details.iter().find(|d| d.start_time == start_time && d.is_bookable()) else {
+        warn!("Booking rejected: Slot {} (UTC) not available. Valid slots: {:?}", start_time.to_rfc3339(), free_slots(&details));
+        return Err(AppError::SlotConflict {
+            message: "Selected time slot is not available or valid".into(),
+            alternatives: alternative_slots(state, &event, date, start_time, audience).await?,
+        });
+    };
 
-    if !valid_slots_utc.contains(&requested_iso) {
-        warn!("Booking rejected: Slot {} (UTC) not available. Valid slots: {:?}", requested_iso, valid_slots_utc);
-        return Err(AppError::Conflict("Selected time slot is not available or valid".into()));
+    // Only a location differing from the event's is stored, like the session and override ones
+    let location = (slot.location != event.location).then(|| slot.location.clone());
+
+    if !overrides.ignore_block && let Some(existing) = state.participant_repo.find_by_email(&tenant_id, &payload.email).await? {
+        let tenant = state.tenant_repo.find_by_id(&tenant_id).await?
+            .ok_or(AppError::NotFound("Tenant not found".into()))?;
+        let history = state.booking_repo.list_by_participant(&tenant_id, &existing.id).await?;
+
+        if let Some(until) = participant::booking_blocked_until(&existing, &history, &tenant, Utc::now()) {
+            // One notice per block, however often the public endpoint is called during it
+            let block_start = NoShowPolicy::from_tenant(&tenant).map_or(until, |policy| policy.block_extended_at(until));
+            let job = Job::new("BOOKING_BLOCKED", existing.id.clone(), tenant_id.clone(), Utc::now())
+                .with_data(json!({ "event_id": event.id, "blocked_until": until }));
+            state.job_repo.create_unless_recent(&job, block_start).await?;
+
+            warn!("Booking rejected: participant {} is blocked until {}", existing.id, until);
+            return Err(AppError::Forbidden(format!(
+                "Booking is blocked until {} due to repeated no-shows",
+                until.with_timezone(&tz).format("%Y-%m-%d")
+            )));
+        }
     }
 
-    let location = override_rule.and_then(|r| r.location)
-        .or_else(|| manual_sessions.as_ref().and_then(|s|
-            s.iter().find(|sess| sess.start_time == start_time).and_then(|sess| sess.location.clone())
-        ));
-
     let booking = Booking::new(NewBookingParams {
         tenant_id: tenant_id.clone(),
         event_id: event.id.clone(),
@@ -136,9 +247,20 @@ pub async fn create_booking(
         email: payload.email,
         note: payload.notes,
         invitee_id,
-        location
+        location,
+        // Linked inside the booking transaction, so a rejected booking registers nobody
+        participant_id: None,
     });
 
+    let consents = consent_purposes.into_iter()
+        .map(|purpose| ConsentRecord::new(tenant_id.clone(), booking.id.clone(), purpose))
+        .collect();
+    let audit = context.staff.map(|actor| AuditLog::new(tenant_id.clone(), actor, "BOOKING_ON_BEHALF", booking.id.clone(), json!({
+        "event_slug": slug,
+        "overrides": overrides,
+        "suppress_confirmation": !context.send_confirmation,
+    })));
+
     let mut jobs = Vec::new();
 
     let rules = state.communication_repo.get_rules_by_event(&event.id).await?;
@@ -146,7 +268,9 @@ pub async fn create_booking(
     for rule in rules {
         match rule.trigger_type.as_str() {
             "ON_BOOKING" => {
-                jobs.push(Job::new("CONFIRMATION", booking.id.clone(), tenant_id.clone(), Utc::now()));
+                if context.send_confirmation {
+                    jobs.push(Job::new("CONFIRMATION", booking.id.clone(), tenant_id.clone(), Utc::now()));
+                }
             },
             "REMINDER_24H" => {
                 let remind_at = booking.start_time - Duration::hours(24);
@@ -172,12 +296,34 @@ pub async fn create_booking(
         }
     }
 
+    // Bookings not checked in by the end of the session are labelled as no-show
+    jobs.push(Job::new("ATTENDANCE_CHECK", booking.id.clone(), tenant_id.clone(), booking.end_time));
+
+    let limit = BookingLimit::for_booking(&event, &booking);
+
     info!("create_booking: Inserting booking into DB...");
-    let created = state.booking_repo.create_with_token(&booking, token_to_burn, jobs).await?;
+    let created = match state.booking_repo.create_with_token(&booking, token_to_burn, BookingWrites { jobs, consents, audit }, limit.as_ref()).await? {
+        BookingOutcome::Created(created) => created,
+        BookingOutcome::Duplicate(existing) => {
+            // The management link only goes to the address the booking was made with, and at
+            // most once per cooldown however often the public endpoint is called
+            let job = Job::new("DUPLICATE_BOOKING", existing.id.clone(), tenant_id.clone(), Utc::now());
+            state.job_repo.create_unless_recent(&job, Utc::now() - Duration::hours(DUPLICATE_NOTICE_COOLDOWN_HOURS)).await?;
+
+            warn!("Booking rejected: {} limit reached, existing booking {}", event.booking_limit, existing.id);
+            return Err(AppError::Conflict(
+                "You already have a booking for this event. We have sent its management link to your email address.".into()
+            ));
+        }
+    };
     info!("create_booking: DB Insert success: {}", created.id);
+    if overrides.any() {
+        info!("Booking {} created on behalf with overrides {:?}", created.id, overrides);
+    }
+    state.availability_cache.invalidate_span(&tenant_id, &event, created.start_time, created.end_time);
 
     info!("Booking confirmed: {} for event {}", created.id, slug);
-    Ok(Json(created))
+    Ok(created)
 }
 
 pub async fn list_bookings(
@@ -222,9 +368,17 @@ pub async fn update_booking(
 ) -> Result<impl IntoResponse, AppError> {
     let mut booking = state.booking_repo.find_by_id(&tenant_id, &booking_id).await?
         .ok_or(AppError::NotFound("Booking not found".into()))?;
+    let original_start = booking.start_time;
+    let original_end = booking.end_time;
 
     if let Some(name) = payload.name { booking.customer_name = name; }
-    if let Some(email) = payload.email { booking.customer_email = email; }
+    if let Some(email) = payload.email {
+        if normalize_email(&email) != normalize_email(&booking.customer_email) {
+            let participant = state.participant_repo.find_or_create(&tenant_id, &email, &booking.customer_name).await?;
+            booking.participant_id = Some(participant.id);
+        }
+        booking.customer_email = email;
+    }
 
     if let Some(label_id) = payload.label_id {
         if label_id.is_empty() {
@@ -255,28 +409,19 @@ pub async fn update_booking(
         let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
             .map_err(|_| AppError::Validation("Invalid date".into()))?;
 
-        let time = if time_str.contains('T') {
-            let dt = chrono::DateTime::parse_from_rfc3339(&time_str)
-                .map_err(|_| AppError::Validation("Invalid ISO time".into()))?;
-            dt.with_timezone(&tz).time()
+        let new_start = if time_str.contains('T') {
+            chrono::DateTime::parse_from_rfc3339(&time_str)
+                .map_err(|_| AppError::Validation("Invalid ISO time".into()))?
+                .with_timezone(&Utc)
         } else {
-            NaiveTime::parse_from_str(&time_str, "%H:%M")
-                .map_err(|_| AppError::Validation("Invalid time".into()))?
-        };
-
-        let naive_dt = date.and_time(time);
-        let new_start = tz.from_local_datetime(&naive_dt)
-            .single()
-            .ok_or(AppError::Validation("Invalid local time".into()))?
-            .with_timezone(&Utc);
+            let time = NaiveTime::parse_from_str(&time_str, "%H:%M")
+                .map_err(|_| AppError::Validation("Invalid time".into()))?;
 
-        let day_start_tz = tz.from_local_datetime(&date.and_hms_opt(0,0,0).unwrap()).single().unwrap();
-        let day_end_tz = tz.from_local_datetime(&date.and_hms_opt(23,59,59).unwrap()).single().unwrap();
-
-        let day_start_utc = day_start_tz.with_timezone(&Utc);
-        let day_end_utc = day_end_tz.with_timezone(&Utc);
+            local_time::resolve(&tz, date.and_time(time), Overlap::Earliest)
+        };
 
-        let existing_bookings = state.booking_repo.list_by_range(&event.id, day_start_utc, day_end_utc).await?;
+        let (span_start_utc, span_end_utc) = availability_span(&event, date, date);
+        let existing_bookings = state.booking_repo.list_by_range(&event.id, span_start_utc, span_end_utc).await?;
 
         let override_rule = if event.schedule_type == "MANUAL" {
             None
@@ -285,16 +430,20 @@ pub async fn update_booking(
         };
 
         let manual_sessions = if event.schedule_type == "MANUAL" {
-            Some(state.session_repo.list_by_range(&event.id, day_start_utc, day_end_utc).await?)
+            Some(state.session_repo.list_by_range(&event.id, span_start_utc, span_end_utc).await?)
         } else {
             None
         };
 
-        let valid_slots_utc = calculate_slots(&event, date, &existing_bookings, override_rule.as_ref(), manual_sessions.as_deref());
+        let audience = Audience::for_booking(&booking);
+        let valid_slots_utc = calculate_slots(&event, date, &existing_bookings, override_rule.as_ref(), manual_sessions.as_deref(), audience);
         let requested_iso = new_start.to_rfc3339();
 
         if !valid_slots_utc.contains(&requested_iso) {
-            return Err(AppError::Conflict("Target slot is unavailable or invalid".into()));
+            return Err(AppError::SlotConflict {
+                message: "Target slot is unavailable or invalid".into(),
+                alternatives: alternative_slots(&state, &event, date, new_start, audience).await?,
+            });
         }
 
         let mut new_end = new_start + Duration::minutes(event.duration_min as i64);
@@ -307,7 +456,20 @@ pub async fn update_booking(
         booking.end_time = new_end;
     }
 
+    let rescheduled = booking.end_time != original_end;
     let updated = state.booking_repo.update(&booking).await?;
+
+    if rescheduled {
+        // Stale attendance checks are ignored by the worker, so only the new one is needed
+        let job = Job::new("ATTENDANCE_CHECK", updated.id.clone(), tenant_id.clone(), updated.end_time);
+        state.job_repo.create(&job).await?;
+
+        if let Some(event) = state.event_repo.find_by_id(&tenant_id, &updated.event_id).await? {
+            state.availability_cache.invalidate_span(&tenant_id, &event, original_start, original_end);
+            state.availability_cache.invalidate_span(&tenant_id, &event, updated.start_time, updated.end_time);
+        }
+    }
+
     info!("Booking updated: {}", updated.id);
     Ok(Json(updated))
 }
@@ -318,7 +480,12 @@ pub async fn delete_booking(
     _user: AuthUser,
     Path((_, booking_id)): Path<(String, String)>,
 ) -> Result<impl IntoResponse, AppError> {
+    let booking = state.booking_repo.find_by_id(&tenant_id, &booking_id).await?;
     state.booking_repo.delete(&tenant_id, &booking_id).await?;
+    if let Some(booking) = booking
+        && let Some(event) = state.event_repo.find_by_id(&tenant_id, &booking.event_id).await? {
+        state.availability_cache.invalidate_span(&tenant_id, &event, booking.start_time, booking.end_time);
+    }
     info!("Booking cancelled: {}", booking_id);
     Ok(Json(serde_json::json!({"status": "cancelled"})))
 }
\ No newline at end of file
@@ -1,11 +1,16 @@
 use axum::{extract::{State, Path}, response::IntoResponse, Json};
 use crate::state::AppState;
-use crate::api::dtos::requests::RescheduleBookingRequest;
-use crate::domain::services::availability::calculate_slots;
-use crate::domain::models::job::Job;
+use crate::api::dtos::requests::{RescheduleBookingRequest, BankDetailsRequest};
+use crate::api::extractors::idempotency::IdempotencyKey;
+use crate::api::handlers::booking::alternative_slots;
+use crate::domain::services::availability::{availability_span, calculate_slots, Audience};
+use crate::domain::models::{booking::Booking, job::Job, payout::BankDetails};
+use crate::domain::services::{checkin, idempotency, sepa};
+use crate::domain::services::local_time::{self, Overlap};
+use crate::infra::crypto::FieldCipher;
 use crate::error::AppError;
 use std::sync::Arc;
-use chrono::{NaiveDate, NaiveTime, Utc, TimeZone, Duration};
+use chrono::{NaiveDate, NaiveTime, Utc, Duration};
 use chrono_tz::Tz;
 use tracing::info;
 
@@ -23,9 +28,15 @@ pub async fn get_booking_by_token(
     let event = state.event_repo.find_by_id(&booking.tenant_id, &booking.event_id).await?
         .ok_or(AppError::Internal)?;
 
+    let bank_details = state.payout_repo.find_bank_details(&booking.id).await?;
+
+    let checkin_code = checkin::sign_code(&state.config.checkin_secret, &booking.id);
+
     let response = serde_json::json!({
         "booking": booking,
-        "event": event
+        "event": event,
+        "bank_details": bank_details,
+        "checkin_code": checkin_code
     });
 
     Ok(Json(response))
@@ -34,12 +45,23 @@ pub async fn get_booking_by_token(
 pub async fn cancel_booking(
     State(state): State<Arc<AppState>>,
     Path(token): Path<String>,
+    idempotency_key: IdempotencyKey,
 ) -> Result<impl IntoResponse, AppError> {
+    let request_hash = idempotency::request_hash(&());
+    idempotency_key.run(&state, request_hash, cancel(&state, token)).await
+}
+
+async fn cancel(state: &AppState, token: String) -> Result<Booking, AppError> {
     let booking = state.booking_repo.find_by_token(&token).await?
         .ok_or(AppError::NotFound("Booking not found".into()))?;
 
+    cancel_for_customer(state, booking).await
+}
+
+/// Cancels a booking on behalf of its participant, respecting the event's cancellation policy.
+pub(crate) async fn cancel_for_customer(state: &AppState, booking: Booking) -> Result<Booking, AppError> {
     if booking.status == "CANCELLED" {
-        return Ok(Json(booking));
+        return Ok(booking);
     }
 
     let event = state.event_repo.find_by_id(&booking.tenant_id, &booking.event_id).await?
@@ -50,6 +72,7 @@ pub async fn cancel_booking(
     }
 
     let cancelled = state.booking_repo.cancel(&booking).await?;
+    state.availability_cache.invalidate_span(&booking.tenant_id, &event, booking.start_time, booking.end_time);
     info!("Booking cancelled via management token: {}", booking.id);
 
     state.job_repo.cancel_jobs_for_booking(&booking.id).await?;
@@ -62,17 +85,29 @@ pub async fn cancel_booking(
         }
     }
 
-    Ok(Json(cancelled))
+    Ok(cancelled)
 }
 
 pub async fn reschedule_booking(
     State(state): State<Arc<AppState>>,
     Path(token): Path<String>,
+    idempotency_key: IdempotencyKey,
     Json(payload): Json<RescheduleBookingRequest>,
 ) -> Result<impl IntoResponse, AppError> {
+    let request_hash = idempotency::request_hash(&payload);
+    idempotency_key.run(&state, request_hash, reschedule(&state, token, payload)).await
+}
+
+async fn reschedule(state: &AppState, token: String, payload: RescheduleBookingRequest) -> Result<Booking, AppError> {
     let booking = state.booking_repo.find_by_token(&token).await?
         .ok_or(AppError::NotFound("Booking not found".into()))?;
 
+    reschedule_for_customer(state, booking, payload).await
+}
+
+/// Moves a booking to another available slot on behalf of its participant,
+/// respecting the event's rescheduling policy.
+pub(crate) async fn reschedule_for_customer(state: &AppState, booking: Booking, payload: RescheduleBookingRequest) -> Result<Booking, AppError> {
     if booking.status == "CANCELLED" {
         return Err(AppError::Validation("Cannot reschedule a cancelled booking.".into()));
     }
@@ -88,25 +123,19 @@ pub async fn reschedule_booking(
     let date = NaiveDate::parse_from_str(&payload.date, "%Y-%m-%d")
         .map_err(|_| AppError::Validation("Invalid date".into()))?;
 
-    let time = if payload.time.contains('T') {
-        let dt = chrono::DateTime::parse_from_rfc3339(&payload.time)
-            .map_err(|_| AppError::Validation("Invalid ISO time".into()))?;
-        dt.with_timezone(&tz).time()
+    let new_start = if payload.time.contains('T') {
+        chrono::DateTime::parse_from_rfc3339(&payload.time)
+            .map_err(|_| AppError::Validation("Invalid ISO time".into()))?
+            .with_timezone(&Utc)
     } else {
-        NaiveTime::parse_from_str(&payload.time, "%H:%M")
-            .map_err(|_| AppError::Validation("Invalid time".into()))?
+        let time = NaiveTime::parse_from_str(&payload.time, "%H:%M")
+            .map_err(|_| AppError::Validation("Invalid time".into()))?;
+        local_time::resolve(&tz, date.and_time(time), Overlap::Earliest)
     };
-
-    let naive_dt = date.and_time(time);
-    let new_start = tz.from_local_datetime(&naive_dt).single().unwrap().with_timezone(&Utc);
     let mut new_end = new_start + Duration::minutes(event.duration_min as i64);
 
-    let day_start_tz = tz.from_local_datetime(&date.and_hms_opt(0,0,0).unwrap()).single().unwrap();
-    let day_end_tz = tz.from_local_datetime(&date.and_hms_opt(23,59,59).unwrap()).single().unwrap();
-    let day_start_utc = day_start_tz.with_timezone(&Utc);
-    let day_end_utc = day_end_tz.with_timezone(&Utc);
-
-    let existing_bookings = state.booking_repo.list_by_range(&event.id, day_start_utc, day_end_utc).await?;
+    let (span_start_utc, span_end_utc) = availability_span(&event, date, date);
+    let existing_bookings = state.booking_repo.list_by_range(&event.id, span_start_utc, span_end_utc).await?;
     let override_rule = if event.schedule_type == "MANUAL" {
         None
     } else {
@@ -117,7 +146,7 @@ pub async fn reschedule_booking(
     }
 
     let manual_sessions = if event.schedule_type == "MANUAL" {
-        let sessions = state.session_repo.list_by_range(&event.id, day_start_utc, day_end_utc).await?;
+        let sessions = state.session_repo.list_by_range(&event.id, span_start_utc, span_end_utc).await?;
         if let Some(session) = sessions.iter().find(|s| s.start_time == new_start) {
             new_end = session.end_time;
         } else {
@@ -128,9 +157,13 @@ pub async fn reschedule_booking(
         None
     };
 
-    let valid_slots = calculate_slots(&event, date, &existing_bookings, override_rule.as_ref(), manual_sessions.as_deref());
+    let audience = Audience::for_booking(&booking);
+    let valid_slots = calculate_slots(&event, date, &existing_bookings, override_rule.as_ref(), manual_sessions.as_deref(), audience);
     if !valid_slots.contains(&new_start.to_rfc3339()) {
-        return Err(AppError::Conflict("New slot is not available.".into()));
+        return Err(AppError::SlotConflict {
+            message: "New slot is not available.".into(),
+            alternatives: alternative_slots(state, &event, date, new_start, audience).await?,
+        });
     }
 
     let location = override_rule.and_then(|r| r.location)
@@ -144,6 +177,8 @@ pub async fn reschedule_booking(
     booking_to_update.location = location;
 
     let updated = state.booking_repo.update(&booking_to_update).await?;
+    state.availability_cache.invalidate_span(&updated.tenant_id, &event, booking.start_time, booking.end_time);
+    state.availability_cache.invalidate_span(&updated.tenant_id, &event, updated.start_time, updated.end_time);
 
     // Cancel old reminders
     state.job_repo.cancel_jobs_for_booking(&updated.id).await?;
@@ -155,6 +190,9 @@ pub async fn reschedule_booking(
         state.job_repo.create(&job).await?;
     }
 
+    let job = Job::new("ATTENDANCE_CHECK", updated.id.clone(), updated.tenant_id.clone(), updated.end_time);
+    state.job_repo.create(&job).await?;
+
     // Schedule New Reminders
     let reminder_rules = state.communication_repo.get_rules_by_event(&event.id).await?;
     for rule in reminder_rules {
@@ -177,5 +215,49 @@ pub async fn reschedule_booking(
     }
 
     info!("Rescheduled booking {}", updated.id);
-    Ok(Json(updated))
-}
\ No newline at end of file
+    Ok(updated)
+}
+
+pub async fn update_bank_details(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+    Json(payload): Json<BankDetailsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking = state.booking_repo.find_by_token(&token).await?
+        .ok_or(AppError::NotFound("Booking not found".into()))?;
+
+    if booking.status == "CANCELLED" {
+        return Err(AppError::Validation("Booking is already cancelled".into()));
+    }
+
+    let cipher = FieldCipher::from_config(&state.config)?;
+
+    let account_holder = payload.account_holder.trim().to_string();
+    if account_holder.is_empty() {
+        return Err(AppError::Validation("Account holder is required".into()));
+    }
+
+    let iban = sepa::normalize(&payload.iban);
+    if !sepa::is_valid_iban(&iban) {
+        return Err(AppError::Validation("Invalid IBAN".into()));
+    }
+
+    let bic = payload.bic.as_deref().map(sepa::normalize).filter(|b| !b.is_empty());
+    if let Some(ref bic) = bic && !sepa::is_valid_bic(bic) {
+        return Err(AppError::Validation("Invalid BIC".into()));
+    }
+
+    let details = BankDetails {
+        booking_id: booking.id.clone(),
+        tenant_id: booking.tenant_id.clone(),
+        account_holder,
+        iban_encrypted: cipher.encrypt(&iban)?,
+        bic_encrypted: bic.map(|b| cipher.encrypt(&b)).transpose()?,
+        iban_last4: iban[iban.len() - 4..].to_string(),
+        updated_at: Utc::now(),
+    };
+
+    let saved = state.payout_repo.upsert_bank_details(&details).await?;
+    info!("Bank details stored for booking {}", booking.id);
+    Ok(Json(saved))
+}
@@ -0,0 +1,48 @@
+use axum::{extract::State, response::IntoResponse, Json};
+use crate::state::AppState;
+use crate::api::extractors::{auth::AuthUser, tenant::TenantId};
+use crate::api::dtos::requests::CheckInRequest;
+use crate::domain::services::checkin;
+use crate::error::AppError;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::info;
+
+pub async fn check_in(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Json(payload): Json<CheckInRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_id = checkin::verify_code(&state.config.checkin_secret, &payload.code)
+        .ok_or(AppError::Validation("Invalid check-in code".into()))?;
+
+    let booking = state.booking_repo.find_by_id(&tenant_id, &booking_id).await?
+        .ok_or(AppError::NotFound("Booking not found".into()))?;
+
+    if booking.status == "CANCELLED" {
+        return Err(AppError::Conflict("Booking is cancelled".into()));
+    }
+
+    let tenant = state.tenant_repo.find_by_id(&tenant_id).await?
+        .ok_or(AppError::NotFound("Tenant not found".into()))?;
+
+    // Scanning the same code twice is harmless and reports the original check-in
+    match state.booking_repo.check_in(&tenant_id, &booking.id, Utc::now(), tenant.show_label_id.as_deref()).await? {
+        Some(updated) => {
+            info!("Checked in booking {}", updated.id);
+            Ok(Json(serde_json::json!({
+                "booking": updated,
+                "already_checked_in": false
+            })))
+        }
+        None => {
+            let current = state.booking_repo.find_by_id(&tenant_id, &booking.id).await?
+                .ok_or(AppError::NotFound("Booking not found".into()))?;
+            Ok(Json(serde_json::json!({
+                "booking": current,
+                "already_checked_in": true
+            })))
+        }
+    }
+}
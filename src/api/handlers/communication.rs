@@ -233,6 +233,11 @@ pub async fn get_placeholders() -> impl IntoResponse {
         TemplatePlaceholder { key: "token".to_string(), description: "Invitee Token".to_string(), sample_value: "abc-123-xyz".to_string() },
         TemplatePlaceholder { key: "book_link".to_string(), description: "Direct booking link (Invite)".to_string(), sample_value: "https://example.com/book?token=abc".to_string() },
         TemplatePlaceholder { key: "booking_link".to_string(), description: "Alias for book_link".to_string(), sample_value: "https://example.com/book?token=abc".to_string() },
+        TemplatePlaceholder { key: "checkin_code".to_string(), description: "Signed check-in code".to_string(), sample_value: "0b5c7e1a-3f2d-4c8b-9a61-2d4e8f7c1b90.4f2a9c1e7b3d5a8f6e0c2b4d".to_string() },
+        TemplatePlaceholder { key: "checkin_qr".to_string(), description: "Check-in QR code as HTML (use with | safe)".to_string(), sample_value: "<table>...</table>".to_string() },
+        TemplatePlaceholder { key: "blocked_until".to_string(), description: "End of a no-show booking block".to_string(), sample_value: "2024-04-15".to_string() },
+        TemplatePlaceholder { key: "portal_link".to_string(), description: "Magic link to all bookings of the participant".to_string(), sample_value: "https://example.com/portal/abc".to_string() },
+        TemplatePlaceholder { key: "expires_at".to_string(), description: "Expiry of the portal link".to_string(), sample_value: "2023-10-15 14:30".to_string() },
     ];
     Json(placeholders)
 }
@@ -5,16 +5,62 @@ use crate::api::dtos::{
     requests::{CreateEventRequest, UpdateEventRequest},
     responses::SlotsResponse
 };
-use crate::domain::models::{event::Event, communication::{EmailTemplate, NotificationRule, EmailTemplateVersion}};
-use crate::domain::services::{availability::calculate_slots, defaults};
+use crate::domain::models::{event::Event, booking::{Booking, BOOKING_LIMITS}, communication::{EmailTemplate, NotificationRule, EmailTemplateVersion}, job::Job};
+use crate::api::handlers::invitee::find_valid_invitee;
+use crate::domain::services::{access, availability::{availability_span, calculate_range, calculate_slot_details, event_days, free_slots, sort_details, Audience, DayAvailability, SlotDetail}, defaults, event_changes::{self, FieldChange, Venue}, payout};
 use crate::error::AppError;
 use std::sync::Arc;
 use uuid::Uuid;
-use chrono::{Utc, NaiveDate, Duration, TimeZone};
+use chrono::{Utc, NaiveDate};
 use chrono_tz::Tz;
 use tracing::info;
 use std::collections::HashMap;
 
+fn normalize_series_key(series_key: Option<String>) -> Option<String> {
+    series_key.map(|k| k.trim().to_string()).filter(|k| !k.is_empty())
+}
+
+fn validate_booking_limit(booking_limit: &str, series_key: &Option<String>) -> Result<(), AppError> {
+    if !BOOKING_LIMITS.contains(&booking_limit) {
+        return Err(AppError::Validation("Invalid booking_limit".into()));
+    }
+    if booking_limit == "SERIES" && series_key.is_none() {
+        return Err(AppError::Validation("booking_limit SERIES requires a series_key".into()));
+    }
+    Ok(())
+}
+
+fn validate_payout(payout: &str) -> Result<(), AppError> {
+    if payout::parse_amount(payout).is_none() {
+        return Err(AppError::Validation("Payout must be a single amount in whole euros".into()));
+    }
+    Ok(())
+}
+
+fn validate_access_mode(access_mode: &str) -> Result<(), AppError> {
+    match access_mode {
+        "OPEN" | "RESTRICTED" | "HYBRID" | "DOMAIN" | "PASSCODE" | "CLOSED" => Ok(()),
+        _ => Err(AppError::Validation("Invalid access_mode".into()))
+    }
+}
+
+fn normalize_passcode(passcode: Option<String>) -> Option<String> {
+    passcode.map(|p| p.trim().to_string()).filter(|p| !p.is_empty())
+}
+
+fn validate_access_settings(event: &Event) -> Result<(), AppError> {
+    if event.reserved_capacity < 0 || event.reserved_release_hours < 0 {
+        return Err(AppError::Validation("reserved_capacity and reserved_release_hours must not be negative".into()));
+    }
+    if event.access_mode == "HYBRID" && event.reserved_capacity > event.max_participants {
+        return Err(AppError::Validation("reserved_capacity must not exceed max_participants".into()));
+    }
+    if event.access_mode == "PASSCODE" && event.passcode.is_none() {
+        return Err(AppError::Validation("access_mode PASSCODE requires a passcode".into()));
+    }
+    Ok(())
+}
+
 pub async fn create_event(
     State(state): State<Arc<AppState>>,
     TenantId(tenant_id): TenantId,
@@ -23,10 +69,8 @@ pub async fn create_event(
 ) -> Result<impl IntoResponse, AppError> {
     info!("Creating event: {} for tenant: {}", payload.slug, tenant_id);
 
-    match payload.access_mode.as_str() {
-        "OPEN" | "RESTRICTED" | "CLOSED" => {},
-        _ => return Err(AppError::Validation("Invalid access_mode".into()))
-    }
+    validate_access_mode(&payload.access_mode)?;
+    validate_payout(&payload.payout)?;
 
     let schedule_type = payload.schedule_type.unwrap_or_else(|| "RECURRING".to_string());
     match schedule_type.as_str() {
@@ -45,6 +89,10 @@ pub async fn create_event(
     let config_json = serde_json::to_string(&payload.config)
         .map_err(|_| AppError::Validation("Invalid config JSON".into()))?;
 
+    let booking_limit = payload.booking_limit.unwrap_or_else(|| "NONE".to_string());
+    let series_key = normalize_series_key(payload.series_key);
+    validate_booking_limit(&booking_limit, &series_key)?;
+
     let event = Event {
         id: Uuid::new_v4().to_string(),
         tenant_id: tenant_id.clone(),
@@ -70,8 +118,16 @@ pub async fn create_event(
         schedule_type,
         allow_customer_cancel: payload.allow_customer_cancel.unwrap_or(true),
         allow_customer_reschedule: payload.allow_customer_reschedule.unwrap_or(true),
+        booking_limit,
+        series_key,
+        reserved_capacity: payload.reserved_capacity.unwrap_or(0),
+        reserved_release_hours: payload.reserved_release_hours.unwrap_or(48),
+        allowed_domains: payload.allowed_domains.map(access::normalize_domains).transpose()?.flatten(),
+        denied_domains: payload.denied_domains.map(access::normalize_domains).transpose()?.flatten(),
+        passcode: normalize_passcode(payload.passcode),
         created_at: Utc::now(),
     };
+    validate_access_settings(&event)?;
 
     let created_event = state.event_repo.create(&event).await?;
 
@@ -81,6 +137,10 @@ pub async fn create_event(
         ("Cancellation", defaults::DEFAULT_CANCELLATION_SUBJECT, defaults::get_default_template("cancellation"), Some("ON_CANCEL")),
         ("Reschedule", defaults::DEFAULT_RESCHEDULE_SUBJECT, defaults::get_default_template("reschedule"), Some("ON_RESCHEDULE")),
         ("Invitation", defaults::DEFAULT_INVITATION_SUBJECT, defaults::get_default_template("invitation"), None),
+        ("Booking Blocked", defaults::DEFAULT_BOOKING_BLOCKED_SUBJECT, defaults::get_default_template("booking_blocked"), Some("ON_BOOKING_BLOCKED")),
+        ("Duplicate Booking", defaults::DEFAULT_DUPLICATE_BOOKING_SUBJECT, defaults::get_default_template("duplicate_booking"), Some("ON_DUPLICATE_BOOKING")),
+        ("Session Cancelled", defaults::DEFAULT_SESSION_CANCELLED_SUBJECT, defaults::get_default_template("session_cancelled"), Some("ON_SESSION_CANCELLED")),
+        ("Event Updated", defaults::DEFAULT_EVENT_UPDATED_SUBJECT, defaults::get_default_template("event_updated"), Some("ON_EVENT_UPDATED")),
     ];
 
     for (suffix, subj, body, trigger_opt) in templates_to_create {
@@ -130,6 +190,7 @@ pub async fn update_event(
 ) -> Result<impl IntoResponse, AppError> {
     let mut event = state.event_repo.find_by_slug(&tenant_id, &slug).await?
         .ok_or(AppError::NotFound("Event not found".into()))?;
+    let previous = event.clone();
 
     if let Some(val) = payload.slug { event.slug = val; }
     if let Some(val) = payload.title_en { event.title_en = val; }
@@ -137,7 +198,13 @@ pub async fn update_event(
     if let Some(val) = payload.desc_en { event.desc_en = val; }
     if let Some(val) = payload.desc_de { event.desc_de = val; }
     if let Some(val) = payload.location { event.location = val; }
-    if let Some(val) = payload.payout { event.payout = val; }
+    if let Some(val) = payload.payout {
+        // Events from before the check keep their text until it is changed
+        if val != event.payout {
+            validate_payout(&val)?;
+        }
+        event.payout = val;
+    }
     if let Some(val) = payload.host_name { event.host_name = val; }
     if let Some(val) = payload.timezone {
         if val.parse::<Tz>().is_err() {
@@ -153,20 +220,85 @@ pub async fn update_event(
     if let Some(val) = payload.interval_min { event.interval_min = val; }
     if let Some(val) = payload.max_participants { event.max_participants = val; }
     if let Some(val) = payload.image_url { event.image_url = val; }
-    if let Some(val) = payload.access_mode { event.access_mode = val; }
+    if let Some(val) = payload.access_mode {
+        validate_access_mode(&val)?;
+        event.access_mode = val;
+    }
     if let Some(val) = payload.schedule_type { event.schedule_type = val; }
     if let Some(val) = payload.allow_customer_cancel { event.allow_customer_cancel = val; }
     if let Some(val) = payload.allow_customer_reschedule { event.allow_customer_reschedule = val; }
+    if let Some(val) = payload.booking_limit { event.booking_limit = val; }
+    if payload.series_key.is_some() { event.series_key = normalize_series_key(payload.series_key); }
+    validate_booking_limit(&event.booking_limit, &event.series_key)?;
+    if let Some(val) = payload.reserved_capacity { event.reserved_capacity = val; }
+    if let Some(val) = payload.reserved_release_hours { event.reserved_release_hours = val; }
+    if let Some(val) = payload.allowed_domains { event.allowed_domains = access::normalize_domains(val)?; }
+    if let Some(val) = payload.denied_domains { event.denied_domains = access::normalize_domains(val)?; }
+    if payload.passcode.is_some() { event.passcode = normalize_passcode(payload.passcode); }
+    validate_access_settings(&event)?;
     if let Some(val) = payload.config {
         event.config_json = serde_json::to_string(&val)
             .map_err(|_| AppError::Validation("Invalid config".into()))?;
     }
 
-    let updated = state.event_repo.update(&event).await?;
+    let changes = event_changes::event_changes(&previous, &event);
+    let mut jobs = Vec::new();
+    if !changes.is_empty() {
+        for booking in state.booking_repo.find_future_active_bookings(&event.id).await? {
+            jobs.extend(event_updated_job(&booking, event_changes::affecting(&changes, &booking)));
+        }
+    }
+    // The notices are only queued along with the change they announce
+    let updated = state.event_repo.update_with_notices(&event, &jobs).await?;
+    state.availability_cache.invalidate_event(&tenant_id, &updated.id);
     info!("Event updated: {}", slug);
     Ok(Json(updated))
 }
 
+/// The notice telling the participant of `booking` about `changes`, with an updated calendar entry.
+fn event_updated_job(booking: &Booking, changes: Vec<FieldChange>) -> Option<Job> {
+    if changes.is_empty() {
+        return None;
+    }
+    Some(Job::new("EVENT_UPDATED", booking.id.clone(), booking.tenant_id.clone(), Utc::now())
+        .with_data(serde_json::json!({ "changes": changes })))
+}
+
+/// Queues the notice telling the participant of `booking` about `changes`.
+async fn notify_event_updated(state: &AppState, booking: &Booking, changes: Vec<FieldChange>) -> Result<(), AppError> {
+    let Some(job) = event_updated_job(booking, changes) else {
+        return Ok(());
+    };
+    state.booking_repo.next_revision(&booking.tenant_id, &booking.id).await?;
+    state.job_repo.create(&job).await?;
+    Ok(())
+}
+
+/// Moves the future bookings among `bookings` made at the `old` venue of a day or session to
+/// the `new` one and notifies them.
+pub(crate) async fn notify_venue_change(state: &AppState, event: &Event, bookings: Vec<Booking>, old: &Venue, new: &Venue) -> Result<(), AppError> {
+    let changes = event_changes::venue_changes(event, old, new);
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let from = event_changes::booked_location(event, old.location.as_deref());
+    let to = event_changes::booked_location(event, new.location.as_deref());
+    let now = Utc::now();
+
+    for mut booking in bookings {
+        // Bookings at a window location of their own stay where they are
+        if booking.start_time <= now || booking.location != from {
+            continue;
+        }
+        if from != to {
+            booking.location = to.clone();
+            booking = state.booking_repo.update(&booking).await?;
+        }
+        notify_event_updated(state, &booking, changes.clone()).await?;
+    }
+    Ok(())
+}
+
 pub async fn delete_event(
     State(state): State<Arc<AppState>>,
     TenantId(tenant_id): TenantId,
@@ -177,6 +309,7 @@ pub async fn delete_event(
         .ok_or(AppError::NotFound("Event not found".into()))?;
 
     state.event_repo.delete(&tenant_id, &event.id).await?;
+    state.availability_cache.invalidate_event(&tenant_id, &event.id);
     info!("Event deleted: {}", slug);
     Ok(Json(serde_json::json!({"status": "deleted"})))
 }
@@ -192,7 +325,10 @@ pub async fn get_event(
         .ok_or_else(|| AppError::NotFound(format!("Event '{}' not found", slug)))?;
 
     if maybe_user.is_some() {
-        return Ok(Json(serde_json::to_value(event).unwrap()));
+        // The passcode is left out of every other response
+        let mut event_json = serde_json::to_value(&event).map_err(|_| AppError::Internal)?;
+        event_json["passcode"] = serde_json::json!(event.passcode);
+        return Ok(Json(event_json));
     }
 
     let mut invitee_email = None;
@@ -201,21 +337,16 @@ pub async fn get_event(
         "CLOSED" => {
             return Err(AppError::Forbidden("This event is closed.".into()));
         },
-        "RESTRICTED" => {
-            let token = params.get("token")
-                .ok_or(AppError::Forbidden("Access restricted. Token required.".into()))?;
-
-            let invitee = state.invitee_repo.find_by_token(token).await?
-                .ok_or(AppError::Forbidden("Invalid token.".into()))?;
-
-            if invitee.event_id != event.id {
-                return Err(AppError::Forbidden("Token does not belong to this event.".into()));
-            }
-            if invitee.status != "ACTIVE" {
-                return Err(AppError::Conflict("This invitation token has already been used.".into()));
+        "RESTRICTED" | "HYBRID" => {
+            if let Some(token) = params.get("token") {
+                let invitee = find_valid_invitee(&state, token, &event.id).await?;
+                invitee_email = invitee.email;
+            } else if event.access_mode == "RESTRICTED" {
+                return Err(AppError::Forbidden("Access restricted. Token required.".into()));
             }
-
-            invitee_email = invitee.email;
+        },
+        "PASSCODE" => {
+            access::check_passcode(&event, params.get("passcode").map(String::as_str))?;
         },
         _ => {}
     }
@@ -229,35 +360,30 @@ pub async fn get_event(
     Ok(Json(event_json))
 }
 
-pub async fn get_available_dates(
-    State(state): State<Arc<AppState>>,
-    TenantId(tenant_id): TenantId,
-    Path((_, slug)): Path<(String, String)>,
-    Query(params): Query<HashMap<String, String>>,
-) -> Result<impl IntoResponse, AppError> {
-    let event = state.event_repo.find_by_slug(&tenant_id, &slug).await?
-        .ok_or(AppError::NotFound("Event not found".into()))?;
-
-    let start_str = params.get("start").ok_or(AppError::Validation("start required".into()))?;
-    let end_str = params.get("end").ok_or(AppError::Validation("end required".into()))?;
-
-    let start_date = NaiveDate::parse_from_str(start_str, "%Y-%m-%d").map_err(|_| AppError::Validation("Invalid start".into()))?;
-    let end_date = NaiveDate::parse_from_str(end_str, "%Y-%m-%d").map_err(|_| AppError::Validation("Invalid end".into()))?;
-
-    let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
-
-    let range_start_tz = tz.from_local_datetime(&start_date.and_hms_opt(0,0,0).unwrap()).single().unwrap();
-    let range_end_tz = tz.from_local_datetime(&end_date.and_hms_opt(23,59,59).unwrap()).single().unwrap();
-
-    let range_start_utc = range_start_tz.with_timezone(&Utc);
-    let range_end_utc = range_end_tz.with_timezone(&Utc);
+/// Checks the passcode of PASSCODE events. Token holders of a HYBRID event
+/// also see the seats reserved for them.
+async fn audience_for(state: &AppState, event: &Event, params: &HashMap<String, String>) -> Result<Audience, AppError> {
+    if event.access_mode == "PASSCODE" {
+        access::check_passcode(event, params.get("passcode").map(String::as_str))?;
+    }
+    match params.get("token") {
+        Some(token) if event.access_mode == "HYBRID" => {
+            find_valid_invitee(state, token, &event.id).await?;
+            Ok(Audience::Invited)
+        }
+        _ => Ok(Audience::Public),
+    }
+}
 
+/// Computes the slots of the days from `start` to `end` in one pass.
+async fn load_range(state: &AppState, event: &Event, start: NaiveDate, end: NaiveDate, audience: Audience) -> Result<Vec<DayAvailability>, AppError> {
+    let (range_start_utc, range_end_utc) = availability_span(event, start, end);
     let all_bookings = state.booking_repo.list_by_range(&event.id, range_start_utc, range_end_utc).await?;
 
     let overrides = if event.schedule_type == "MANUAL" {
         vec![]
     } else {
-        state.event_override_repo.list_by_range(&event.id, start_date, end_date).await?
+        state.event_override_repo.list_by_range(&event.id, start, end).await?
     };
 
     let manual_sessions = if event.schedule_type == "MANUAL" {
@@ -266,32 +392,87 @@ pub async fn get_available_dates(
         None
     };
 
-    let mut available_dates = Vec::new();
-    let mut current_date = start_date;
-
-    while current_date <= end_date {
-        let day_start_tz = tz.from_local_datetime(&current_date.and_hms_opt(0,0,0).unwrap()).single().unwrap();
-        let day_end_tz = tz.from_local_datetime(&current_date.and_hms_opt(23,59,59).unwrap()).single().unwrap();
-
-        let day_start_utc = day_start_tz.with_timezone(&Utc);
-        let day_end_utc = day_end_tz.with_timezone(&Utc);
+    Ok(calculate_range(event, start, end, &all_bookings, &overrides, manual_sessions.as_deref(), audience))
+}
 
-        if day_end_utc >= event.active_start && day_start_utc <= event.active_end {
-            let day_bookings: Vec<_> = all_bookings.iter()
-                .filter(|b| b.start_time < day_end_utc && b.end_time > day_start_utc)
-                .cloned()
-                .collect();
+/// Timezone the caller reads dates and times in, the event's when not given.
+fn viewer_timezone(params: &HashMap<String, String>) -> Result<Option<Tz>, AppError> {
+    params.get("tz")
+        .map(|name| name.parse::<Tz>().map_err(|_| AppError::Validation("Invalid timezone".into())))
+        .transpose()
+}
 
-            let override_rule = overrides.iter().find(|o| o.date == current_date);
-            let slots = calculate_slots(&event, current_date, &day_bookings, override_rule, manual_sessions.as_deref());
-            if !slots.is_empty() {
-                available_dates.push(current_date.to_string());
+/// Slots of the event days from `start` to `end`, recomputing only the span between
+/// the first and last uncached day.
+async fn cached_days(state: &AppState, tenant_id: &str, event: &Event, start: NaiveDate, end: NaiveDate, audience: Audience) -> Result<Vec<(NaiveDate, Vec<SlotDetail>)>, AppError> {
+    let mut days: Vec<(NaiveDate, Option<Vec<SlotDetail>>)> = start.iter_days()
+        .take_while(|date| *date <= end)
+        .map(|date| (date, state.availability_cache.get(tenant_id, &event.id, audience, date)))
+        .collect();
+
+    let missing: Vec<NaiveDate> = days.iter().filter(|(_, slots)| slots.is_none()).map(|(date, _)| *date).collect();
+    if let (Some(&first), Some(&last)) = (missing.first(), missing.last()) {
+        for day in load_range(state, event, first, last, audience).await? {
+            state.availability_cache.put(tenant_id, &event.id, audience, day.date, day.slots.clone());
+            if let Some((_, slots)) = days.iter_mut().find(|(date, _)| *date == day.date) {
+                *slots = Some(day.slots);
             }
         }
-        current_date += Duration::days(1);
     }
 
-    Ok(Json(available_dates))
+    Ok(days.into_iter().map(|(date, slots)| (date, slots.unwrap_or_default())).collect())
+}
+
+/// Slots starting on the viewer's days `start` to `end`, in start order.
+async fn viewer_slots(state: &AppState, tenant_id: &str, event: &Event, viewer: &Tz, start: NaiveDate, end: NaiveDate, audience: Audience) -> Result<Vec<SlotDetail>, AppError> {
+    let (first, last) = event_days(event, viewer, start, end);
+    let mut slots: Vec<SlotDetail> = cached_days(state, tenant_id, event, first, last, audience).await?
+        .into_iter()
+        .flat_map(|(_, slots)| slots)
+        .filter(|slot| {
+            let date = slot.start_time.with_timezone(viewer).date_naive();
+            date >= start && date <= end
+        })
+        .collect();
+    sort_details(&mut slots);
+    Ok(slots)
+}
+
+pub async fn get_available_dates(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    Path((_, slug)): Path<(String, String)>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<impl IntoResponse, AppError> {
+    let event = state.event_repo.find_by_slug(&tenant_id, &slug).await?
+        .ok_or(AppError::NotFound("Event not found".into()))?;
+
+    let start_str = params.get("start").ok_or(AppError::Validation("start required".into()))?;
+    let end_str = params.get("end").ok_or(AppError::Validation("end required".into()))?;
+
+    let start_date = NaiveDate::parse_from_str(start_str, "%Y-%m-%d").map_err(|_| AppError::Validation("Invalid start".into()))?;
+    let end_date = NaiveDate::parse_from_str(end_str, "%Y-%m-%d").map_err(|_| AppError::Validation("Invalid end".into()))?;
+
+    let viewer = viewer_timezone(&params)?;
+    let audience = audience_for(&state, &event, &params).await?;
+
+    // Without a viewer timezone a date lists the slots of its windows, with one the
+    // slots are bucketed by the viewer's date they start on
+    let mut available_dates: Vec<NaiveDate> = match viewer {
+        None => cached_days(&state, &tenant_id, &event, start_date, end_date, audience).await?
+            .into_iter()
+            .filter(|(_, slots)| slots.iter().any(SlotDetail::is_bookable))
+            .map(|(date, _)| date)
+            .collect(),
+        Some(viewer) => viewer_slots(&state, &tenant_id, &event, &viewer, start_date, end_date, audience).await?
+            .into_iter()
+            .filter(SlotDetail::is_bookable)
+            .map(|slot| slot.start_time.with_timezone(&viewer).date_naive())
+            .collect(),
+    };
+    available_dates.dedup();
+
+    Ok(Json(available_dates.into_iter().map(|date| date.to_string()).collect::<Vec<_>>()))
 }
 
 pub async fn get_slots(
@@ -311,32 +492,60 @@ pub async fn get_slots(
     let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
         .map_err(|_| AppError::Validation("Invalid date format".into()))?;
 
-    let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
-
-    let day_start_tz = tz.from_local_datetime(&date.and_hms_opt(0,0,0).unwrap()).single().unwrap();
-    let day_end_tz = tz.from_local_datetime(&date.and_hms_opt(23,59,59).unwrap()).single().unwrap();
-
-    let day_start_utc = day_start_tz.with_timezone(&Utc);
-    let day_end_utc = day_end_tz.with_timezone(&Utc);
-
-    let bookings = state.booking_repo.list_by_range(&event.id, day_start_utc, day_end_utc).await?;
-
-    let override_rule = if event.schedule_type == "MANUAL" {
-        None
-    } else {
-        state.event_override_repo.find_by_date(&event.id, date).await?
-    };
+    let viewer = viewer_timezone(&params)?;
+    let audience = audience_for(&state, &event, &params).await?;
+
+    if let Some(viewer) = viewer {
+        let details = viewer_slots(&state, &tenant_id, &event, &viewer, date, date, audience).await?;
+        let slots = details.iter()
+            .filter(|slot| slot.is_bookable())
+            .map(|slot| slot.start_time.with_timezone(&viewer).to_rfc3339())
+            .collect();
+        return Ok(Json(SlotsResponse {
+            date: date_str.to_string(),
+            timezone: viewer.name().to_string(),
+            slots,
+            details,
+        }));
+    }
 
-    let manual_sessions = if event.schedule_type == "MANUAL" {
-        Some(state.session_repo.list_by_range(&event.id, day_start_utc, day_end_utc).await?)
-    } else {
-        None
+    let details = match state.availability_cache.get(&tenant_id, &event.id, audience, date) {
+        Some(details) => details,
+        None => {
+            let (span_start_utc, span_end_utc) = availability_span(&event, date, date);
+            let bookings = state.booking_repo.list_by_range(&event.id, span_start_utc, span_end_utc).await?;
+
+            let override_rule = if event.schedule_type == "MANUAL" {
+                None
+            } else {
+                state.event_override_repo.find_by_date(&event.id, date).await?
+            };
+
+            let manual_sessions = if event.schedule_type == "MANUAL" {
+                Some(state.session_repo.list_by_range(&event.id, span_start_utc, span_end_utc).await?)
+            } else {
+                None
+            };
+
+            let details = calculate_slot_details(&event, date, &bookings, override_rule.as_ref(), manual_sessions.as_deref(), audience);
+            state.availability_cache.put(&tenant_id, &event.id, audience, date, details.clone());
+            details
+        }
     };
 
-    let slots = calculate_slots(&event, date, &bookings, override_rule.as_ref(), manual_sessions.as_deref());
-
     Ok(Json(SlotsResponse {
         date: date_str.to_string(),
-        slots,
+        timezone: event.timezone.clone(),
+        slots: free_slots(&details),
+        details,
     }))
+}
+
+/// Hit rate and size of the tenant's share of the availability cache.
+pub async fn get_availability_cache_stats(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(Json(state.availability_cache.stats(&tenant_id)))
 }
\ No newline at end of file
@@ -2,13 +2,35 @@ use axum::{extract::{State, Path, Query}, response::IntoResponse, Json};
 use crate::state::AppState;
 use crate::api::extractors::{auth::AuthUser, tenant::TenantId};
 use crate::api::dtos::requests::EventOverrideRequest;
-use crate::domain::models::event_override::EventOverride;
+use crate::api::handlers::event::notify_venue_change;
+use crate::domain::models::{booking::Booking, event::Event, event_override::EventOverride};
+use crate::domain::services::{availability::window_date, event_changes::Venue, local_time};
 use crate::error::AppError;
 use std::sync::Arc;
-use chrono::NaiveDate;
+use chrono::{Duration, NaiveDate};
+use chrono_tz::Tz;
 use std::collections::HashMap;
 use tracing::info;
 
+/// Active bookings in the windows of the local `date` of the event, as configured there by
+/// `rule`, including those after midnight in a window running past it.
+async fn bookings_on(state: &AppState, event: &Event, date: NaiveDate, rule: Option<&EventOverride>) -> Result<Vec<Booking>, AppError> {
+    let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
+    let next = date + Duration::days(1);
+    let (start, end) = (local_time::day_start(&tz, date), local_time::day_end(&tz, next));
+    let day_before = state.event_override_repo.find_by_date(&event.id, date - Duration::days(1)).await?;
+    let bookings = state.booking_repo.list_by_range(&event.id, start, end).await?;
+    Ok(bookings.into_iter().filter(|b| {
+        let starts_on = b.start_time.with_timezone(&tz).date_naive();
+        let previous = if starts_on == next { rule } else { day_before.as_ref() };
+        b.start_time >= start && b.start_time <= end && window_date(event, b.start_time, previous) == date
+    }).collect())
+}
+
+fn override_venue(entity: Option<&EventOverride>) -> Venue {
+    entity.map(|o| Venue { location: o.location.clone(), host_name: o.host_name.clone() }).unwrap_or_default()
+}
+
 pub async fn upsert_override(
     State(state): State<Arc<AppState>>,
     TenantId(tenant_id): TenantId,
@@ -29,9 +51,11 @@ pub async fn upsert_override(
         None
     };
 
+    let previous = state.event_override_repo.find_by_date(&event.id, payload.date).await?;
+
     let entity = EventOverride {
         id: uuid::Uuid::new_v4().to_string(),
-        event_id: event.id,
+        event_id: event.id.clone(),
         date: payload.date,
         is_unavailable: payload.is_unavailable,
         override_config_json,
@@ -42,6 +66,10 @@ pub async fn upsert_override(
     };
 
     let saved = state.event_override_repo.upsert(&entity).await?;
+    state.availability_cache.invalidate_dates(&tenant_id, &saved.event_id, &[saved.date]);
+
+    let bookings = bookings_on(&state, &event, saved.date, previous.as_ref()).await?;
+    notify_venue_change(&state, &event, bookings, &override_venue(previous.as_ref()), &override_venue(Some(&saved))).await?;
     info!("Upserted override for event {} on {}", slug, payload.date);
     Ok(Json(saved))
 }
@@ -58,7 +86,12 @@ pub async fn delete_override(
     let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
         .map_err(|_| AppError::Validation("Invalid date".into()))?;
 
+    let previous = state.event_override_repo.find_by_date(&event.id, date).await?;
     state.event_override_repo.delete(&event.id, date).await?;
+    state.availability_cache.invalidate_dates(&tenant_id, &event.id, &[date]);
+
+    let bookings = bookings_on(&state, &event, date, previous.as_ref()).await?;
+    notify_venue_change(&state, &event, bookings, &override_venue(previous.as_ref()), &Venue::default()).await?;
     info!("Deleted override for event {} on {}", slug, date_str);
     Ok(Json(serde_json::json!({"status": "deleted"})))
 }
@@ -3,10 +3,53 @@ use crate::state::AppState;
 use crate::api::extractors::{auth::AuthUser, tenant::TenantId};
 use crate::api::dtos::{requests::{CreateInviteeRequest, UpdateInviteeRequest}};
 use crate::domain::models::invitee::Invitee;
+use crate::domain::services::access;
 use crate::error::AppError;
 use std::sync::Arc;
+use chrono::{DateTime, Utc};
 use tracing::info;
 
+/// Looks up a token supplied by a booker and checks it grants access to the event.
+pub(crate) async fn find_valid_invitee(state: &AppState, token: &str, event_id: &str) -> Result<Invitee, AppError> {
+    let invitee = state.invitee_repo.find_by_token(token).await?
+        .ok_or(AppError::Forbidden("Invalid token".into()))?;
+    access::check_invitee(&invitee, event_id, Utc::now())?;
+    Ok(invitee)
+}
+
+async fn resolve_event_ids(state: &AppState, tenant_id: &str, slugs: &[String]) -> Result<Vec<String>, AppError> {
+    let mut event_ids = Vec::new();
+    for slug in slugs {
+        let event = state.event_repo.find_by_slug(tenant_id, slug).await?
+            .ok_or_else(|| AppError::NotFound(format!("Event '{}' not found", slug)))?;
+        if !event_ids.contains(&event.id) {
+            event_ids.push(event.id);
+        }
+    }
+    Ok(event_ids)
+}
+
+fn apply_limits(invitee: &mut Invitee, expires_at: Option<DateTime<Utc>>, max_uses: Option<i32>) -> Result<(), AppError> {
+    if let Some(max_uses) = max_uses {
+        if max_uses < 1 {
+            return Err(AppError::Validation("max_uses must be at least 1".into()));
+        }
+        invitee.max_uses = max_uses;
+    }
+    if expires_at.is_some() {
+        invitee.expires_at = expires_at;
+    }
+    Ok(())
+}
+
+async fn create(state: &AppState, tenant_id: String, event_id: String, payload: CreateInviteeRequest) -> Result<Invitee, AppError> {
+    let mut invitee = Invitee::new(tenant_id.clone(), event_id, payload.email);
+    apply_limits(&mut invitee, payload.expires_at, payload.max_uses)?;
+    invitee.event_ids = resolve_event_ids(state, &tenant_id, payload.event_slugs.as_deref().unwrap_or_default()).await?;
+
+    state.invitee_repo.create(&invitee).await
+}
+
 pub async fn create_invitee(
     State(state): State<Arc<AppState>>,
     TenantId(tenant_id): TenantId,
@@ -17,14 +60,33 @@ pub async fn create_invitee(
     let event = state.event_repo.find_by_slug(&tenant_id, &slug).await?
         .ok_or(AppError::NotFound("Event not found".into()))?;
 
-    let invitee = Invitee::new(tenant_id.clone(), event.id, payload.email);
-    let created = state.invitee_repo.create(&invitee).await?;
+    let created = create(&state, tenant_id, event.id, payload).await?;
 
     info!("Created invitee token for event {}", slug);
 
     Ok(Json(created))
 }
 
+/// Creates a token valid for several events, e.g. all studies of a recruitment wave.
+pub async fn create_tenant_invitee(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Json(payload): Json<CreateInviteeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let slugs = payload.event_slugs.clone().unwrap_or_default();
+    let first_slug = slugs.first()
+        .ok_or(AppError::Validation("event_slugs must name at least one event".into()))?;
+    let event = state.event_repo.find_by_slug(&tenant_id, first_slug).await?
+        .ok_or_else(|| AppError::NotFound(format!("Event '{}' not found", first_slug)))?;
+
+    let created = create(&state, tenant_id, event.id, payload).await?;
+
+    info!("Created invitee token for {} events", slugs.len());
+
+    Ok(Json(created))
+}
+
 pub async fn list_invitees(
     State(state): State<Arc<AppState>>,
     TenantId(tenant_id): TenantId,
@@ -48,10 +110,17 @@ pub async fn update_invitee(
     let mut invitee = state.invitee_repo.find_by_id(&tenant_id, &invitee_id).await?
         .ok_or(AppError::NotFound("Invitee not found".into()))?;
 
-    invitee.status = payload.status;
     if let Some(email) = payload.email {
         invitee.email = Some(email);
     }
+    apply_limits(&mut invitee, payload.expires_at, payload.max_uses)?;
+    invitee.sync_status();
+    if let Some(status) = payload.status {
+        invitee.status = status;
+    }
+    if let Some(slugs) = payload.event_slugs {
+        invitee.event_ids = resolve_event_ids(&state, &tenant_id, &slugs).await?;
+    }
 
     let updated = state.invitee_repo.update(&invitee).await?;
     info!("Updated invitee: {}", invitee_id);
@@ -10,4 +10,12 @@ pub mod label;
 pub mod session;
 pub mod booking_management;
 pub mod communication;
-pub mod ai;
\ No newline at end of file
+pub mod ai;
+pub mod payout;
+pub mod checkin;
+
+pub mod participant;
+pub mod privacy;
+pub mod retention;
+pub mod portal;
+pub mod search;
\ No newline at end of file
@@ -0,0 +1,161 @@
+use axum::{extract::{State, Path, Query}, response::IntoResponse, Json};
+use crate::state::AppState;
+use crate::api::extractors::{auth::AuthUser, tenant::TenantId};
+use crate::api::dtos::requests::{ParticipantQuery, UpdateParticipantRequest, MergeParticipantsRequest};
+use crate::api::dtos::responses::{ParticipantSummaryResponse, ParticipantDetailResponse};
+use crate::api::handlers::payout::load_ledger;
+use crate::domain::models::{booking::Booking, participant::Participant, tenant::Tenant};
+use crate::domain::services::participant::{self, ParticipantStats};
+use crate::domain::services::payout::PayoutLine;
+use crate::error::AppError;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::info;
+
+/// Bookings and payout lines grouped by the participant they belong to, in one pass each.
+fn group_by_participant(bookings: Vec<Booking>, ledger: Vec<PayoutLine>) -> HashMap<String, (Vec<Booking>, Vec<PayoutLine>)> {
+    let owners: HashMap<String, String> = bookings.iter()
+        .filter_map(|b| Some((b.id.clone(), b.participant_id.clone()?)))
+        .collect();
+    let mut groups: HashMap<String, (Vec<Booking>, Vec<PayoutLine>)> = HashMap::new();
+    for line in ledger {
+        if let Some(owner) = owners.get(&line.booking_id) {
+            groups.entry(owner.clone()).or_default().1.push(line);
+        }
+    }
+    for booking in bookings {
+        if let Some(owner) = booking.participant_id.clone() {
+            groups.entry(owner).or_default().0.push(booking);
+        }
+    }
+    groups
+}
+
+fn stats_for(participant: &Participant, groups: &HashMap<String, (Vec<Booking>, Vec<PayoutLine>)>, tenant: &Tenant) -> ParticipantStats {
+    let (bookings, lines) = groups.get(&participant.id).map(|(b, l)| (b.as_slice(), l.as_slice())).unwrap_or_default();
+    participant::compute_stats(participant, bookings, tenant, lines)
+}
+
+async fn load_tenant(state: &AppState, tenant_id: &str) -> Result<Tenant, AppError> {
+    state.tenant_repo.find_by_id(tenant_id).await?
+        .ok_or(AppError::NotFound("Tenant not found".into()))
+}
+
+async fn load_participant(state: &AppState, tenant_id: &str, participant_id: &str) -> Result<Participant, AppError> {
+    state.participant_repo.find_by_id(tenant_id, participant_id).await?
+        .ok_or(AppError::NotFound("Participant not found".into()))
+}
+
+async fn build_detail(state: &AppState, tenant_id: &str, participant: Participant) -> Result<ParticipantDetailResponse, AppError> {
+    let tenant = load_tenant(state, tenant_id).await?;
+    let bookings = state.booking_repo.list_by_participant(tenant_id, &participant.id).await?;
+    let groups = group_by_participant(bookings.clone(), load_ledger(state, tenant_id).await?);
+    let stats = stats_for(&participant, &groups, &tenant);
+    let emails = state.participant_repo.list_emails(tenant_id, &participant.id).await?;
+
+    Ok(ParticipantDetailResponse { participant, emails, stats, bookings })
+}
+
+pub async fn list_participants(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Query(query): Query<ParticipantQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let tenant = load_tenant(&state, &tenant_id).await?;
+    let search = query.search.map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty());
+
+    let participants: Vec<Participant> = state.participant_repo.list(&tenant_id).await?
+        .into_iter()
+        .filter(|p| search.as_ref().is_none_or(|s| p.name.to_lowercase().contains(s) || p.email.contains(s)))
+        .collect();
+
+    let bookings = state.booking_repo.list_by_tenant(&tenant_id).await?;
+    let groups = group_by_participant(bookings, load_ledger(&state, &tenant_id).await?);
+
+    let response: Vec<ParticipantSummaryResponse> = participants.into_iter()
+        .map(|p| {
+            let stats = stats_for(&p, &groups, &tenant);
+            ParticipantSummaryResponse { participant: p, stats }
+        })
+        .collect();
+
+    Ok(Json(response))
+}
+
+pub async fn get_participant(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Path((_, participant_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    let participant = load_participant(&state, &tenant_id, &participant_id).await?;
+    Ok(Json(build_detail(&state, &tenant_id, participant).await?))
+}
+
+pub async fn update_participant(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Path((_, participant_id)): Path<(String, String)>,
+    Json(payload): Json<UpdateParticipantRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut participant = load_participant(&state, &tenant_id, &participant_id).await?;
+
+    if let Some(name) = payload.name {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::Validation("Name must not be empty".into()));
+        }
+        participant.name = name;
+    }
+    if let Some(notes) = payload.notes {
+        participant.notes = if notes.trim().is_empty() { None } else { Some(notes) };
+    }
+    if let Some(exempt) = payload.noshow_exempt {
+        participant.noshow_exempt = exempt;
+    }
+
+    let updated = state.participant_repo.update(&participant).await?;
+    Ok(Json(updated))
+}
+
+pub async fn merge_participants(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Path((_, participant_id)): Path<(String, String)>,
+    Json(payload): Json<MergeParticipantsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut target = load_participant(&state, &tenant_id, &participant_id).await?;
+
+    let source_ids: Vec<String> = payload.source_ids.into_iter()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    if source_ids.is_empty() {
+        return Err(AppError::Validation("No participants to merge".into()));
+    }
+    if source_ids.contains(&target.id) {
+        return Err(AppError::Validation("Cannot merge a participant into itself".into()));
+    }
+
+    let mut sources = HashMap::new();
+    for id in &source_ids {
+        let source = load_participant(&state, &tenant_id, id).await?;
+        sources.insert(id.clone(), source);
+    }
+
+    // Keep the annotations of the merged profiles
+    let notes: Vec<String> = target.notes.iter()
+        .chain(source_ids.iter().filter_map(|id| sources[id].notes.as_ref()))
+        .cloned()
+        .collect();
+    target.notes = if notes.is_empty() { None } else { Some(notes.join("\n\n")) };
+
+    state.participant_repo.merge(&tenant_id, &target.id, &source_ids).await?;
+    let target = state.participant_repo.update(&target).await?;
+    info!("Merged {} participants into {}", source_ids.len(), target.id);
+
+    Ok(Json(build_detail(&state, &tenant_id, target).await?))
+}
@@ -0,0 +1,229 @@
+use axum::{extract::{State, Query}, http::header, response::{IntoResponse, Response}, Json};
+use crate::state::AppState;
+use crate::api::extractors::{auth::AuthUser, tenant::TenantId};
+use crate::api::dtos::requests::{PayoutQuery, MarkPayoutsPaidRequest, SepaExportRequest};
+use crate::api::dtos::responses::PayoutReportResponse;
+use crate::domain::models::payout::PayoutEntry;
+use crate::domain::services::payout::{self, PayoutGrouping, PayoutLine, PayoutSummary};
+use crate::domain::services::sepa::{self, SepaParty, SepaTransfer};
+use crate::infra::crypto::FieldCipher;
+use crate::error::AppError;
+use chrono::Utc;
+use uuid::Uuid;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) async fn load_ledger(state: &AppState, tenant_id: &str) -> Result<Vec<PayoutLine>, AppError> {
+    let bookings = state.booking_repo.list_by_tenant(tenant_id).await?;
+    let events = state.event_repo.list(tenant_id).await?
+        .into_iter().map(|e| (e.id.clone(), e)).collect::<HashMap<_, _>>();
+    let labels = state.label_repo.list(tenant_id).await?
+        .into_iter().map(|l| (l.id.clone(), l)).collect::<HashMap<_, _>>();
+    let entries = state.payout_repo.list_by_tenant(tenant_id).await?
+        .into_iter().map(|p| (p.booking_id.clone(), p)).collect::<HashMap<_, _>>();
+
+    Ok(payout::build_ledger(&bookings, &events, &labels, &entries))
+}
+
+fn apply_filters(lines: Vec<PayoutLine>, query: &PayoutQuery) -> Result<Vec<PayoutLine>, AppError> {
+    if let Some(status) = &query.status
+        && !["UNPAID", "EXPORTED", "PAID"].contains(&status.as_str()) {
+        return Err(AppError::Validation("Status must be UNPAID, EXPORTED or PAID".into()));
+    }
+
+    Ok(lines.into_iter()
+        .filter(|l| query.start.is_none_or(|d| l.start_time.date_naive() >= d))
+        .filter(|l| query.end.is_none_or(|d| l.start_time.date_naive() <= d))
+        .filter(|l| query.event_id.as_ref().is_none_or(|id| &l.event_id == id))
+        .filter(|l| query.status.as_ref().is_none_or(|s| &l.status == s))
+        .collect())
+}
+
+fn csv_response(body: String, filename: &str) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        body,
+    ).into_response()
+}
+
+pub async fn list_payouts(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Query(query): Query<PayoutQuery>,
+) -> Result<Response, AppError> {
+    let lines = apply_filters(load_ledger(&state, &tenant_id).await?, &query)?;
+
+    if query.format.as_deref() == Some("csv") {
+        return Ok(csv_response(payout::ledger_to_csv(&lines), "payouts.csv"));
+    }
+    Ok(Json(lines).into_response())
+}
+
+pub async fn payout_report(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Query(query): Query<PayoutQuery>,
+) -> Result<Response, AppError> {
+    let group_by = query.group_by.clone().unwrap_or_else(|| "participant".to_string());
+    let grouping = PayoutGrouping::parse(&group_by)
+        .ok_or(AppError::Validation("group_by must be participant, event or period".into()))?;
+
+    let lines = apply_filters(load_ledger(&state, &tenant_id).await?, &query)?;
+    let groups = payout::summarize(&lines, grouping);
+
+    if query.format.as_deref() == Some("csv") {
+        return Ok(csv_response(payout::summary_to_csv(&groups), &format!("payouts-by-{}.csv", group_by)));
+    }
+
+    let totals = PayoutSummary {
+        key: "total".to_string(),
+        name: "Total".to_string(),
+        bookings: lines.len(),
+        total: groups.iter().map(|g| g.total).sum(),
+        paid: groups.iter().map(|g| g.paid).sum(),
+        exported: groups.iter().map(|g| g.exported).sum(),
+        unpaid: groups.iter().map(|g| g.unpaid).sum(),
+    };
+
+    Ok(Json(PayoutReportResponse { group_by, totals, groups }).into_response())
+}
+
+pub async fn mark_paid(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Json(payload): Json<MarkPayoutsPaidRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if payload.booking_ids.is_empty() {
+        return Err(AppError::Validation("No bookings selected".into()));
+    }
+    if payload.payment_reference.trim().is_empty() {
+        return Err(AppError::Validation("Payment reference is required".into()));
+    }
+
+    let requested: HashSet<&String> = payload.booking_ids.iter().collect();
+    let ledger = load_ledger(&state, &tenant_id).await?;
+    let lines: HashMap<&String, &PayoutLine> = ledger.iter().map(|l| (&l.booking_id, l)).collect();
+
+    let paid_at = payload.paid_at.unwrap_or_else(Utc::now);
+    let mut entries = Vec::with_capacity(requested.len());
+
+    for booking_id in requested {
+        let line = lines.get(booking_id)
+            .ok_or(AppError::NotFound(format!("Booking {} not found or cancelled", booking_id)))?;
+
+        // Exported bookings are confirmed here once the bank has executed the transfer
+        if line.status == "PAID" {
+            return Err(AppError::Conflict(format!("Booking {} is already paid", booking_id)));
+        }
+
+        entries.push(PayoutEntry::paid(
+            tenant_id.clone(),
+            booking_id.clone(),
+            line.amount,
+            payload.payment_reference.trim().to_string(),
+            paid_at,
+        ));
+    }
+
+    state.payout_repo.claim_many(&entries).await?;
+
+    let total: i64 = entries.iter().map(|e| e.amount as i64).sum();
+    info!("Marked {} bookings as paid ({}€, ref {})", entries.len(), total, payload.payment_reference);
+
+    Ok(Json(serde_json::json!({
+        "status": "paid",
+        "count": entries.len(),
+        "total": total,
+    })))
+}
+
+pub async fn sepa_export(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Json(payload): Json<SepaExportRequest>,
+) -> Result<Response, AppError> {
+    if payload.booking_ids.is_empty() {
+        return Err(AppError::Validation("No bookings selected".into()));
+    }
+
+    let cipher = FieldCipher::from_config(&state.config)?;
+
+    let debtor_iban = sepa::normalize(&payload.debtor_iban);
+    if !sepa::is_valid_iban(&debtor_iban) {
+        return Err(AppError::Validation("Invalid debtor IBAN".into()));
+    }
+    let debtor_bic = payload.debtor_bic.as_deref().map(sepa::normalize).filter(|b| !b.is_empty());
+    if let Some(ref bic) = debtor_bic && !sepa::is_valid_bic(bic) {
+        return Err(AppError::Validation("Invalid debtor BIC".into()));
+    }
+    if payload.debtor_name.trim().is_empty() {
+        return Err(AppError::Validation("Debtor name is required".into()));
+    }
+
+    let today = Utc::now().date_naive();
+    let execution_date = payload.execution_date.unwrap_or(today);
+    if execution_date < today {
+        return Err(AppError::Validation("Execution date must not be in the past".into()));
+    }
+
+    let requested: HashSet<&String> = payload.booking_ids.iter().collect();
+    let ledger = load_ledger(&state, &tenant_id).await?;
+    let bank_details: HashMap<String, _> = state.payout_repo.list_bank_details(&tenant_id).await?
+        .into_iter().map(|d| (d.booking_id.clone(), d)).collect();
+
+    let now = Utc::now();
+    let message_id = format!("PAYOUT-{}-{}", now.format("%Y%m%d%H%M%S"), &Uuid::new_v4().simple().to_string()[..8]);
+
+    let mut transfers = Vec::new();
+    let mut entries = Vec::new();
+
+    for line in ledger.iter().filter(|l| requested.contains(&l.booking_id)) {
+        if line.status != "UNPAID" {
+            return Err(AppError::Conflict(format!("Booking {} has already been exported or paid", line.booking_id)));
+        }
+        if line.amount <= 0 {
+            return Err(AppError::Validation(format!("Booking {} has no payout amount", line.booking_id)));
+        }
+        let details = bank_details.get(&line.booking_id)
+            .ok_or(AppError::Validation(format!("Booking {} has no bank details", line.booking_id)))?;
+
+        transfers.push(SepaTransfer {
+            end_to_end_id: line.booking_id.replace('-', ""),
+            creditor: SepaParty {
+                name: details.account_holder.clone(),
+                iban: cipher.decrypt(&details.iban_encrypted)?,
+                bic: details.bic_encrypted.as_deref().map(|b| cipher.decrypt(b)).transpose()?,
+            },
+            amount: line.amount,
+            remittance: format!("{} {}", line.event_title, line.start_time.format("%d.%m.%Y")),
+        });
+        entries.push(PayoutEntry::exported(tenant_id.clone(), line.booking_id.clone(), line.amount, message_id.clone()));
+    }
+
+    if entries.len() != requested.len() {
+        return Err(AppError::NotFound("Some bookings were not found or are cancelled".into()));
+    }
+
+    // A concurrent export of the same bookings fails here, before any XML exists
+    state.payout_repo.claim_many(&entries).await?;
+
+    let debtor = SepaParty { name: payload.debtor_name.trim().to_string(), iban: debtor_iban, bic: debtor_bic };
+    let xml = sepa::build_pain001(&message_id, now, execution_date, &debtor, &transfers);
+    info!("Exported {} payouts as SEPA batch {}", entries.len(), message_id);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/xml; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}.xml\"", message_id)),
+        ],
+        xml,
+    ).into_response())
+}
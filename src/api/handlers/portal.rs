@@ -0,0 +1,115 @@
+use axum::{extract::{State, Path}, response::IntoResponse, Json};
+use crate::state::AppState;
+use crate::api::extractors::tenant::TenantId;
+use crate::api::dtos::requests::{PortalLinkRequest, RescheduleBookingRequest};
+use crate::api::dtos::responses::{PortalBookingResponse, PortalResponse};
+use crate::api::handlers::booking_management::{cancel_for_customer, reschedule_for_customer};
+use crate::domain::models::{booking::Booking, job::Job, participant::normalize_email, portal::{PortalToken, PORTAL_LINK_COOLDOWN_SECONDS}};
+use crate::error::AppError;
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use tracing::info;
+
+async fn load_token(state: &AppState, token: &str) -> Result<PortalToken, AppError> {
+    state.portal_repo.find_valid(token, Utc::now()).await?
+        .ok_or(AppError::NotFound("Link is invalid or has expired".into()))
+}
+
+async fn load_own_booking(state: &AppState, portal: &PortalToken, booking_id: &str) -> Result<Booking, AppError> {
+    state.booking_repo.find_by_id(&portal.tenant_id, booking_id).await?
+        .filter(|b| b.participant_id.as_deref() == Some(portal.participant_id.as_str()))
+        .ok_or(AppError::NotFound("Booking not found".into()))
+}
+
+/// Emails a magic link if the address belongs to a participant. The response is the
+/// same either way, so the endpoint does not reveal who has booked.
+pub async fn request_portal_link(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    Json(payload): Json<PortalLinkRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if payload.email.trim().is_empty() {
+        return Err(AppError::Validation("Email is required".into()));
+    }
+
+    if let Some(participant) = state.participant_repo.find_by_email(&tenant_id, &payload.email).await? {
+        let token = PortalToken::new(tenant_id.clone(), participant.id.clone());
+        let job = Job::new("PORTAL_LINK", participant.id.clone(), tenant_id.clone(), Utc::now())
+            .with_data(json!({ "token": token.token, "email": normalize_email(&payload.email) }));
+        let since = Utc::now() - Duration::seconds(PORTAL_LINK_COOLDOWN_SECONDS);
+
+        if state.portal_repo.issue(&token, &job, since).await? {
+            info!("Portal link issued for participant {}", participant.id);
+        } else {
+            info!("Portal link for participant {} requested again too soon", participant.id);
+        }
+    }
+
+    Ok(Json(json!({"status": "sent"})))
+}
+
+pub async fn get_portal(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let portal = load_token(&state, &token).await?;
+    let participant = state.participant_repo.find_by_id(&portal.tenant_id, &portal.participant_id).await?
+        .ok_or(AppError::NotFound("Link is invalid or has expired".into()))?;
+
+    let events: HashMap<String, _> = state.event_repo.list(&portal.tenant_id).await?
+        .into_iter()
+        .map(|e| (e.id.clone(), e))
+        .collect();
+    let bookings = state.booking_repo.list_by_participant(&portal.tenant_id, &participant.id).await?;
+
+    let now = Utc::now();
+    let mut upcoming = Vec::new();
+    let mut past = Vec::new();
+    for booking in bookings {
+        let Some(event) = events.get(&booking.event_id) else { continue };
+        let active = booking.status != "CANCELLED" && booking.start_time > now;
+        let entry = PortalBookingResponse {
+            event_title: event.title_en.clone(),
+            event_slug: event.slug.clone(),
+            timezone: event.timezone.clone(),
+            can_cancel: active && event.allow_customer_cancel,
+            can_reschedule: active && event.allow_customer_reschedule,
+            booking,
+        };
+        if entry.booking.end_time > now {
+            upcoming.push(entry);
+        } else {
+            past.push(entry);
+        }
+    }
+    past.reverse();
+
+    Ok(Json(PortalResponse {
+        name: participant.name,
+        email: participant.email,
+        expires_at: portal.expires_at,
+        upcoming,
+        past,
+    }))
+}
+
+pub async fn cancel_portal_booking(
+    State(state): State<Arc<AppState>>,
+    Path((token, booking_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    let portal = load_token(&state, &token).await?;
+    let booking = load_own_booking(&state, &portal, &booking_id).await?;
+    Ok(Json(cancel_for_customer(&state, booking).await?))
+}
+
+pub async fn reschedule_portal_booking(
+    State(state): State<Arc<AppState>>,
+    Path((token, booking_id)): Path<(String, String)>,
+    Json(payload): Json<RescheduleBookingRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let portal = load_token(&state, &token).await?;
+    let booking = load_own_booking(&state, &portal, &booking_id).await?;
+    Ok(Json(reschedule_for_customer(&state, booking, payload).await?))
+}
@@ -0,0 +1,140 @@
+use axum::{extract::State, http::header, response::{IntoResponse, Response}, Json};
+use crate::state::AppState;
+use crate::api::extractors::{auth::AuthUser, tenant::TenantId};
+use crate::api::dtos::requests::DataSubjectRequest;
+use crate::domain::models::{audit::AuditLog, booking::Booking, participant::{Participant, normalize_email}};
+use crate::domain::services::privacy;
+use crate::error::AppError;
+use chrono::Utc;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::info;
+
+/// Everything linked to a data subject: their participant profile (if any), all emails it
+/// was known under and the bookings made with them.
+struct DataSubject {
+    participant: Option<Participant>,
+    emails: Vec<String>,
+    bookings: Vec<Booking>,
+}
+
+async fn resolve_subject(state: &AppState, tenant_id: &str, email: &str) -> Result<DataSubject, AppError> {
+    let email = normalize_email(email);
+    if email.is_empty() {
+        return Err(AppError::Validation("Email is required".into()));
+    }
+
+    let participant = state.participant_repo.find_by_email(tenant_id, &email).await?;
+    let mut emails = vec![email];
+    if let Some(p) = &participant {
+        for alias in state.participant_repo.list_emails(tenant_id, &p.id).await? {
+            if !emails.contains(&alias) {
+                emails.push(alias);
+            }
+        }
+    }
+
+    let participant_id = participant.as_ref().map(|p| p.id.as_str());
+    let bookings = state.booking_repo.list_by_tenant(tenant_id).await?
+        .into_iter()
+        .filter(|b| (participant_id.is_some() && b.participant_id.as_deref() == participant_id)
+            || emails.contains(&normalize_email(&b.customer_email)))
+        .collect();
+
+    Ok(DataSubject { participant, emails, bookings })
+}
+
+pub async fn export_subject(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    user: AuthUser,
+    Json(payload): Json<DataSubjectRequest>,
+) -> Result<Response, AppError> {
+    let subject = resolve_subject(&state, &tenant_id, &payload.email).await?;
+    let booking_ids: HashSet<&str> = subject.bookings.iter().map(|b| b.id.as_str()).collect();
+
+    let payouts: Vec<_> = state.payout_repo.list_by_tenant(&tenant_id).await?
+        .into_iter()
+        .filter(|p| booking_ids.contains(p.booking_id.as_str()))
+        .collect();
+    let bank_details: Vec<_> = state.payout_repo.list_bank_details(&tenant_id).await?
+        .into_iter()
+        .filter(|d| booking_ids.contains(d.booking_id.as_str()))
+        .collect();
+    let invitees = state.privacy_repo.find_invitees(&tenant_id, &subject.emails).await?;
+    let mail_logs = state.privacy_repo.find_mail_logs(&tenant_id, &subject.emails).await?;
+    let consent_booking_ids: Vec<String> = subject.bookings.iter().map(|b| b.id.clone()).collect();
+    let consents = state.privacy_repo.find_consents(&tenant_id, &consent_booking_ids).await?;
+
+    let export = json!({
+        "subject": {
+            "emails": subject.emails,
+            "tenant_id": tenant_id,
+            "exported_at": Utc::now(),
+        },
+        "participant": subject.participant,
+        "bookings": subject.bookings,
+        "payouts": payouts,
+        "bank_details": bank_details,
+        "invitees": invitees,
+        "mail_logs": mail_logs,
+        "consents": consents,
+    });
+
+    let entry = AuditLog::new(tenant_id.clone(), user.0.username, "DATA_EXPORT", privacy::subject_hash(&payload.email), json!({
+        "bookings": subject.bookings.len(),
+        "invitees": invitees.len(),
+        "mail_logs": mail_logs.len(),
+        "consents": consents.len(),
+        "format": payload.format.as_deref().unwrap_or("json"),
+    }));
+    state.audit_repo.record(&entry).await?;
+    info!("Exported data subject {}", entry.subject);
+
+    if payload.format.as_deref() == Some("zip") {
+        let archive = privacy::build_archive(&export)?;
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "application/zip".to_string()),
+                (header::CONTENT_DISPOSITION, "attachment; filename=\"data-export.zip\"".to_string()),
+            ],
+            archive,
+        ).into_response());
+    }
+    Ok(Json(export).into_response())
+}
+
+pub async fn erase_subject(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    user: AuthUser,
+    Json(payload): Json<DataSubjectRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let subject = resolve_subject(&state, &tenant_id, &payload.email).await?;
+    let booking_ids: Vec<String> = subject.bookings.iter().map(|b| b.id.clone()).collect();
+    let participant_id = subject.participant.as_ref().map(|p| p.id.as_str());
+
+    let summary = state.privacy_repo.erase_subject(&tenant_id, &subject.emails, &booking_ids, participant_id).await?;
+    // Future bookings of the subject are cancelled, possibly across many events
+    state.availability_cache.invalidate_tenant(&tenant_id);
+
+    let entry = AuditLog::new(tenant_id.clone(), user.0.username, "DATA_ERASURE", privacy::subject_hash(&payload.email), json!({
+        "participant_id": participant_id,
+        "emails": subject.emails.len(),
+        "summary": summary,
+    }));
+    state.audit_repo.record(&entry).await?;
+    info!("Erased data subject {}", entry.subject);
+
+    Ok(Json(summary))
+}
+
+pub async fn list_audit_logs(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let logs = state.audit_repo.list(&tenant_id).await?;
+    Ok(Json(logs))
+}
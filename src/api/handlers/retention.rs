@@ -0,0 +1,60 @@
+use axum::{extract::State, response::IntoResponse, Json};
+use crate::state::AppState;
+use crate::api::extractors::{auth::AuthUser, tenant::TenantId};
+use crate::api::dtos::requests::UpdateRetentionPolicyRequest;
+use crate::domain::models::{audit::AuditLog, retention::RetentionPolicy};
+use crate::error::AppError;
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+use tracing::info;
+
+async fn load_policy(state: &AppState, tenant_id: &str) -> Result<RetentionPolicy, AppError> {
+    Ok(state.retention_repo.find_policy(tenant_id).await?
+        .unwrap_or_else(|| RetentionPolicy::new(tenant_id.to_string())))
+}
+
+pub async fn get_policy(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(Json(load_policy(&state, &tenant_id).await?))
+}
+
+pub async fn update_policy(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    user: AuthUser,
+    Json(payload): Json<UpdateRetentionPolicyRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    for months in [payload.anonymize_after_months, payload.mail_logs_after_months, payload.invitees_after_months].into_iter().flatten() {
+        if months < 1 {
+            return Err(AppError::Validation("Retention periods must be at least one month".into()));
+        }
+    }
+
+    let mut policy = load_policy(&state, &tenant_id).await?;
+    policy.anonymize_after_months = payload.anonymize_after_months;
+    policy.mail_logs_after_months = payload.mail_logs_after_months;
+    policy.invitees_after_months = payload.invitees_after_months;
+    policy.updated_at = Utc::now();
+
+    let saved = state.retention_repo.upsert_policy(&policy).await?;
+    let entry = AuditLog::new(tenant_id.clone(), user.0.username, "RETENTION_POLICY_UPDATED", tenant_id.clone(), json!(saved));
+    state.audit_repo.record(&entry).await?;
+
+    info!("Retention policy updated for tenant {}", tenant_id);
+    Ok(Json(saved))
+}
+
+/// Reports what the next scheduled run of the current policy would change.
+pub async fn retention_report(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let policy = load_policy(&state, &tenant_id).await?;
+    let report = state.retention_repo.report(&policy, Utc::now()).await?;
+    Ok(Json(report))
+}
@@ -0,0 +1,98 @@
+use axum::{extract::{State, Query}, response::IntoResponse, Json};
+use crate::state::AppState;
+use crate::api::extractors::tenant::TenantId;
+use crate::api::dtos::requests::SlotSearchQuery;
+use crate::api::dtos::responses::SlotSearchResult;
+use crate::domain::models::{booking::Booking, event::Event, event_override::EventOverride, session::EventSession};
+use crate::domain::services::availability::{calculate_range, Audience, SlotDetail};
+use crate::error::AppError;
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::Duration;
+
+const MAX_SEARCH_DAYS: i64 = 31;
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+const MAX_SEARCH_LIMIT: usize = 100;
+
+fn group_by_event<T>(items: Vec<T>, event_id: impl Fn(&T) -> &str) -> HashMap<String, Vec<T>> {
+    let mut grouped: HashMap<String, Vec<T>> = HashMap::new();
+    for item in items {
+        grouped.entry(event_id(&item).to_string()).or_default().push(item);
+    }
+    grouped
+}
+
+/// Earliest free slots across all OPEN events of the tenant. Bookings, overrides
+/// and sessions are loaded once for the whole range instead of per event.
+pub async fn search_slots(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    Query(query): Query<SlotSearchQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    if query.end < query.start {
+        return Err(AppError::Validation("End date must be after start date".into()));
+    }
+    if (query.end - query.start).num_days() >= MAX_SEARCH_DAYS {
+        return Err(AppError::Validation(format!("The search range is limited to {} days", MAX_SEARCH_DAYS)));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_SEARCH_LIMIT);
+
+    let events: Vec<Event> = state.event_repo.list(&tenant_id).await?
+        .into_iter()
+        .filter(|e| e.access_mode == "OPEN")
+        .collect();
+    if events.is_empty() {
+        return Ok(Json(Vec::<SlotSearchResult>::new()));
+    }
+
+    // Padded by a day on both ends as the events may use different timezones,
+    // and by another one at the end for windows running past midnight
+    let range_start_utc = query.start.and_hms_opt(0, 0, 0).unwrap().and_utc() - Duration::days(1);
+    let range_end_utc = query.end.and_hms_opt(23, 59, 59).unwrap().and_utc() + Duration::days(2);
+
+    let bookings = group_by_event(
+        state.booking_repo.list_by_tenant_range(&tenant_id, range_start_utc, range_end_utc).await?,
+        |b: &Booking| b.event_id.as_str(),
+    );
+    let overrides = group_by_event(
+        state.event_override_repo.list_by_tenant_range(&tenant_id, query.start, query.end).await?,
+        |o: &EventOverride| o.event_id.as_str(),
+    );
+    let sessions = group_by_event(
+        state.session_repo.list_by_tenant_range(&tenant_id, range_start_utc, range_end_utc).await?,
+        |s: &EventSession| s.event_id.as_str(),
+    );
+
+    let mut results = Vec::new();
+    for event in &events {
+        let event_bookings = bookings.get(&event.id).map(Vec::as_slice).unwrap_or_default();
+        let event_overrides = overrides.get(&event.id).map(Vec::as_slice).unwrap_or_default();
+        let manual_sessions = (event.schedule_type == "MANUAL")
+            .then(|| sessions.get(&event.id).map(Vec::as_slice).unwrap_or_default());
+
+        // Only the earliest `limit` slots of an event can make it into the result
+        let slots = calculate_range(event, query.start, query.end, event_bookings, event_overrides, manual_sessions, Audience::Public)
+            .into_iter()
+            .flat_map(|day| day.slots)
+            .filter(SlotDetail::is_bookable)
+            .take(limit);
+
+        for slot in slots {
+            results.push(SlotSearchResult {
+                event_slug: event.slug.clone(),
+                title_en: event.title_en.clone(),
+                title_de: event.title_de.clone(),
+                location: slot.location,
+                payout: event.payout.clone(),
+                timezone: event.timezone.clone(),
+                start_time: slot.start_time,
+                end_time: slot.end_time,
+            });
+        }
+    }
+
+    results.sort_by(|a, b| a.start_time.cmp(&b.start_time).then_with(|| a.event_slug.cmp(&b.event_slug)));
+    results.truncate(limit);
+
+    Ok(Json(results))
+}
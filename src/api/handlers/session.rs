@@ -1,13 +1,20 @@
 use axum::{extract::{State, Path}, response::IntoResponse, Json};
 use crate::state::AppState;
 use crate::api::extractors::{auth::AuthUser, tenant::TenantId};
-use crate::api::dtos::requests::{CreateSessionRequest, UpdateSessionRequest};
-use crate::domain::models::session::EventSession;
+use crate::api::dtos::requests::{BulkCreateSessionsRequest, CancelSessionRequest, BulkDeleteSessionsRequest, BulkShiftSessionsRequest, CreateSessionRequest, SessionSelection, UpdateSessionRequest};
+use crate::api::dtos::responses::{BulkSessionsResponse, SessionCancelledResponse};
+use crate::api::handlers::event::notify_venue_change;
+use crate::domain::models::{audit::AuditLog, event::Event, invitee::Invitee, job::Job, session::EventSession};
+use crate::domain::services::{event_changes::Venue, local_time};
 use crate::error::AppError;
 use std::sync::Arc;
-use chrono::{NaiveDate, NaiveTime, Utc, TimeZone};
+use chrono::{Duration, NaiveDate, NaiveTime, Utc, TimeZone};
 use chrono_tz::Tz;
 use tracing::info;
+use serde_json::json;
+
+/// Furthest a bulk shift may move sessions, a year either way.
+const MAX_SHIFT_MINUTES: i64 = 366 * 24 * 60;
 
 pub async fn create_session(
     State(state): State<Arc<AppState>>,
@@ -54,8 +61,11 @@ pub async fn create_session(
         return Err(AppError::Conflict("Session overlaps with an existing session".into()));
     }
 
-    let session = EventSession::new(event.id, start_utc, end_utc, payload.max_participants);
+    let mut session = EventSession::new(event.id.clone(), start_utc, end_utc, payload.max_participants);
+    session.location = payload.location.filter(|loc| !loc.is_empty());
+    session.host_name = payload.host_name.filter(|host| !host.is_empty());
     let created = state.session_repo.create(&session).await?;
+    state.availability_cache.invalidate_span(&tenant_id, &event, created.start_time, created.end_time);
 
     info!("Created manual session for event {}", slug);
     Ok(Json(created))
@@ -99,6 +109,8 @@ pub async fn update_session(
         session.max_participants = cap;
     }
 
+    let previous = Venue { location: session.location.clone(), host_name: session.host_name.clone() };
+
     if let Some(loc) = payload.location {
         session.location = if loc.is_empty() { None } else { Some(loc) };
     }
@@ -108,6 +120,11 @@ pub async fn update_session(
     }
 
     let updated = state.session_repo.update(&session).await?;
+    state.availability_cache.invalidate_span(&tenant_id, &event, updated.start_time, updated.end_time);
+
+    let bookings = state.booking_repo.list_by_range(&event.id, updated.start_time, updated.end_time).await?;
+    let current = Venue { location: updated.location.clone(), host_name: updated.host_name.clone() };
+    notify_venue_change(&state, &event, bookings, &previous, &current).await?;
     info!("Updated session {}", session_id);
     Ok(Json(updated))
 }
@@ -135,6 +152,207 @@ pub async fn delete_session(
     }
 
     state.session_repo.delete(&session_id).await?;
+    state.availability_cache.invalidate_span(&tenant_id, &event, session.start_time, session.end_time);
     info!("Deleted session {}", session_id);
-    Ok(Json(serde_json::json!({"status": "deleted"})))
+    Ok(Json(json!({"status": "deleted"})))
+}
+
+/// Cancels a session together with its bookings, e.g. when the experimenter falls ill.
+/// Each participant gets an ON_SESSION_CANCELLED email with a link to pick another
+/// session. The link carries an invitation token: the participant's own, released by
+/// the cancellation, or a new single-use one for RESTRICTED and HYBRID events, so the
+/// participant may book the seats reserved for invitees.
+pub async fn cancel_session(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    user: AuthUser,
+    Path((_, slug, session_id)): Path<(String, String, String)>,
+    Json(payload): Json<CancelSessionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let event = state.event_repo.find_by_slug(&tenant_id, &slug).await?
+        .ok_or(AppError::NotFound("Event not found".into()))?;
+
+    let session = state.session_repo.find_by_id(&session_id).await?
+        .ok_or(AppError::NotFound("Session not found".into()))?;
+
+    if session.event_id != event.id {
+        return Err(AppError::NotFound("Session not found for this event".into()));
+    }
+
+    let bookings = state.booking_repo.list_by_range(&event.id, session.start_time, session.end_time).await?;
+    let mut invitees = Vec::new();
+    let mut jobs = Vec::with_capacity(bookings.len());
+
+    for booking in &bookings {
+        let own_token = match &booking.invitee_id {
+            Some(invitee_id) => state.invitee_repo.find_by_id(&tenant_id, invitee_id).await?.map(|i| i.token),
+            None => None,
+        };
+        let token = match own_token {
+            Some(token) => Some(token),
+            None if matches!(event.access_mode.as_str(), "RESTRICTED" | "HYBRID") => {
+                let mut invitee = Invitee::new(tenant_id.clone(), event.id.clone(), Some(booking.customer_email.clone()));
+                invitee.expires_at = Some(event.active_end);
+                let token = invitee.token.clone();
+                invitees.push(invitee);
+                Some(token)
+            }
+            // OPEN events take bookings without a token, so the plain booking page is the rebook link
+            None => None,
+        };
+
+        jobs.push(Job::new("SESSION_CANCELLED", booking.id.clone(), tenant_id.clone(), Utc::now())
+            .with_data(json!({ "reason": payload.reason, "token": token })));
+    }
+
+    // The notices are only queued once the whole cancellation has gone through
+    let cancelled_bookings = state.session_repo.cancel_with_bookings(&session, &bookings, &invitees, jobs).await?;
+    state.availability_cache.invalidate_span(&tenant_id, &event, session.start_time, session.end_time);
+
+    let entry = AuditLog::new(tenant_id.clone(), user.0.username, "SESSION_CANCELLED", session.id.clone(), json!({
+        "event_slug": slug,
+        "start_time": session.start_time,
+        "reason": payload.reason,
+        "cancelled_bookings": cancelled_bookings.len(),
+    }));
+    state.audit_repo.record(&entry).await?;
+
+    info!("Cancelled session {} with {} bookings", session_id, cancelled_bookings.len());
+    Ok(Json(SessionCancelledResponse { session, cancelled_bookings }))
+}
+
+async fn find_manual_event(state: &AppState, tenant_id: &str, slug: &str) -> Result<Event, AppError> {
+    let event = state.event_repo.find_by_slug(tenant_id, slug).await?
+        .ok_or(AppError::NotFound("Event not found".into()))?;
+    if event.schedule_type != "MANUAL" {
+        return Err(AppError::Validation("Sessions can only be created for MANUAL events".into()));
+    }
+    Ok(event)
+}
+
+/// The selected sessions of the event, split into those without and those with bookings.
+async fn select_sessions(state: &AppState, event: &Event, selection: &SessionSelection) -> Result<(Vec<EventSession>, Vec<EventSession>), AppError> {
+    let sessions: Vec<EventSession> = match (&selection.session_ids, selection.start_date, selection.end_date) {
+        (Some(ids), _, _) => {
+            let sessions: Vec<EventSession> = state.session_repo.list_by_event(&event.id).await?
+                .into_iter()
+                .filter(|s| ids.contains(&s.id))
+                .collect();
+            if sessions.len() != ids.len() {
+                return Err(AppError::NotFound("Session not found for this event".into()));
+            }
+            sessions
+        }
+        (None, Some(start), Some(end)) if start <= end => {
+            let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
+            let (range_start, range_end) = (local_time::day_start(&tz, start), local_time::day_end(&tz, end));
+            state.session_repo.list_by_range(&event.id, range_start, range_end).await?
+                .into_iter()
+                .filter(|s| s.start_time >= range_start && s.start_time <= range_end)
+                .collect()
+        }
+        _ => return Err(AppError::Validation("session_ids or a start_date and end_date required".into())),
+    };
+
+    let (Some(first), Some(last)) = (sessions.iter().map(|s| s.start_time).min(), sessions.iter().map(|s| s.end_time).max()) else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+    let bookings = state.booking_repo.list_by_range(&event.id, first, last).await?;
+    Ok(sessions.into_iter().partition(|s| {
+        !bookings.iter().any(|b| b.start_time < s.end_time && b.end_time > s.start_time)
+    }))
+}
+
+/// Generates the sessions of a pattern at once. With `dry_run` they are only
+/// returned, together with the ones that would overlap a session.
+pub async fn bulk_create_sessions(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Path((_, slug)): Path<(String, String)>,
+    Json(payload): Json<BulkCreateSessionsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let event = find_manual_event(&state, &tenant_id, &slug).await?;
+    let sessions = payload.pattern.expand(&event)?;
+
+    let conflicts = state.session_repo.create_many(&sessions, payload.dry_run).await?;
+    if payload.dry_run {
+        return Ok(Json(BulkSessionsResponse { dry_run: true, sessions, skipped: Vec::new(), conflicts }));
+    }
+    if !conflicts.is_empty() {
+        return Err(AppError::Conflict(format!("{} of the generated sessions overlap other sessions", conflicts.len())));
+    }
+    state.availability_cache.invalidate_event(&tenant_id, &event.id);
+
+    info!("Generated {} sessions for event {}", sessions.len(), slug);
+    Ok(Json(BulkSessionsResponse { dry_run: false, sessions, skipped: Vec::new(), conflicts }))
+}
+
+/// Deletes the selected sessions that have no bookings.
+pub async fn bulk_delete_sessions(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Path((_, slug)): Path<(String, String)>,
+    Json(payload): Json<BulkDeleteSessionsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let event = find_manual_event(&state, &tenant_id, &slug).await?;
+    let (sessions, skipped) = select_sessions(&state, &event, &payload.selection).await?;
+
+    if !payload.dry_run {
+        let ids: Vec<String> = sessions.iter().map(|s| s.id.clone()).collect();
+        state.session_repo.delete_many(&ids).await?;
+        state.availability_cache.invalidate_event(&tenant_id, &event.id);
+        info!("Deleted {} sessions of event {}, skipped {} with bookings", sessions.len(), slug, skipped.len());
+    }
+
+    Ok(Json(BulkSessionsResponse { dry_run: payload.dry_run, sessions, skipped, conflicts: Vec::new() }))
+}
+
+/// Moves the selected sessions that have no bookings by `shift_minutes`.
+pub async fn bulk_shift_sessions(
+    State(state): State<Arc<AppState>>,
+    TenantId(tenant_id): TenantId,
+    _user: AuthUser,
+    Path((_, slug)): Path<(String, String)>,
+    Json(payload): Json<BulkShiftSessionsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if payload.shift_minutes == 0 {
+        return Err(AppError::Validation("shift_minutes must not be zero".into()));
+    }
+    if payload.shift_minutes.abs() > MAX_SHIFT_MINUTES {
+        return Err(AppError::Validation(format!("shift_minutes may be at most {} either way", MAX_SHIFT_MINUTES)));
+    }
+    let event = find_manual_event(&state, &tenant_id, &slug).await?;
+    let (mut sessions, skipped) = select_sessions(&state, &event, &payload.selection).await?;
+
+    let shift = Duration::minutes(payload.shift_minutes);
+    for session in &mut sessions {
+        session.start_time += shift;
+        session.end_time += shift;
+    }
+
+    // Sessions moving along are no obstacle, the ones staying behind are
+    let mut conflicts = Vec::new();
+    for session in &sessions {
+        let blocked = state.session_repo.find_overlap(&event.id, session.start_time, session.end_time).await?
+            .iter()
+            .any(|other| !sessions.iter().any(|moved| moved.id == other.id));
+        if blocked {
+            conflicts.push(session.clone());
+        }
+    }
+
+    if payload.dry_run {
+        return Ok(Json(BulkSessionsResponse { dry_run: true, sessions, skipped, conflicts }));
+    }
+    if !conflicts.is_empty() {
+        return Err(AppError::Conflict(format!("{} of the shifted sessions would overlap other sessions", conflicts.len())));
+    }
+
+    let moved = state.session_repo.update_times(&sessions).await?;
+    state.availability_cache.invalidate_event(&tenant_id, &event.id);
+
+    info!("Shifted {} sessions of event {} by {} minutes", moved.len(), slug, payload.shift_minutes);
+    Ok(Json(BulkSessionsResponse { dry_run: false, sessions: moved, skipped, conflicts }))
 }
\ No newline at end of file
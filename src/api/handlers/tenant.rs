@@ -46,10 +46,18 @@ pub async fn create_tenant(
         ("Abgesagt", "#9e9e9e", 0),
     ];
 
+    let mut attendance_tenant = created_tenant.clone();
     for (name, color, payout) in defaults {
         let label = BookingLabel::new(created_tenant.id.clone(), name.to_string(), color.to_string(), payout);
-        let _ = state.label_repo.create(&label).await;
+        if let Ok(created_label) = state.label_repo.create(&label).await {
+            match name {
+                "Show" => attendance_tenant.show_label_id = Some(created_label.id),
+                "Noshow" => attendance_tenant.noshow_label_id = Some(created_label.id),
+                _ => {}
+            }
+        }
     }
+    state.tenant_repo.update(&attendance_tenant).await?;
 
     Ok(Json(TenantCreatedResponse {
         tenant_id: created_tenant.id,
@@ -86,12 +94,41 @@ pub async fn update_tenant(
     if let Some(key) = payload.ai_api_key {
         tenant.ai_api_key = Some(key);
     }
+    if let Some(label_id) = payload.show_label_id {
+        tenant.show_label_id = resolve_attendance_label(&state, &tenant_id, label_id).await?;
+    }
+    if let Some(label_id) = payload.noshow_label_id {
+        tenant.noshow_label_id = resolve_attendance_label(&state, &tenant_id, label_id).await?;
+    }
+    if let Some(threshold) = payload.noshow_block_threshold {
+        // 0 disables the no-show policy
+        tenant.noshow_block_threshold = (threshold > 0).then_some(threshold);
+    }
+    if let Some(months) = payload.noshow_window_months {
+        if months < 1 { return Err(AppError::Validation("No-show window must be at least one month".into())); }
+        tenant.noshow_window_months = months;
+    }
+    if let Some(months) = payload.noshow_block_months {
+        if months < 1 { return Err(AppError::Validation("Block duration must be at least one month".into())); }
+        tenant.noshow_block_months = months;
+    }
 
     let updated = state.tenant_repo.update(&tenant).await?;
     info!("Tenant updated: {}", tenant_id);
     Ok(Json(updated))
 }
 
+/// Empty string clears the label; otherwise it must belong to the tenant.
+async fn resolve_attendance_label(state: &AppState, tenant_id: &str, label_id: String) -> Result<Option<String>, AppError> {
+    if label_id.is_empty() {
+        return Ok(None);
+    }
+    match state.label_repo.find_by_id(&label_id).await? {
+        Some(label) if label.tenant_id == tenant_id => Ok(Some(label.id)),
+        _ => Err(AppError::Validation("Label not found".into())),
+    }
+}
+
 pub async fn get_current_tenant(
     State(state): State<Arc<AppState>>,
     user: AuthUser,
@@ -7,7 +7,7 @@ use axum::{
 use std::sync::Arc;
 use std::time::Duration;
 use crate::state::AppState;
-use crate::api::handlers::{health, tenant, event, booking, invitee, member, event_override, auth, label, session, booking_management, communication, ai};
+use crate::api::handlers::{health, tenant, event, booking, invitee, member, event_override, auth, label, session, booking_management, communication, ai, payout, checkin, participant, privacy, retention, portal, search};
 use tower_http::{
     trace::TraceLayer,
     classify::ServerErrorsFailureClass,
@@ -66,6 +66,7 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/api/v1/{tenant_id}/events", post(event::create_event).get(event::list_events))
         .route("/api/v1/{tenant_id}/events/{slug}", get(event::get_event).put(event::update_event).delete(event::delete_event))
         .route("/api/v1/{tenant_id}/events/{slug}/invitees", post(invitee::create_invitee).get(invitee::list_invitees))
+        .route("/api/v1/{tenant_id}/invitees", post(invitee::create_tenant_invitee))
         .route("/api/v1/{tenant_id}/invitees/{invitee_id}", put(invitee::update_invitee).delete(invitee::delete_invitee))
 
         // Overrides & Sessions
@@ -73,21 +74,53 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/api/v1/{tenant_id}/events/{slug}/overrides/{date}", delete(event_override::delete_override))
         .route("/api/v1/{tenant_id}/events/{slug}/sessions", get(session::list_sessions).post(session::create_session))
         .route("/api/v1/{tenant_id}/events/{slug}/sessions/{session_id}", put(session::update_session).delete(session::delete_session))
+        .route("/api/v1/{tenant_id}/events/{slug}/sessions/{session_id}/cancel", post(session::cancel_session))
+        .route("/api/v1/{tenant_id}/events/{slug}/sessions/bulk", post(session::bulk_create_sessions))
+        .route("/api/v1/{tenant_id}/events/{slug}/sessions/bulk-delete", post(session::bulk_delete_sessions))
+        .route("/api/v1/{tenant_id}/events/{slug}/sessions/bulk-shift", post(session::bulk_shift_sessions))
 
         // Public Booking Flow
         .route("/api/v1/{tenant_id}/events/{slug}/dates", get(event::get_available_dates))
         .route("/api/v1/{tenant_id}/events/{slug}/slots", get(event::get_slots))
         .route("/api/v1/{tenant_id}/events/{slug}/book", post(booking::create_booking))
+        .route("/api/v1/{tenant_id}/slots/search", get(search::search_slots))
 
         // Customer Booking Management
         .route("/api/v1/bookings/manage/{token}", get(booking_management::get_booking_by_token))
         .route("/api/v1/bookings/manage/{token}/cancel", post(booking_management::cancel_booking))
         .route("/api/v1/bookings/manage/{token}/reschedule", post(booking_management::reschedule_booking))
+        .route("/api/v1/bookings/manage/{token}/bank-details", put(booking_management::update_bank_details))
+
+        // Participant Portal
+        .route("/api/v1/{tenant_id}/portal/link", post(portal::request_portal_link))
+        .route("/api/v1/portal/{token}", get(portal::get_portal))
+        .route("/api/v1/portal/{token}/bookings/{booking_id}/cancel", post(portal::cancel_portal_booking))
+        .route("/api/v1/portal/{token}/bookings/{booking_id}/reschedule", post(portal::reschedule_portal_booking))
 
         // Admin Booking Management
-        .route("/api/v1/{tenant_id}/events/{slug}/bookings", get(booking::list_bookings))
+        .route("/api/v1/{tenant_id}/events/{slug}/bookings", get(booking::list_bookings).post(booking::create_booking_on_behalf))
         .route("/api/v1/{tenant_id}/bookings/{booking_id}", get(booking::get_booking).put(booking::update_booking).delete(booking::delete_booking))
         .route("/api/v1/{tenant_id}/bookings", get(booking::list_all_bookings))
+        .route("/api/v1/{tenant_id}/checkin", post(checkin::check_in))
+        .route("/api/v1/{tenant_id}/availability/cache-stats", get(event::get_availability_cache_stats))
+
+        // Payouts
+        .route("/api/v1/{tenant_id}/payouts", get(payout::list_payouts))
+        .route("/api/v1/{tenant_id}/payouts/report", get(payout::payout_report))
+        .route("/api/v1/{tenant_id}/payouts/mark-paid", post(payout::mark_paid))
+        .route("/api/v1/{tenant_id}/payouts/sepa-export", post(payout::sepa_export))
+
+        // Participants
+        .route("/api/v1/{tenant_id}/participants", get(participant::list_participants))
+        .route("/api/v1/{tenant_id}/participants/{participant_id}", get(participant::get_participant).put(participant::update_participant))
+        .route("/api/v1/{tenant_id}/participants/{participant_id}/merge", post(participant::merge_participants))
+
+        // Privacy & Audit
+        .route("/api/v1/{tenant_id}/privacy/export", post(privacy::export_subject))
+        .route("/api/v1/{tenant_id}/privacy/erase", post(privacy::erase_subject))
+        .route("/api/v1/{tenant_id}/audit-logs", get(privacy::list_audit_logs))
+        .route("/api/v1/{tenant_id}/retention", get(retention::get_policy).put(retention::update_policy))
+        .route("/api/v1/{tenant_id}/retention/report", get(retention::retention_report))
 
         .layer(
             TraceLayer::new_for_http()
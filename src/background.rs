@@ -5,14 +5,22 @@ use tokio::time::sleep;
 use tracing::{error, info, warn, info_span, Instrument};
 use crate::state::AppState;
 use crate::domain::services::calendar::generate_ics;
+use crate::domain::services::checkin;
+use crate::domain::services::defaults;
+use crate::domain::services::idempotency;
+use crate::domain::models::audit::AuditLog;
 use crate::domain::services::communication_service::CommunicationService;
 use chrono_tz::Tz;
 use serde_json::json;
 
+/// How often retention policies are applied.
+const RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 pub async fn start_background_worker(state: Arc<AppState>) {
     info!("Starting background job worker...");
 
     let comm_service = CommunicationService::new(state.communication_repo.clone());
+    tokio::spawn(start_retention_scheduler(state.clone()));
 
     loop {
         match state.job_repo.find_pending(10).await {
@@ -60,6 +68,78 @@ pub async fn start_background_worker(state: Arc<AppState>) {
     }
 }
 
+async fn start_retention_scheduler(state: Arc<AppState>) {
+    loop {
+        run_retention(&state).await;
+        sleep(RETENTION_INTERVAL).await;
+    }
+}
+
+/// Applies every tenant's retention policy and purges expired refresh tokens.
+/// Failures are logged per tenant so one broken policy does not block the others.
+pub async fn run_retention(state: &Arc<AppState>) {
+    let now = Utc::now();
+    let policies = match state.retention_repo.list_policies().await {
+        Ok(policies) => policies,
+        Err(e) => {
+            error!("Failed to load retention policies: {:?}", e);
+            return;
+        }
+    };
+
+    for policy in policies {
+        match state.retention_repo.apply(&policy, now).await {
+            Ok(report) if !report.is_empty() => {
+                info!("Retention applied for tenant {}: {:?}", policy.tenant_id, report);
+                let entry = AuditLog::new(policy.tenant_id.clone(), "system".to_string(), "RETENTION_RUN", policy.tenant_id.clone(), json!(report));
+                if let Err(e) = state.audit_repo.record(&entry).await {
+                    error!("Failed to audit retention run: {:?}", e);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => error!("Retention failed for tenant {}: {:?}", policy.tenant_id, e),
+        }
+    }
+
+    match state.retention_repo.purge_expired_refresh_tokens(now).await {
+        Ok(0) => {}
+        Ok(count) => info!("Purged {} expired refresh tokens", count),
+        Err(e) => error!("Failed to purge refresh tokens: {:?}", e),
+    }
+
+    match state.idempotency_repo.purge(idempotency::expired_before(now)).await {
+        Ok(0) => {}
+        Ok(count) => info!("Purged {} expired idempotency keys", count),
+        Err(e) => error!("Failed to purge idempotency keys: {:?}", e),
+    }
+
+    match state.portal_repo.purge_expired(now).await {
+        Ok(0) => {}
+        Ok(count) => info!("Purged {} expired portal links", count),
+        Err(e) => error!("Failed to purge portal links: {:?}", e),
+    }
+}
+
+/// Fills `template`'s subject and body with `context` and renders the body to HTML.
+fn render_email(template: &crate::domain::models::communication::EmailTemplate, context: &serde_json::Value) -> Result<(String, String), crate::error::AppError> {
+    let context = tera::Context::from_value(context.clone()).map_err(|_| crate::error::AppError::Internal)?;
+    let mut tera = tera::Tera::default();
+    tera.add_raw_template(&template.name, &template.body_template).map_err(|e| {
+        error!("Tera parse error: {:?}", e);
+        crate::error::AppError::InternalWithMsg(format!("Tera parse error: {:?}", e))
+    })?;
+    let body_with_vars = tera.render(&template.name, &context).map_err(|e| {
+        error!("Tera render error: {:?}", e);
+        crate::error::AppError::InternalWithMsg(format!("Tera render error: {:?}", e))
+    })?;
+    let html = render_email_body(&template.template_type, &body_with_vars)?;
+
+    let subject_tmpl_name = format!("{}_subject", template.name);
+    tera.add_raw_template(&subject_tmpl_name, &template.subject_template).map_err(|e| crate::error::AppError::InternalWithMsg(format!("Tera subject error: {:?}", e)))?;
+    let subject = tera.render(&subject_tmpl_name, &context).map_err(|e| crate::error::AppError::InternalWithMsg(format!("Tera subject render error: {:?}", e)))?;
+    Ok((subject, html))
+}
+
 fn render_email_body(template_type: &str, body_content: &str) -> Result<String, crate::error::AppError> {
     if template_type == "mjml" {
         match mrml::parse(body_content) {
@@ -102,7 +182,7 @@ async fn process_job(
         let target_type = parts[1];
         let template_id = parts[2];
 
-        let mut email = String::new();
+        let email;
         let mut context_map = serde_json::Map::new();
         let event_id;
 
@@ -138,7 +218,10 @@ async fn process_job(
         let base_url = &state.config.frontend_url;
 
         if target_type == "BOOKING" {
-            let booking = state.booking_repo.find_by_id(tenant_id, payload_id).await?.unwrap();
+            let Some(booking) = state.booking_repo.find_by_id(tenant_id, payload_id).await? else {
+                info!("Booking {} is gone. Skipping campaign job {}.", payload_id, job.id);
+                return Ok(());
+            };
             let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
             let event_time = booking.start_time.with_timezone(&tz);
             context_map.insert("start_time".to_string(), json!(event_time.format("%Y-%m-%d %H:%M").to_string()));
@@ -165,14 +248,7 @@ async fn process_job(
         let template = state.communication_repo.get_template(template_id).await?
             .ok_or(crate::error::AppError::NotFound(format!("Template {} not found", template_id)))?;
 
-        let mut tera = tera::Tera::default();
-        tera.add_raw_template(&template.name, &template.body_template).map_err(|e| crate::error::AppError::InternalWithMsg(format!("Tera parse error: {:?}", e)))?;
-        let body_with_vars = tera.render(&template.name, &tera::Context::from_value(context_data.clone()).unwrap()).map_err(|e| crate::error::AppError::InternalWithMsg(format!("Tera render error: {:?}", e)))?;
-        let final_html = render_email_body(&template.template_type, &body_with_vars)?;
-
-        let subject_tmpl_name = format!("{}_subject", template.name);
-        tera.add_raw_template(&subject_tmpl_name, &template.subject_template).map_err(|e| crate::error::AppError::InternalWithMsg(format!("Tera subject parse error: {:?}", e)))?;
-        let final_subject = tera.render(&subject_tmpl_name, &tera::Context::from_value(context_data.clone()).unwrap()).map_err(|e| crate::error::AppError::InternalWithMsg(format!("Tera subject render error: {:?}", e)))?;
+        let (final_subject, final_html) = render_email(&template, &context_data)?;
 
         info!("Sending campaign email to {}", email);
         state.email_service.send(&email, &final_subject, &final_html, None, None).await?;
@@ -181,6 +257,18 @@ async fn process_job(
         return Ok(());
     }
 
+    if job.job_type == "ATTENDANCE_CHECK" {
+        return process_attendance_check(state, &tenant, job).await;
+    }
+
+    if job.job_type == "BOOKING_BLOCKED" {
+        return process_booking_blocked(state, comm_service, &tenant, job).await;
+    }
+
+    if job.job_type == "PORTAL_LINK" {
+        return process_portal_link(state, comm_service, &tenant, job).await;
+    }
+
     // Standard Flow (Confirmation/Reminder)
     let booking = state.booking_repo.find_by_id(tenant_id, payload_id).await?
         .ok_or(crate::error::AppError::NotFound(format!("Booking {} not found", payload_id)))?;
@@ -208,16 +296,43 @@ async fn process_job(
     context.insert("duration", &event.duration_min);
 
     let base_url = &state.config.frontend_url;
-    let manage_link = format!("{}/en/manage/{}", base_url, booking.management_token);
-    context.insert("manage_link", &manage_link);
+    // A cancelled booking can neither be managed nor checked in
+    let cancelled = job.job_type == "SESSION_CANCELLED";
+    if !cancelled {
+        let manage_link = format!("{}/en/manage/{}", base_url, booking.management_token);
+        context.insert("manage_link", &manage_link);
+    }
     let book_link = format!("{}/en/book/{}/{}", base_url, tenant_id, event.slug);
     context.insert("book_link", &book_link);
     context.insert("booking_link", &book_link); // Alias
 
+    if cancelled {
+        let data = job.payload.data.clone().unwrap_or_default();
+        let rebook_link = match data["token"].as_str() {
+            Some(token) => format!("{}?accessToken={}", book_link, token),
+            None => book_link.clone(),
+        };
+        context.insert("rebook_link", &rebook_link);
+        context.insert("reason", data["reason"].as_str().unwrap_or_default());
+    }
+    if job.job_type == "EVENT_UPDATED" {
+        let data = job.payload.data.clone().unwrap_or_default();
+        context.insert("changes", &data["changes"]);
+    }
+
+    if !cancelled {
+        let checkin_code = checkin::sign_code(&state.config.checkin_secret, &booking.id);
+        context.insert("checkin_qr", &checkin::render_qr_html(&checkin_code));
+        context.insert("checkin_code", &checkin_code);
+    }
+
     let mut resolved_trigger = job.job_type.clone();
     if resolved_trigger == "CONFIRMATION" { resolved_trigger = "ON_BOOKING".to_string(); }
     else if resolved_trigger == "CANCELLATION" { resolved_trigger = "ON_CANCEL".to_string(); }
     else if resolved_trigger == "RESCHEDULE" { resolved_trigger = "ON_RESCHEDULE".to_string(); }
+    else if resolved_trigger == "DUPLICATE_BOOKING" { resolved_trigger = "ON_DUPLICATE_BOOKING".to_string(); }
+    else if resolved_trigger == "SESSION_CANCELLED" { resolved_trigger = "ON_SESSION_CANCELLED".to_string(); }
+    else if resolved_trigger == "EVENT_UPDATED" { resolved_trigger = "ON_EVENT_UPDATED".to_string(); }
     else if resolved_trigger == "REMINDER" {
         let diff = booking.start_time - job.execute_at;
         if diff.num_hours() >= 23 { resolved_trigger = "REMINDER_24H".to_string(); }
@@ -227,66 +342,222 @@ async fn process_job(
     let rules = state.communication_repo.get_rules_by_trigger(tenant_id, Some(&event.id), &resolved_trigger).await?;
     let context_val = context.into_json();
 
-    if let Some(rule) = rules.first() {
-        info!("Using custom template rule {} for trigger {}", rule.id, resolved_trigger);
-        let template = state.communication_repo.get_template(&rule.template_id).await?
-            .ok_or(crate::error::AppError::NotFound(format!("Template {} not found", rule.template_id)))?;
-
-        // Idempotency Check
-        use sha2::{Sha256, Digest};
-        let context_json = serde_json::to_string(&context_val).unwrap_or_default();
-        let mut hasher = Sha256::new();
-        hasher.update(template.name.as_bytes());
-        hasher.update(context_json.as_bytes());
-        let hash = hex::encode(hasher.finalize());
-
-        if state.communication_repo.has_mail_been_sent(&booking.customer_email, &template.name, &hash).await? {
-            info!("Email skipped (idempotency) for job {}. Recipient: {}, Template: {}", job.id, booking.customer_email, template.name);
-            let log = crate::domain::models::communication::MailLog {
-                id: uuid::Uuid::new_v4().to_string(),
-                job_id: job.id.clone(),
-                recipient: booking.customer_email.clone(),
-                template_id: template.name.clone(),
-                context_hash: hash,
-                sent_at: Utc::now(),
-                status: "SKIPPED_DUPLICATE".to_string(),
-            };
-            state.communication_repo.log_mail(&log).await?;
+    let template = match rules.first() {
+        Some(rule) => {
+            info!("Using custom template rule {} for trigger {}", rule.id, resolved_trigger);
+            state.communication_repo.get_template(&rule.template_id).await?
+                .ok_or(crate::error::AppError::NotFound(format!("Template {} not found", rule.template_id)))?
+        }
+        // Events created before duplicate, session cancellation and update notices existed have no rule for them
+        None if job.job_type == "DUPLICATE_BOOKING" => crate::domain::models::communication::EmailTemplate::new(
+            tenant_id.to_string(),
+            Some(event.id.clone()),
+            "duplicate_booking".to_string(),
+            defaults::DEFAULT_DUPLICATE_BOOKING_SUBJECT.to_string(),
+            defaults::get_default_template("duplicate_booking"),
+            "mjml".to_string(),
+        ),
+        None if job.job_type == "SESSION_CANCELLED" => crate::domain::models::communication::EmailTemplate::new(
+            tenant_id.to_string(),
+            Some(event.id.clone()),
+            "session_cancelled".to_string(),
+            defaults::DEFAULT_SESSION_CANCELLED_SUBJECT.to_string(),
+            defaults::get_default_template("session_cancelled"),
+            "mjml".to_string(),
+        ),
+        None if job.job_type == "EVENT_UPDATED" => crate::domain::models::communication::EmailTemplate::new(
+            tenant_id.to_string(),
+            Some(event.id.clone()),
+            "event_updated".to_string(),
+            defaults::DEFAULT_EVENT_UPDATED_SUBJECT.to_string(),
+            defaults::get_default_template("event_updated"),
+            "mjml".to_string(),
+        ),
+        None => {
+            warn!("No notification rule found for event {} trigger {}. Skipping email.", event.id, resolved_trigger);
             return Ok(());
         }
+    };
+
+    // Idempotency Check
+    use sha2::{Sha256, Digest};
+    let context_json = serde_json::to_string(&context_val).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(template.name.as_bytes());
+    hasher.update(context_json.as_bytes());
+    let hash = hex::encode(hasher.finalize());
+
+    if state.communication_repo.has_mail_been_sent(&booking.customer_email, &template.name, &hash).await? {
+        info!("Email skipped (idempotency) for job {}. Recipient: {}, Template: {}", job.id, booking.customer_email, template.name);
+        let log = crate::domain::models::communication::MailLog {
+            id: uuid::Uuid::new_v4().to_string(),
+            job_id: job.id.clone(),
+            recipient: booking.customer_email.clone(),
+            template_id: template.name.clone(),
+            context_hash: hash,
+            sent_at: Utc::now(),
+            status: "SKIPPED_DUPLICATE".to_string(),
+        };
+        state.communication_repo.log_mail(&log).await?;
+        return Ok(());
+    }
 
-        let mut tera = tera::Tera::default();
-        tera.add_raw_template(&template.name, &template.body_template).map_err(|e| {
-            error!("Tera/MJML parse error: {:?}", e);
-            crate::error::AppError::InternalWithMsg(format!("Tera parse error: {:?}", e))
-        })?;
+    let (final_subject, final_html) = render_email(&template, &context_val)?;
 
-        let body_with_vars = tera.render(&template.name, &tera::Context::from_value(context_val.clone()).unwrap())
-            .map_err(|e| {
-                error!("Tera render error: {:?}", e);
-                crate::error::AppError::InternalWithMsg(format!("Tera render error: {:?}", e))
-            })?;
+    let (attachment_name, attachment_data) = if matches!(job.job_type.as_str(), "CONFIRMATION" | "EVENT_UPDATED") {
+        let ics_string = generate_ics(&event, &booking);
+        (Some("invite.ics"), Some(ics_string.into_bytes()))
+    } else {
+        (None, None)
+    };
 
-        let final_html = render_email_body(&template.template_type, &body_with_vars)?;
+    info!("Sending custom email to {}", booking.customer_email);
+    state.email_service.send(&booking.customer_email, &final_subject, &final_html, attachment_name, attachment_data.as_deref()).await?;
+    comm_service.record_success(&job.id, &booking.customer_email, &template.name, &context_val).await?;
 
-        let subject_tmpl_name = format!("{}_subject", template.name);
-        tera.add_raw_template(&subject_tmpl_name, &template.subject_template).map_err(|e| crate::error::AppError::InternalWithMsg(format!("Tera subject error: {:?}", e)))?;
-        let final_subject = tera.render(&subject_tmpl_name, &tera::Context::from_value(context_val.clone()).unwrap()).map_err(|e| crate::error::AppError::InternalWithMsg(format!("Tera subject render error: {:?}", e)))?;
+    Ok(())
+}
 
-        let (attachment_name, attachment_data) = if job.job_type == "CONFIRMATION" {
-            let ics_string = generate_ics(&event, &booking);
-            (Some("invite.ics"), Some(ics_string.into_bytes()))
-        } else {
-            (None, None)
-        };
+/// Labels a booking as no-show once its session is over and nobody checked it in.
+async fn process_attendance_check(
+    state: &Arc<AppState>,
+    tenant: &crate::domain::models::tenant::Tenant,
+    job: &crate::domain::models::job::Job
+) -> Result<(), crate::error::AppError> {
+    let Some(noshow_label_id) = &tenant.noshow_label_id else {
+        info!("Tenant has no no-show label configured. Skipping attendance check.");
+        return Ok(());
+    };
 
-        info!("Sending custom email to {}", booking.customer_email);
-        state.email_service.send(&booking.customer_email, &final_subject, &final_html, attachment_name, attachment_data.as_deref()).await?;
-        comm_service.record_success(&job.id, &booking.customer_email, &template.name, &context_val).await?;
-    } else {
-        warn!("No notification rule found for event {} trigger {}. Skipping email.", event.id, resolved_trigger);
+    let booking = match state.booking_repo.find_by_id(&tenant.id, &job.payload.booking_id).await? {
+        Some(b) => b,
+        None => return Ok(()),
+    };
+
+    // The booking was moved to a later slot; a newer job covers it
+    if booking.end_time > Utc::now() {
+        info!("Booking {} has not ended yet. Skipping stale attendance check.", booking.id);
         return Ok(());
     }
 
+    if state.booking_repo.mark_noshow(&tenant.id, &booking.id, noshow_label_id).await? {
+        info!("Booking {} labelled as no-show", booking.id);
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Explains a no-show block to the participant. Uses the event's ON_BOOKING_BLOCKED rule
+/// and falls back to the built-in template for events created before the rule existed.
+async fn process_booking_blocked(
+    state: &Arc<AppState>,
+    comm_service: &CommunicationService,
+    tenant: &crate::domain::models::tenant::Tenant,
+    job: &crate::domain::models::job::Job
+) -> Result<(), crate::error::AppError> {
+    let participant = match state.participant_repo.find_by_id(&tenant.id, &job.payload.booking_id).await? {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    let data = job.payload.data.clone().unwrap_or_default();
+    let event_id = data["event_id"].as_str().unwrap_or_default();
+    let blocked_until: chrono::DateTime<Utc> = serde_json::from_value(data["blocked_until"].clone())
+        .map_err(|_| crate::error::AppError::InternalWithMsg("Blocked job without blocked_until".into()))?;
+    let event = state.event_repo.find_by_id(&tenant.id, event_id).await?
+        .ok_or(crate::error::AppError::NotFound(format!("Event {} not found", event_id)))?;
+
+    let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
+    let context_val = json!({
+        "user_name": participant.name,
+        "event_title": event.title_en,
+        "event_description": event.desc_en,
+        "tenant_name": tenant.name,
+        "logo_url": tenant.logo_url.clone().unwrap_or_default(),
+        "blocked_until": blocked_until.with_timezone(&tz).format("%Y-%m-%d").to_string(),
+    });
+
+    let rules = state.communication_repo.get_rules_by_trigger(&tenant.id, Some(&event.id), "ON_BOOKING_BLOCKED").await?;
+    let template = match rules.first() {
+        Some(rule) => state.communication_repo.get_template(&rule.template_id).await?
+            .ok_or(crate::error::AppError::NotFound(format!("Template {} not found", rule.template_id)))?,
+        None => crate::domain::models::communication::EmailTemplate::new(
+            tenant.id.clone(),
+            Some(event.id.clone()),
+            "booking_blocked".to_string(),
+            defaults::DEFAULT_BOOKING_BLOCKED_SUBJECT.to_string(),
+            defaults::get_default_template("booking_blocked"),
+            "mjml".to_string(),
+        ),
+    };
+
+    // Every rejected attempt queues a job; the participant hears about each block once
+    use sha2::{Sha256, Digest};
+    let mut hasher = Sha256::new();
+    hasher.update(template.name.as_bytes());
+    hasher.update(serde_json::to_string(&context_val).unwrap_or_default().as_bytes());
+    let hash = hex::encode(hasher.finalize());
+    if state.communication_repo.has_mail_been_sent(&participant.email, &template.name, &hash).await? {
+        info!("Block notice already sent to {}", participant.email);
+        return Ok(());
+    }
+
+    let (final_subject, final_html) = render_email(&template, &context_val)?;
+
+    info!("Sending booking block notice to {}", participant.email);
+    state.email_service.send(&participant.email, &final_subject, &final_html, None, None).await?;
+    comm_service.record_success(&job.id, &participant.email, &template.name, &context_val).await?;
+    Ok(())
+}
+
+/// Sends a participant the magic link to their bookings. Uses a tenant-wide
+/// ON_PORTAL_LINK rule when one exists, otherwise the built-in template.
+async fn process_portal_link(
+    state: &Arc<AppState>,
+    comm_service: &CommunicationService,
+    tenant: &crate::domain::models::tenant::Tenant,
+    job: &crate::domain::models::job::Job
+) -> Result<(), crate::error::AppError> {
+    let participant = match state.participant_repo.find_by_id(&tenant.id, &job.payload.booking_id).await? {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    let data = job.payload.data.clone().unwrap_or_default();
+    let token = data["token"].as_str()
+        .ok_or(crate::error::AppError::InternalWithMsg("Portal job without token".into()))?;
+    let recipient = data["email"].as_str().unwrap_or(&participant.email).to_string();
+    let portal = match state.portal_repo.find_valid(token, Utc::now()).await? {
+        Some(p) => p,
+        None => {
+            info!("Portal link for participant {} expired before sending", participant.id);
+            return Ok(());
+        }
+    };
+
+    let context_val = json!({
+        "user_name": participant.name,
+        "tenant_name": tenant.name,
+        "logo_url": tenant.logo_url.clone().unwrap_or_default(),
+        "portal_link": format!("{}/en/portal/{}", state.config.frontend_url, portal.token),
+        "expires_at": portal.expires_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+    });
+
+    let rules = state.communication_repo.get_rules_by_trigger(&tenant.id, None, "ON_PORTAL_LINK").await?;
+    let template = match rules.first() {
+        Some(rule) => state.communication_repo.get_template(&rule.template_id).await?
+            .ok_or(crate::error::AppError::NotFound(format!("Template {} not found", rule.template_id)))?,
+        None => crate::domain::models::communication::EmailTemplate::new(
+            tenant.id.clone(),
+            None,
+            "portal_link".to_string(),
+            defaults::DEFAULT_PORTAL_LINK_SUBJECT.to_string(),
+            defaults::get_default_template("portal_link"),
+            "mjml".to_string(),
+        ),
+    };
+
+    let (final_subject, final_html) = render_email(&template, &context_val)?;
+
+    info!("Sending portal link to {}", recipient);
+    state.email_service.send(&recipient, &final_subject, &final_html, None, None).await?;
+    comm_service.record_success(&job.id, &recipient, &template.name, &context_val).await?;
+    Ok(())
+}
@@ -1,4 +1,8 @@
-use chrono::{Duration as ChronoDuration, Utc};
+use booking_backend::domain::models::booking::{Booking, NewBookingParams};
+use booking_backend::domain::models::event::Event;
+use booking_backend::domain::services::availability::{calculate_range, calculate_slots, Audience};
+use booking_backend::domain::services::local_time::{self, Overlap};
+use chrono::{Duration as ChronoDuration, NaiveDate, Utc};
 use colored::*;
 use governor::{Quota, RateLimiter};
 use hdrhistogram::Histogram;
@@ -12,6 +16,9 @@ use uuid::Uuid;
 
 const DURATION_SECS: u64 = 20;
 const BASE_URL: &str = "http://localhost:8000";
+const AVAILABILITY_DAYS: i64 = 31;
+const AVAILABILITY_BOOKINGS: i64 = 2000;
+const AVAILABILITY_ROUNDS: u32 = 20;
 
 struct Target {
     name: &'static str,
@@ -23,7 +30,10 @@ struct Target {
 #[tokio::main]
 async fn main() {
     println!("{}", "🚀 Starting Benchmark Suite".bold().green());
-    println!("Target URL: {}", BASE_URL);
+
+    benchmark_availability();
+
+    println!("\nTarget URL: {}", BASE_URL);
 
     let client = Client::builder()
         .pool_max_idle_per_host(1000)
@@ -88,6 +98,109 @@ async fn main() {
     }
 }
 
+/// Compares a month of per-day `calculate_slots` calls against one `calculate_range`
+/// pass over the same data. Runs in-process and needs no server.
+fn benchmark_availability() {
+    println!("\n{}", "⏱️  Availability engine (in-process)".yellow());
+
+    let tz = chrono_tz::Europe::Berlin;
+    let start = Utc::now().date_naive() + ChronoDuration::days(7);
+    let end = start + ChronoDuration::days(AVAILABILITY_DAYS - 1);
+    let window = json!({"start": "08:00", "end": "20:00"});
+    let event = Event {
+        id: "bench".into(),
+        tenant_id: "bench".into(),
+        slug: "bench".into(),
+        title_en: "Benchmark".into(),
+        title_de: "Benchmark".into(),
+        desc_en: String::new(),
+        desc_de: String::new(),
+        location: "Server".into(),
+        payout: "0".into(),
+        host_name: "Bot".into(),
+        timezone: tz.name().into(),
+        min_notice_general: 0,
+        min_notice_first: 0,
+        active_start: Utc::now(),
+        active_end: Utc::now() + ChronoDuration::days(365),
+        duration_min: 30,
+        interval_min: 15,
+        max_participants: 5,
+        image_url: String::new(),
+        config_json: json!({
+            "monday": [window], "tuesday": [window], "wednesday": [window],
+            "thursday": [window], "friday": [window]
+        }).to_string(),
+        access_mode: "OPEN".into(),
+        schedule_type: "RECURRING".into(),
+        allow_customer_cancel: true,
+        allow_customer_reschedule: true,
+        booking_limit: "NONE".into(),
+        series_key: None,
+        reserved_capacity: 0,
+        reserved_release_hours: 0,
+        allowed_domains: None,
+        denied_domains: None,
+        passcode: None,
+        created_at: Utc::now(),
+    };
+
+    let first_slot = local_time::resolve(&tz, start.and_hms_opt(8, 0, 0).unwrap(), Overlap::Earliest);
+    let bookings: Vec<Booking> = (0..AVAILABILITY_BOOKINGS)
+        .map(|i| Booking::new(NewBookingParams {
+            tenant_id: "bench".into(),
+            event_id: "bench".into(),
+            start: first_slot + ChronoDuration::days(i % AVAILABILITY_DAYS) + ChronoDuration::minutes((i * 15) % (11 * 60)),
+            duration_min: 30,
+            name: "Bench".into(),
+            email: format!("bench{}@example.com", i),
+            note: None,
+            invitee_id: None,
+            location: None,
+            participant_id: None,
+        }))
+        .collect();
+
+    let per_day = || {
+        let mut days = Vec::new();
+        let mut date = start;
+        while date <= end {
+            let day_start = local_time::day_start(&tz, date);
+            let day_end = local_time::day_end(&tz, date);
+            let day_bookings: Vec<Booking> = bookings.iter()
+                .filter(|b| b.start_time < day_end && b.end_time > day_start)
+                .cloned()
+                .collect();
+            days.push((date, calculate_slots(&event, date, &day_bookings, None, None, Audience::Public)));
+            date += ChronoDuration::days(1);
+        }
+        days
+    };
+    let range = || -> Vec<(NaiveDate, Vec<String>)> {
+        calculate_range(&event, start, end, &bookings, &[], None, Audience::Public)
+            .into_iter()
+            .map(|day| (day.date, day.free_slots()))
+            .collect()
+    };
+
+    assert_eq!(per_day(), range(), "range engine and per-day calculation disagree");
+
+    let time = |run: &dyn Fn() -> Vec<(NaiveDate, Vec<String>)>| {
+        let started = Instant::now();
+        for _ in 0..AVAILABILITY_ROUNDS {
+            std::hint::black_box(run());
+        }
+        started.elapsed() / AVAILABILITY_ROUNDS
+    };
+    let per_day_time = time(&per_day);
+    let range_time = time(&range);
+
+    println!("   {} days, {} bookings, mean of {} rounds", AVAILABILITY_DAYS, AVAILABILITY_BOOKINGS, AVAILABILITY_ROUNDS);
+    println!("   Per-day calculation: {:>10.3} ms", per_day_time.as_secs_f64() * 1000.0);
+    println!("   Range engine:        {:>10.3} ms", range_time.as_secs_f64() * 1000.0);
+    println!("   Speedup:             {:>10.1}x", per_day_time.as_secs_f64() / range_time.as_secs_f64());
+}
+
 async fn setup_tenant(client: &Client) -> (String, String) {
     let slug = format!("bench-{}", Uuid::new_v4());
     let res = client.post(format!("{}/api/v1/tenants", BASE_URL))
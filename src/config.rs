@@ -10,6 +10,8 @@ pub struct Config {
     pub jwt_public_key: String, // Public key (PEM or Base64)
     pub auth_issuer: String,
     pub frontend_url: String,
+    pub payment_encryption_key: Option<String>, // Secret for bank details at rest
+    pub checkin_secret: String, // HMAC key for check-in codes
 }
 
 impl Config {
@@ -23,6 +25,10 @@ impl Config {
             jwt_public_key: env::var("JWT_PUBLIC_KEY").expect("JWT_PUBLIC_KEY must be set (Ed25519 Public Key)"),
             auth_issuer: env::var("AUTH_ISSUER").unwrap_or_else(|_| "https://api.booking-system.local".to_string()),
             frontend_url: env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:3000".to_string()),
+            payment_encryption_key: env::var("PAYMENT_ENCRYPTION_KEY").ok().filter(|k| !k.is_empty()),
+            checkin_secret: env::var("CHECKIN_SECRET")
+                .or_else(|_| env::var("JWT_SECRET_KEY"))
+                .expect("CHECKIN_SECRET or JWT_SECRET_KEY must be set"),
         }
     }
 }
\ No newline at end of file
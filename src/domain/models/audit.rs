@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::FromRow;
+
+/// Record of a privileged operation. `subject` identifies what was touched
+/// without repeating personal data (e.g. a hash of an erased email).
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct AuditLog {
+    pub id: String,
+    pub tenant_id: String,
+    pub actor: String,
+    pub action: String,
+    pub subject: String,
+    pub details: Json<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLog {
+    pub fn new(tenant_id: String, actor: String, action: &str, subject: String, details: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            tenant_id,
+            actor,
+            action: action.to_string(),
+            subject,
+            details: Json(details),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Rows touched by a data subject erasure.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ErasureSummary {
+    pub bookings_anonymized: u64,
+    pub bookings_cancelled: u64,
+    pub invitees_anonymized: u64,
+    pub mail_logs_anonymized: u64,
+    pub bank_details_deleted: u64,
+    pub idempotency_records_deleted: u64,
+    pub portal_tokens_deleted: u64,
+}
@@ -3,6 +3,7 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 use rand::{distributions::Alphanumeric, Rng};
+use crate::domain::models::{audit::AuditLog, consent::ConsentRecord, event::Event, job::Job};
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
 pub struct Booking {
@@ -21,6 +22,10 @@ pub struct Booking {
     pub management_token: String,
     pub token: Option<String>,
     pub payout: Option<i32>,
+    pub checked_in_at: Option<DateTime<Utc>>,
+    pub participant_id: Option<String>,
+    /// Raised with every change notice, so calendars replace the entry sent before.
+    pub revision: i32,
     pub created_at: DateTime<Utc>,
 }
 
@@ -34,6 +39,7 @@ pub struct NewBookingParams {
     pub note: Option<String>,
     pub invitee_id: Option<String>,
     pub location: Option<String>,
+    pub participant_id: Option<String>,
 }
 
 impl Booking {
@@ -62,11 +68,92 @@ impl Booking {
             management_token: token,
             token: None,
             payout: None,
+            checked_in_at: None,
+            participant_id: params.participant_id,
+            revision: 0,
             created_at: Utc::now(),
         }
     }
 }
 
+pub const BOOKING_LIMITS: [&str; 4] = ["NONE", "EMAIL", "INVITEE", "SERIES"];
+
+/// Existing bookings that prevent a new one under the event's `booking_limit`.
+/// Every booking that is neither cancelled nor over counts. The participant is the one
+/// the new booking gets linked to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookingLimit {
+    /// One booking per participant (deduplicated by email) and event.
+    Email { event_id: String },
+    /// One booking per invitation token and event.
+    Invitee { event_id: String, invitee_id: String },
+    /// One booking per participant across all events sharing the series key.
+    Series { tenant_id: String, series_key: String },
+}
+
+impl BookingLimit {
+    pub fn for_booking(event: &Event, booking: &Booking) -> Option<Self> {
+        match event.booking_limit.as_str() {
+            "EMAIL" => Some(BookingLimit::Email {
+                event_id: event.id.clone(),
+            }),
+            "INVITEE" => Some(BookingLimit::Invitee {
+                event_id: event.id.clone(),
+                invitee_id: booking.invitee_id.clone()?,
+            }),
+            "SERIES" => Some(BookingLimit::Series {
+                tenant_id: event.tenant_id.clone(),
+                series_key: event.series_key.clone()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+pub enum BookingOutcome {
+    Created(Booking),
+    /// The limit was reached; holds the earliest booking that counts against it.
+    Duplicate(Booking),
+}
+
+/// Rows written in the same transaction as a new booking.
+#[derive(Debug, Default)]
+pub struct BookingWrites {
+    pub jobs: Vec<Job>,
+    pub consents: Vec<ConsentRecord>,
+    pub audit: Option<AuditLog>,
+}
+
+/// Event rules staff may skip when booking on behalf of a participant.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct BookingOverrides {
+    pub min_notice: bool,
+    pub capacity: bool,
+    pub access_mode: bool,
+    pub active_range: bool,
+    /// Books a participant blocked for repeated no-shows.
+    pub ignore_block: bool,
+}
+
+impl BookingOverrides {
+    pub fn any(&self) -> bool {
+        self.min_notice || self.capacity || self.access_mode || self.active_range || self.ignore_block
+    }
+
+    /// Relaxes the notice and active range rules on a copy of the event used for the slot check.
+    pub fn apply(&self, event: &mut Event) {
+        if self.min_notice {
+            event.min_notice_general = 0;
+            event.min_notice_first = 0;
+        }
+        if self.active_range {
+            event.active_start = DateTime::<Utc>::MIN_UTC;
+            event.active_end = DateTime::<Utc>::MAX_UTC;
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
 pub struct BookingLabel {
     pub id: String,
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Longest accepted consent purpose.
+pub const MAX_PURPOSE_LEN: usize = 100;
+
+/// A purpose the participant agreed to in the booking form, e.g. the privacy
+/// notice. Kept with the booking as proof; it holds no personal data itself.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct ConsentRecord {
+    pub id: String,
+    pub tenant_id: String,
+    pub booking_id: String,
+    pub purpose: String,
+    pub granted_at: DateTime<Utc>,
+}
+
+impl ConsentRecord {
+    pub fn new(tenant_id: String, booking_id: String, purpose: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            tenant_id,
+            booking_id,
+            purpose,
+            granted_at: Utc::now(),
+        }
+    }
+}
@@ -7,6 +7,8 @@ pub struct TimeWindow {
     pub start: String,
     pub end: String,
     pub max_participants: Option<i32>,
+    pub location: Option<String>,
+    pub host_name: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -47,5 +49,17 @@ pub struct Event {
     pub schedule_type: String,
     pub allow_customer_cancel: bool,
     pub allow_customer_reschedule: bool,
+    pub booking_limit: String,
+    pub series_key: Option<String>,
+    /// Seats per slot held for token holders of a HYBRID event.
+    pub reserved_capacity: i32,
+    /// Hours before the slot start at which unclaimed reserved seats open to everyone.
+    pub reserved_release_hours: i32,
+    /// Comma-separated email domains of a DOMAIN event; subdomains match as well.
+    pub allowed_domains: Option<String>,
+    pub denied_domains: Option<String>,
+    /// Shared code of a PASSCODE event. Never part of a response.
+    #[serde(skip_serializing)]
+    pub passcode: Option<String>,
     pub created_at: DateTime<Utc>,
 }
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// A request processed under an `Idempotency-Key`. The stored response is
+/// `None` while the first request is still running.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct IdempotencyRecord {
+    pub scope: String,
+    pub idempotency_key: String,
+    pub request_hash: String,
+    pub status_code: Option<i32>,
+    pub response_body: Option<String>,
+    /// Booking the stored response holds, so erasing its participant drops it too.
+    pub booking_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    pub fn new(scope: String, idempotency_key: String, request_hash: String) -> Self {
+        Self {
+            scope,
+            idempotency_key,
+            request_hash,
+            status_code: None,
+            response_body: None,
+            booking_id: None,
+            created_at: Utc::now(),
+        }
+    }
+}
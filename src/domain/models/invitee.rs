@@ -11,8 +11,16 @@ pub struct Invitee {
     pub event_id: String,
     pub token: String,
     pub email: Option<String>,
-    pub status: String, // ACTIVE, USED
+    pub status: String, // ACTIVE, USED, REVOKED
     pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Number of bookings the token allows; it is USED once `use_count` reaches it.
+    pub max_uses: i32,
+    pub use_count: i32,
+    /// Events the token is valid for besides `event_id`.
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub event_ids: Vec<String>,
 }
 
 impl Invitee {
@@ -31,6 +39,23 @@ impl Invitee {
             email,
             status: "ACTIVE".to_string(),
             created_at: Utc::now(),
+            expires_at: None,
+            max_uses: 1,
+            use_count: 0,
+            event_ids: Vec::new(),
         }
     }
-}
\ No newline at end of file
+
+    pub fn is_valid_for(&self, event_id: &str) -> bool {
+        self.event_id == event_id || self.event_ids.iter().any(|id| id == event_id)
+    }
+
+    /// Keeps the status in line with the quota after `max_uses` changed.
+    pub fn sync_status(&mut self) {
+        if self.status == "ACTIVE" && self.use_count >= self.max_uses {
+            self.status = "USED".to_string();
+        } else if self.status == "USED" && self.use_count < self.max_uses {
+            self.status = "ACTIVE".to_string();
+        }
+    }
+}
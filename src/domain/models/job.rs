@@ -8,6 +8,9 @@ use uuid::Uuid;
 pub struct JobPayload {
     pub booking_id: String,
     pub tenant_id: String,
+    /// Extra context for jobs that are not about a single booking.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
@@ -26,11 +29,16 @@ impl Job {
         Self {
             id: Uuid::new_v4().to_string(),
             job_type: job_type.to_string(),
-            payload: Json(JobPayload { booking_id, tenant_id }),
+            payload: Json(JobPayload { booking_id, tenant_id, data: None }),
             execute_at,
             status: "PENDING".to_string(),
             error_message: None,
             created_at: Utc::now(),
         }
     }
+
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.payload.0.data = Some(data);
+        self
+    }
 }
\ No newline at end of file
@@ -8,3 +8,11 @@ pub mod event_override;
 pub mod auth;
 pub mod session;
 pub mod communication;
+pub mod payout;
+
+pub mod participant;
+pub mod audit;
+pub mod retention;
+pub mod idempotency;
+pub mod portal;
+pub mod consent;
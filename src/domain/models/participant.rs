@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// A person who booked with a tenant, identified by their normalized email.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct Participant {
+    pub id: String,
+    pub tenant_id: String,
+    pub email: String,
+    pub name: String,
+    pub notes: Option<String>,
+    /// Set by admins to let the participant book despite the no-show policy.
+    pub noshow_exempt: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Participant {
+    pub fn new(tenant_id: String, email: &str, name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            tenant_id,
+            email: normalize_email(email),
+            name,
+            notes: None,
+            noshow_exempt: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
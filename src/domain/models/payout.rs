@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Persisted settlement state of a single booking's payout.
+/// Bookings without an entry are considered unpaid.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct PayoutEntry {
+    pub booking_id: String,
+    pub tenant_id: String,
+    pub amount: i32,
+    pub status: String, // EXPORTED, PAID
+    pub payment_reference: Option<String>,
+    pub paid_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PayoutEntry {
+    pub fn paid(tenant_id: String, booking_id: String, amount: i32, payment_reference: String, paid_at: DateTime<Utc>) -> Self {
+        Self {
+            booking_id,
+            tenant_id,
+            amount,
+            status: "PAID".to_string(),
+            payment_reference: Some(payment_reference),
+            paid_at: Some(paid_at),
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn exported(tenant_id: String, booking_id: String, amount: i32, message_id: String) -> Self {
+        Self {
+            booking_id,
+            tenant_id,
+            amount,
+            status: "EXPORTED".to_string(),
+            payment_reference: Some(message_id),
+            paid_at: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Bank account a participant submitted for their payout.
+/// IBAN and BIC are stored encrypted; only the last four IBAN digits are kept in clear.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct BankDetails {
+    pub booking_id: String,
+    pub tenant_id: String,
+    pub account_holder: String,
+    #[serde(skip_serializing)]
+    pub iban_encrypted: String,
+    #[serde(skip_serializing)]
+    pub bic_encrypted: Option<String>,
+    pub iban_last4: String,
+    pub updated_at: DateTime<Utc>,
+}
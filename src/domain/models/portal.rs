@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::FromRow;
+use rand::{distributions::Alphanumeric, Rng};
+
+/// How long a magic link gives access to the portal.
+pub const PORTAL_LINK_TTL_MINUTES: i64 = 30;
+
+/// Minimum time between two magic links for the same participant.
+pub const PORTAL_LINK_COOLDOWN_SECONDS: i64 = 60;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct PortalToken {
+    pub token: String,
+    pub tenant_id: String,
+    pub participant_id: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PortalToken {
+    pub fn new(tenant_id: String, participant_id: String) -> Self {
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(48)
+            .map(char::from)
+            .collect();
+        let now = Utc::now();
+
+        Self {
+            token,
+            tenant_id,
+            participant_id,
+            expires_at: now + Duration::minutes(PORTAL_LINK_TTL_MINUTES),
+            created_at: now,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Months, Utc};
+use sqlx::FromRow;
+
+/// How long a tenant keeps personal data. Each rule is disabled when `None`.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct RetentionPolicy {
+    pub tenant_id: String,
+    /// Anonymize participant PII this many months after the booked session ended.
+    pub anonymize_after_months: Option<i32>,
+    pub mail_logs_after_months: Option<i32>,
+    pub invitees_after_months: Option<i32>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RetentionPolicy {
+    pub fn new(tenant_id: String) -> Self {
+        Self {
+            tenant_id,
+            anonymize_after_months: None,
+            mail_logs_after_months: None,
+            invitees_after_months: None,
+            last_run_at: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn cutoff(months: Option<i32>, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let months = u32::try_from(months?).ok().filter(|m| *m > 0)?;
+        now.checked_sub_months(Months::new(months))
+    }
+}
+
+/// What a retention run changed, or would change for a dry run.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub anonymize_before: Option<DateTime<Utc>>,
+    pub mail_logs_before: Option<DateTime<Utc>>,
+    pub invitees_before: Option<DateTime<Utc>>,
+    pub bookings_anonymized: u64,
+    pub participants_anonymized: u64,
+    pub bank_details_deleted: u64,
+    pub mail_logs_deleted: u64,
+    pub invitees_deleted: u64,
+}
+
+impl RetentionReport {
+    /// Empty report of `policy` as applied at `now`, with the cutoffs of its enabled rules.
+    pub fn new(policy: &RetentionPolicy, now: DateTime<Utc>, dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            anonymize_before: RetentionPolicy::cutoff(policy.anonymize_after_months, now),
+            mail_logs_before: RetentionPolicy::cutoff(policy.mail_logs_after_months, now),
+            invitees_before: RetentionPolicy::cutoff(policy.invitees_after_months, now),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bookings_anonymized + self.participants_anonymized + self.bank_details_deleted
+            + self.mail_logs_deleted + self.invitees_deleted == 0
+    }
+}
@@ -12,6 +12,12 @@ pub struct Tenant {
     #[serde(skip_serializing)]
     pub ai_api_key: Option<String>,
     pub ai_provider: Option<String>,
+    pub show_label_id: Option<String>,
+    pub noshow_label_id: Option<String>,
+    /// Number of no-shows within `noshow_window_months` that blocks self-service booking; `None` disables the policy.
+    pub noshow_block_threshold: Option<i32>,
+    pub noshow_window_months: i32,
+    pub noshow_block_months: i32,
     pub created_at: DateTime<Utc>,
 }
 
@@ -24,6 +30,11 @@ impl Tenant {
             logo_url: None,
             ai_api_key: None,
             ai_provider: Some("gemini".to_string()),
+            show_label_id: None,
+            noshow_label_id: None,
+            noshow_block_threshold: None,
+            noshow_window_months: 12,
+            noshow_block_months: 6,
             created_at: Utc::now(),
         }
     }
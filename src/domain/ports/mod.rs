@@ -1,7 +1,10 @@
 use crate::domain::models::{
-    tenant::Tenant, user::User, event::Event, booking::{Booking, BookingLabel},
+    tenant::Tenant, user::User, event::Event, booking::{Booking, BookingLabel, BookingLimit, BookingOutcome, BookingWrites},
     invitee::Invitee, event_override::EventOverride, job::Job, session::EventSession,
-    auth::RefreshTokenRecord, communication::{EmailTemplate, EmailTemplateVersion, NotificationRule, MailLog}
+    auth::RefreshTokenRecord, communication::{EmailTemplate, EmailTemplateVersion, NotificationRule, MailLog},
+    payout::{PayoutEntry, BankDetails}, participant::Participant,
+    audit::{AuditLog, ErasureSummary}, retention::{RetentionPolicy, RetentionReport},
+    idempotency::IdempotencyRecord, portal::PortalToken, consent::ConsentRecord
 };
 use crate::error::AppError;
 use async_trait::async_trait;
@@ -50,6 +53,8 @@ pub trait EventRepository: Send + Sync {
     async fn find_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<Event>, AppError>;
     async fn list(&self, tenant_id: &str) -> Result<Vec<Event>, AppError>;
     async fn update(&self, event: &Event) -> Result<Event, AppError>;
+    /// Updates `event` and queues `jobs`, raising the revision of each booking they notify.
+    async fn update_with_notices(&self, event: &Event, jobs: &[Job]) -> Result<Event, AppError>;
     async fn delete(&self, tenant_id: &str, id: &str) -> Result<(), AppError>;
 }
 
@@ -58,6 +63,7 @@ pub trait EventOverrideRepository: Send + Sync {
     async fn upsert(&self, override_entity: &EventOverride) -> Result<EventOverride, AppError>;
     async fn find_by_date(&self, event_id: &str, date: NaiveDate) -> Result<Option<EventOverride>, AppError>;
     async fn list_by_range(&self, event_id: &str, start: NaiveDate, end: NaiveDate) -> Result<Vec<EventOverride>, AppError>;
+    async fn list_by_tenant_range(&self, tenant_id: &str, start: NaiveDate, end: NaiveDate) -> Result<Vec<EventOverride>, AppError>;
     async fn delete(&self, event_id: &str, date: NaiveDate) -> Result<(), AppError>;
 }
 
@@ -67,22 +73,48 @@ pub trait SessionRepository: Send + Sync {
     async fn find_by_id(&self, id: &str) -> Result<Option<EventSession>, AppError>;
     async fn list_by_event(&self, event_id: &str) -> Result<Vec<EventSession>, AppError>;
     async fn list_by_range(&self, event_id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<EventSession>, AppError>;
+    async fn list_by_tenant_range(&self, tenant_id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<EventSession>, AppError>;
     async fn update(&self, session: &EventSession) -> Result<EventSession, AppError>;
     async fn delete(&self, id: &str) -> Result<(), AppError>;
     async fn find_overlap(&self, event_id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<EventSession>, AppError>;
+    /// Returns the sessions overlapping another session of their event or of the batch,
+    /// checked in the transaction inserting them. Nothing is written when there are any
+    /// or for a `dry_run`.
+    async fn create_many(&self, sessions: &[EventSession], dry_run: bool) -> Result<Vec<EventSession>, AppError>;
+    /// Moves the sessions to their new start and end times in one transaction, with the
+    /// same overlap check as `create_many`. Booked sessions are refused.
+    async fn update_times(&self, sessions: &[EventSession]) -> Result<Vec<EventSession>, AppError>;
+    /// Deletes the sessions in one transaction, refusing booked ones.
+    async fn delete_many(&self, ids: &[String]) -> Result<(), AppError>;
+    /// Cancels `bookings` with their pending jobs, releases the invitation tokens they used,
+    /// stores `invitees`, queues `jobs` and deletes the session, all in one transaction.
+    /// Refused when the session has a booking not among `bookings`.
+    async fn cancel_with_bookings(&self, session: &EventSession, bookings: &[Booking], invitees: &[Invitee], jobs: Vec<Job>) -> Result<Vec<Booking>, AppError>;
 }
 
 #[async_trait]
 pub trait BookingRepository: Send + Sync {
     async fn create(&self, booking: &Booking) -> Result<Booking, AppError>;
-    async fn create_with_token(&self, booking: &Booking, token: Option<String>, jobs: Vec<Job>) -> Result<Booking, AppError>;
+    /// Inserts the booking, burns the invitation token and stores `writes` in one transaction.
+    /// A booking without participant is linked to the one of its email, registered if new.
+    /// Nothing is written when `limit` already holds an active booking.
+    async fn create_with_token(&self, booking: &Booking, token: Option<String>, writes: BookingWrites, limit: Option<&BookingLimit>) -> Result<BookingOutcome, AppError>;
     async fn find_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<Booking>, AppError>;
     async fn find_by_token(&self, token: &str) -> Result<Option<Booking>, AppError>;
     async fn list_by_event(&self, tenant_id: &str, event_id: &str) -> Result<Vec<Booking>, AppError>;
     async fn list_by_tenant(&self, tenant_id: &str) -> Result<Vec<Booking>, AppError>;
+    async fn list_by_participant(&self, tenant_id: &str, participant_id: &str) -> Result<Vec<Booking>, AppError>;
     async fn list_by_range(&self, event_id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Booking>, AppError>;
+    /// Active bookings of all events of the tenant overlapping the range.
+    async fn list_by_tenant_range(&self, tenant_id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Booking>, AppError>;
     async fn update(&self, booking: &Booking) -> Result<Booking, AppError>;
+    /// Raises the booking's revision by one and returns the new one.
+    async fn next_revision(&self, tenant_id: &str, id: &str) -> Result<i32, AppError>;
     async fn cancel(&self, booking: &Booking) -> Result<Booking, AppError>;
+    /// Sets `checked_in_at` (and the show label) unless the booking is already checked in.
+    async fn check_in(&self, tenant_id: &str, id: &str, at: DateTime<Utc>, label_id: Option<&str>) -> Result<Option<Booking>, AppError>;
+    /// Applies the no-show label to an unlabelled, not checked-in, active booking.
+    async fn mark_noshow(&self, tenant_id: &str, id: &str, label_id: &str) -> Result<bool, AppError>;
     async fn delete(&self, tenant_id: &str, id: &str) -> Result<(), AppError>;
     async fn count_overlap(&self, event_id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<i64, AppError>;
     async fn find_future_active_bookings(&self, event_id: &str) -> Result<Vec<Booking>, AppError>;
@@ -97,6 +129,82 @@ pub trait BookingLabelRepository: Send + Sync {
     async fn delete(&self, tenant_id: &str, id: &str) -> Result<(), AppError>;
 }
 
+#[async_trait]
+pub trait PayoutRepository: Send + Sync {
+    async fn list_by_tenant(&self, tenant_id: &str) -> Result<Vec<PayoutEntry>, AppError>;
+    /// Stores the entries in one transaction. An entry may only replace an EXPORTED one by a
+    /// PAID one; any other existing entry refuses the whole batch with a conflict, so
+    /// concurrent requests cannot settle a booking twice.
+    async fn claim_many(&self, entries: &[PayoutEntry]) -> Result<(), AppError>;
+    async fn upsert_bank_details(&self, details: &BankDetails) -> Result<BankDetails, AppError>;
+    async fn find_bank_details(&self, booking_id: &str) -> Result<Option<BankDetails>, AppError>;
+    async fn list_bank_details(&self, tenant_id: &str) -> Result<Vec<BankDetails>, AppError>;
+}
+
+#[async_trait]
+pub trait ParticipantRepository: Send + Sync {
+    /// Resolves the participant owning the (normalized) email, creating one on first contact.
+    async fn find_or_create(&self, tenant_id: &str, email: &str, name: &str) -> Result<Participant, AppError>;
+    async fn find_by_email(&self, tenant_id: &str, email: &str) -> Result<Option<Participant>, AppError>;
+    async fn find_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<Participant>, AppError>;
+    async fn list(&self, tenant_id: &str) -> Result<Vec<Participant>, AppError>;
+    async fn list_emails(&self, tenant_id: &str, participant_id: &str) -> Result<Vec<String>, AppError>;
+    async fn update(&self, participant: &Participant) -> Result<Participant, AppError>;
+    /// Moves bookings and email aliases of `source_ids` to the target and deletes the sources.
+    async fn merge(&self, tenant_id: &str, target_id: &str, source_ids: &[String]) -> Result<(), AppError>;
+}
+
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn record(&self, entry: &AuditLog) -> Result<(), AppError>;
+    async fn list(&self, tenant_id: &str) -> Result<Vec<AuditLog>, AppError>;
+}
+
+#[async_trait]
+pub trait PrivacyRepository: Send + Sync {
+    /// Invitees and mail logs are matched on their normalized email.
+    async fn find_invitees(&self, tenant_id: &str, emails: &[String]) -> Result<Vec<Invitee>, AppError>;
+    async fn find_mail_logs(&self, tenant_id: &str, emails: &[String]) -> Result<Vec<MailLog>, AppError>;
+    async fn find_consents(&self, tenant_id: &str, booking_ids: &[String]) -> Result<Vec<ConsentRecord>, AppError>;
+    /// Anonymizes everything linked to the emails in one transaction. Bookings keep
+    /// their labels, payouts and attendance so aggregate statistics stay intact;
+    /// future ones are cancelled to free their seats.
+    async fn erase_subject(&self, tenant_id: &str, emails: &[String], booking_ids: &[String], participant_id: Option<&str>) -> Result<ErasureSummary, AppError>;
+}
+
+#[async_trait]
+pub trait RetentionRepository: Send + Sync {
+    async fn find_policy(&self, tenant_id: &str) -> Result<Option<RetentionPolicy>, AppError>;
+    async fn upsert_policy(&self, policy: &RetentionPolicy) -> Result<RetentionPolicy, AppError>;
+    async fn list_policies(&self) -> Result<Vec<RetentionPolicy>, AppError>;
+    /// Counts what applying the policy at `now` would change, without changing anything.
+    async fn report(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<RetentionReport, AppError>;
+    /// Applies the policy in one transaction.
+    async fn apply(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<RetentionReport, AppError>;
+    async fn purge_expired_refresh_tokens(&self, now: DateTime<Utc>) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+pub trait IdempotencyRepository: Send + Sync {
+    /// Stores the record unless the key is taken; returns the existing record in that case.
+    /// Expired and abandoned records count as free and are replaced.
+    async fn reserve(&self, record: &IdempotencyRecord) -> Result<Option<IdempotencyRecord>, AppError>;
+    /// Stores the response; `booking_id` names the booking it holds, if any.
+    async fn complete(&self, scope: &str, key: &str, status_code: i32, response_body: &str, booking_id: Option<&str>) -> Result<(), AppError>;
+    async fn release(&self, scope: &str, key: &str) -> Result<(), AppError>;
+    async fn purge(&self, before: DateTime<Utc>) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+pub trait PortalRepository: Send + Sync {
+    /// Stores the token and queues `job` sending it, unless the participant got a token after
+    /// `since`. Returns whether it was issued. Concurrent requests issue one token.
+    async fn issue(&self, token: &PortalToken, job: &Job, since: DateTime<Utc>) -> Result<bool, AppError>;
+    /// Returns the token unless it has expired.
+    async fn find_valid(&self, token: &str, now: DateTime<Utc>) -> Result<Option<PortalToken>, AppError>;
+    async fn purge_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError>;
+}
+
 #[async_trait]
 pub trait JobRepository: Send + Sync {
     async fn create(&self, job: &Job) -> Result<Job, AppError>;
@@ -104,6 +212,10 @@ pub trait JobRepository: Send + Sync {
     async fn list_jobs(&self, tenant_id: &str) -> Result<Vec<Job>, AppError>;
     async fn update_status(&self, id: &str, status: &str, error_message: Option<String>) -> Result<(), AppError>;
     async fn cancel_jobs_for_booking(&self, booking_id: &str) -> Result<(), AppError>;
+    /// Queues `job` unless one of its type about the same booking was created after `since`.
+    /// Returns whether it was queued. Concurrent calls queue it once, so public endpoints can
+    /// throttle the notices they trigger.
+    async fn create_unless_recent(&self, job: &Job, since: DateTime<Utc>) -> Result<bool, AppError>;
     async fn delete_jobs_by_type_and_event(&self, event_id: &str, job_type: &str) -> Result<(), AppError>;
     async fn find_future_bookings_for_event(&self, event_id: &str) -> Result<Vec<Booking>, AppError>;
 }
@@ -0,0 +1,73 @@
+use crate::domain::models::{event::Event, invitee::Invitee, participant::normalize_email};
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+/// Checks that an invitation token currently grants booking access to the event.
+pub fn check_invitee(invitee: &Invitee, event_id: &str, now: DateTime<Utc>) -> Result<(), AppError> {
+    if !invitee.is_valid_for(event_id) {
+        return Err(AppError::Forbidden("Token is not valid for this event".into()));
+    }
+    if invitee.expires_at.is_some_and(|at| at <= now) {
+        return Err(AppError::Forbidden("This invitation has expired".into()));
+    }
+    if invitee.status != "ACTIVE" || invitee.use_count >= invitee.max_uses {
+        return Err(AppError::Conflict("This invitation token has already been used".into()));
+    }
+    Ok(())
+}
+
+/// Normalizes an admin-supplied domain list for storage; an empty list clears it.
+pub fn normalize_domains(domains: Vec<String>) -> Result<Option<String>, AppError> {
+    let mut normalized: Vec<String> = Vec::new();
+    for domain in domains {
+        let domain = domain.trim().trim_start_matches('@').to_lowercase();
+        if domain.is_empty() {
+            continue;
+        }
+        if !domain.contains('.') || domain.contains(|c: char| c == ',' || c == '@' || c.is_whitespace()) {
+            return Err(AppError::Validation(format!("Invalid email domain '{}'", domain)));
+        }
+        if !normalized.contains(&domain) {
+            normalized.push(domain);
+        }
+    }
+    Ok(if normalized.is_empty() { None } else { Some(normalized.join(",")) })
+}
+
+fn matches_any(domain: &str, list: Option<&str>) -> bool {
+    list.unwrap_or_default()
+        .split(',')
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| domain == entry || domain.ends_with(&format!(".{}", entry)))
+}
+
+/// Checks the booker's email against the allow and deny lists of a DOMAIN event.
+/// The deny list wins; an empty allow list admits every domain not denied.
+pub fn check_email_domain(event: &Event, email: &str) -> Result<(), AppError> {
+    let domain = normalize_email(email).rsplit_once('@')
+        .map(|(_, domain)| domain.to_string())
+        .unwrap_or_default();
+
+    let allowed = event.allowed_domains.is_none() || matches_any(&domain, event.allowed_domains.as_deref());
+    if domain.is_empty() || !allowed || matches_any(&domain, event.denied_domains.as_deref()) {
+        return Err(AppError::Forbidden("Bookings for this event are limited to certain email addresses".into()));
+    }
+    Ok(())
+}
+
+/// Checks the shared code of a PASSCODE event.
+pub fn check_passcode(event: &Event, passcode: Option<&str>) -> Result<(), AppError> {
+    match (event.passcode.as_deref(), passcode) {
+        (Some(expected), Some(given)) if same_secret(expected, given.trim()) => Ok(()),
+        (_, None) => Err(AppError::Forbidden("Passcode required".into())),
+        _ => Err(AppError::Forbidden("Invalid passcode".into())),
+    }
+}
+
+/// Compares in time independent of where the values differ. Hashing first makes the
+/// lengths equal as well.
+fn same_secret(expected: &str, given: &str) -> bool {
+    let (expected, given) = (Sha256::digest(expected.as_bytes()), Sha256::digest(given.as_bytes()));
+    expected.iter().zip(given.iter()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
@@ -1,184 +1,451 @@
-use chrono::{NaiveDate, NaiveTime, Datelike, Timelike, Utc, Duration, TimeZone};
+use chrono::{DateTime, NaiveDate, NaiveTime, Datelike, Timelike, Utc, Duration, Weekday};
 use chrono_tz::Tz;
-use crate::domain::models::event::{Event, WeekdayConfig};
+use crate::domain::models::event::{Event, TimeWindow, WeekdayConfig};
 use crate::domain::models::booking::Booking;
 use crate::domain::models::event_override::EventOverride;
 use crate::domain::models::session::EventSession;
-use std::cmp::{max, min};
+use crate::domain::services::local_time;
+use serde::Serialize;
+use std::cmp::{max, min, Reverse};
+use std::collections::HashMap;
 
 const TOTAL_MINUTES: usize = 1440;
 
-pub fn calculate_slots(
-    event: &Event,
-    date: NaiveDate,
-    existing_bookings: &[Booking],
-    override_rule: Option<&EventOverride>,
-    manual_sessions: Option<&[EventSession]>
-) -> Vec<String> {
+/// Days after the requested date searched for alternatives to a taken slot.
+pub const ALTERNATIVE_SEARCH_DAYS: i64 = 7;
+pub const MAX_ALTERNATIVES: usize = 3;
+
+/// Who is asking for availability. Only HYBRID events treat the two differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Audience {
+    Public,
+    /// Holder of a valid invitation token, may use the reserved seats.
+    Invited,
+}
+
+impl Audience {
+    pub fn for_booking(booking: &Booking) -> Self {
+        if booking.invitee_id.is_some() { Audience::Invited } else { Audience::Public }
+    }
+}
+
+/// Seats of a slot the audience may not take because they are still held for token holders.
+fn reserved_seats(event: &Event, audience: Audience, slot_start: DateTime<Utc>, now: DateTime<Utc>) -> i32 {
+    if event.access_mode != "HYBRID" || audience == Audience::Invited {
+        return 0;
+    }
+    if slot_start - Duration::hours(event.reserved_release_hours as i64) <= now {
+        return 0;
+    }
+    event.reserved_capacity.max(0)
+}
+
+/// Seats taken for the audience. Bookings made with a token use up the reservation
+/// before taking public seats.
+fn occupied(count: i32, invited: i32, reserved: i32) -> i32 {
+    count - invited + invited.max(reserved)
+}
+
+/// A slot as shown on the booking page. Fully booked slots are included, so the
+/// page can show them as full instead of leaving a gap.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SlotDetail {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub capacity: i32,
+    pub remaining_seats: i32,
+    pub location: String,
+    pub host_name: String,
+}
+
+impl SlotDetail {
+    pub fn is_bookable(&self) -> bool {
+        self.remaining_seats > 0
+    }
+}
+
+pub(crate) fn sort_details(details: &mut Vec<SlotDetail>) {
+    // Two windows starting at the same time offer the slot with the more free seats
+    details.sort_by_key(|d| (d.start_time, Reverse(d.remaining_seats)));
+    details.dedup_by_key(|d| d.start_time);
+}
+
+fn parse_config(json: &str) -> WeekdayConfig {
+    serde_json::from_str(json).unwrap_or_default()
+}
+
+fn weekday_windows(config: &WeekdayConfig, weekday: Weekday) -> Option<&Vec<TimeWindow>> {
+    match weekday {
+        Weekday::Mon => config.monday.as_ref(),
+        Weekday::Tue => config.tuesday.as_ref(),
+        Weekday::Wed => config.wednesday.as_ref(),
+        Weekday::Thu => config.thursday.as_ref(),
+        Weekday::Fri => config.friday.as_ref(),
+        Weekday::Sat => config.saturday.as_ref(),
+        Weekday::Sun => config.sunday.as_ref(),
+    }
+}
+
+fn day_bounds(tz: &Tz, date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    (local_time::day_start(tz, date), local_time::day_end(tz, date))
+}
+
+/// UTC span of the bookings and sessions that can affect the slots of the local days
+/// `start` to `end`. Includes the following morning for windows running past midnight.
+pub fn availability_span(event: &Event, start: NaiveDate, end: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
     let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
+    (day_bounds(&tz, start).0, day_bounds(&tz, end + Duration::days(1)).1)
+}
 
-    if event.schedule_type == "MANUAL" {
-        let mut valid_slots = Vec::new();
-        if let Some(sessions) = manual_sessions {
-            for session in sessions {
-                let session_start_tz = session.start_time.with_timezone(&tz);
-                if session_start_tz.date_naive() != date {
-                    continue;
-                }
+/// Local days of the event whose slots can start on the viewer's days `start` to `end`,
+/// including the day before for windows running past midnight.
+pub fn event_days(event: &Event, viewer: &Tz, start: NaiveDate, end: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
+    let first = local_time::day_start(viewer, start).with_timezone(&tz).date_naive() - Duration::days(1);
+    let last = local_time::day_end(viewer, end).with_timezone(&tz).date_naive();
+    (first, last)
+}
 
-                let booking_count = existing_bookings.iter().filter(|b| {
-                    b.start_time < session.end_time && b.end_time > session.start_time
-                }).count();
+/// Local day of the event whose windows offer a slot starting at `start`. That is the
+/// day before the one it starts on when a window of the previous day, as configured
+/// there by `previous_override`, runs past midnight over it.
+pub fn window_date(event: &Event, start: DateTime<Utc>, previous_override: Option<&EventOverride>) -> NaiveDate {
+    let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
+    let local = start.with_timezone(&tz);
+    let date = local.date_naive();
+    let previous = date - Duration::days(1);
 
-                if (booking_count as i32) < session.max_participants {
-                    valid_slots.push(session.start_time.to_rfc3339());
-                }
-            }
+    if event.schedule_type == "MANUAL" || previous_override.is_some_and(|r| r.is_unavailable) {
+        return date;
+    }
+    let override_config = previous_override
+        .and_then(|rule| rule.override_config_json.as_ref())
+        .and_then(|json| serde_json::from_str::<WeekdayConfig>(json).ok());
+    let config = override_config.unwrap_or_else(|| parse_config(&event.config_json));
+
+    let runs_over = weekday_windows(&config, previous.weekday()).into_iter().flatten().any(|window| {
+        match (NaiveTime::parse_from_str(&window.start, "%H:%M"), NaiveTime::parse_from_str(&window.end, "%H:%M")) {
+            (Ok(win_start), Ok(win_end)) => win_end < win_start && local.time() < win_end,
+            _ => false,
+        }
+    });
+    if runs_over { previous } else { date }
+}
+
+/// Everything that stays the same across the days of one availability request.
+struct SlotContext<'a> {
+    event: &'a Event,
+    tz: Tz,
+    base_config: WeekdayConfig,
+    audience: Audience,
+    now: DateTime<Utc>,
+}
+
+impl<'a> SlotContext<'a> {
+    fn new(event: &'a Event, audience: Audience) -> Self {
+        Self {
+            event,
+            tz: event.timezone.parse().unwrap_or(chrono_tz::UTC),
+            base_config: parse_config(&event.config_json),
+            audience,
+            now: Utc::now(),
         }
-        valid_slots.sort();
-        valid_slots.dedup();
-        return valid_slots;
     }
 
-    if override_rule.is_some_and(|r| r.is_unavailable) {
-        return Vec::new();
+    fn manual_slots(&self, date: NaiveDate, bookings: &[&Booking], sessions: &[&EventSession]) -> Vec<SlotDetail> {
+        let mut details = Vec::new();
+        for session in sessions {
+            let session_start_tz = session.start_time.with_timezone(&self.tz);
+            if session_start_tz.date_naive() != date {
+                continue;
+            }
+
+            let overlapping: Vec<&&Booking> = bookings.iter().filter(|b| {
+                b.start_time < session.end_time && b.end_time > session.start_time
+            }).collect();
+            let invited_count = overlapping.iter().filter(|b| b.invitee_id.is_some()).count();
+            let reserved = reserved_seats(self.event, self.audience, session.start_time, self.now);
+            let remaining = session.max_participants - occupied(overlapping.len() as i32, invited_count as i32, reserved);
+
+            details.push(SlotDetail {
+                start_time: session.start_time,
+                end_time: session.end_time,
+                capacity: session.max_participants,
+                remaining_seats: remaining.max(0),
+                location: session.location.clone().unwrap_or_else(|| self.event.location.clone()),
+                host_name: session.host_name.clone().unwrap_or_else(|| self.event.host_name.clone()),
+            });
+        }
+        sort_details(&mut details);
+        details
     }
 
-    let config: WeekdayConfig = if let Some(rule) = override_rule {
-        if let Some(ref json) = rule.override_config_json {
-            serde_json::from_str(json).unwrap_or_else(|_|
-                serde_json::from_str(&event.config_json).unwrap_or_default()
-            )
+    /// `bookings` must contain the bookings overlapping the day, in any order.
+    fn day_slots(&self, date: NaiveDate, bookings: &[&Booking], override_rule: Option<&EventOverride>) -> Vec<SlotDetail> {
+        let event = self.event;
+        let tz = self.tz;
+
+        if override_rule.is_some_and(|r| r.is_unavailable) {
+            return Vec::new();
+        }
+
+        let override_config = override_rule
+            .and_then(|rule| rule.override_config_json.as_ref())
+            .and_then(|json| serde_json::from_str::<WeekdayConfig>(json).ok());
+        let config = override_config.as_ref().unwrap_or(&self.base_config);
+
+        let day_max_capacity = if let Some(rule) = override_rule
+            && let Some(cap) = rule.override_max_participants {
+            cap
         } else {
-            serde_json::from_str(&event.config_json).unwrap_or_default()
+            event.max_participants
+        };
+        let day_location = override_rule.and_then(|r| r.location.as_deref()).unwrap_or(&event.location);
+        let day_host = override_rule.and_then(|r| r.host_name.as_deref()).unwrap_or(&event.host_name);
+
+        let duration_min = event.duration_min as usize;
+        let interval_min = event.interval_min as usize;
+
+        if duration_min == 0 || interval_min == 0 {
+            return Vec::new();
         }
-    } else {
-        serde_json::from_str(&event.config_json).unwrap_or_default()
-    };
-
-    let day_max_capacity = if let Some(rule) = override_rule
-        && let Some(cap) = rule.override_max_participants {
-        cap
-    } else {
-        event.max_participants
-    };
-
-    let mut minute_counts = [0u8; TOTAL_MINUTES];
-    let day_start_tz = tz.from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap()).single().unwrap();
-    let day_end_tz = tz.from_local_datetime(&date.and_hms_opt(23, 59, 59).unwrap()).single().unwrap();
-
-    let day_start_utc = day_start_tz.with_timezone(&Utc);
-    let day_end_utc = day_end_tz.with_timezone(&Utc);
-
-    let mut earliest_booking_start = None;
-
-    for booking in existing_bookings {
-        let b_start = max(booking.start_time, day_start_utc);
-        let b_end = min(booking.end_time, day_end_utc);
-
-        if b_start < b_end {
-            match earliest_booking_start {
-                Some(current_min) if booking.start_time < current_min => {
-                    earliest_booking_start = Some(booking.start_time);
+
+        let Some(windows) = weekday_windows(config, date.weekday()).filter(|w| !w.is_empty()) else {
+            return Vec::new();
+        };
+
+        // Minutes after local midnight. A window ending before it starts runs past midnight,
+        // its slots still belong to the day the window starts on.
+        let windows: Vec<(usize, usize, &TimeWindow)> = windows.iter().filter_map(|window| {
+            let start = NaiveTime::parse_from_str(&window.start, "%H:%M").ok()?;
+            let end = NaiveTime::parse_from_str(&window.end, "%H:%M").ok()?;
+            let start_idx = (start.hour() * 60 + start.minute()) as usize;
+            let mut end_idx = (end.hour() * 60 + end.minute()) as usize;
+            if end_idx == 1439 { end_idx = TOTAL_MINUTES; }
+            if end_idx < start_idx { end_idx += TOTAL_MINUTES; }
+            Some((start_idx, end_idx, window))
+        }).collect();
+        let overnight = windows.iter().any(|&(_, end_idx, _)| end_idx > TOTAL_MINUTES);
+
+        let (day_start_utc, _) = day_bounds(&tz, date);
+        let (_, span_end_utc) = day_bounds(&tz, if overnight { date + Duration::days(1) } else { date });
+
+        // Occupancy per UTC minute since the local midnight
+        let span_minutes = ((span_end_utc - day_start_utc).num_minutes() + 1) as usize;
+        let mut minute_counts = vec![0i32; span_minutes];
+        let mut invited_counts = vec![0i32; span_minutes];
+
+        let mut earliest_booking_start = None;
+
+        for booking in bookings {
+            let b_start = max(booking.start_time, day_start_utc);
+            let b_end = min(booking.end_time, span_end_utc);
+
+            if b_start < b_end {
+                match earliest_booking_start {
+                    Some(current_min) if booking.start_time < current_min => {
+                        earliest_booking_start = Some(booking.start_time);
+                    }
+                    None => {
+                        earliest_booking_start = Some(booking.start_time);
+                    }
+                    _ => {}
+                }
+
+                let s_idx = min((b_start - day_start_utc).num_minutes() as usize, span_minutes);
+                let e_idx = min((b_end - day_start_utc).num_minutes() as usize, span_minutes);
+
+                for count in &mut minute_counts[s_idx..e_idx] {
+                    *count += 1;
                 }
-                None => {
-                    earliest_booking_start = Some(booking.start_time);
+                if booking.invitee_id.is_some() {
+                    for count in &mut invited_counts[s_idx..e_idx] {
+                        *count += 1;
+                    }
                 }
-                _ => {}
             }
+        }
+
+        let cutoff_general = self.now + Duration::minutes(event.min_notice_general as i64);
+        let cutoff_first = self.now + Duration::minutes(event.min_notice_first as i64);
+
+        let mut details = Vec::new();
+        let midnight = date.and_time(NaiveTime::MIN);
+
+        for (win_start_idx, win_end_idx, window) in windows {
+            // Hierarchy: Window Specific > Override Specific (Day) > Event Global
+            let window_capacity = window.max_participants.unwrap_or(day_max_capacity);
+            let location = window.location.as_deref().unwrap_or(day_location);
+            let host_name = window.host_name.as_deref().unwrap_or(day_host);
 
-            let start_diff = b_start.timestamp() - day_start_utc.timestamp();
-            let end_diff = b_end.timestamp() - day_start_utc.timestamp();
+            let mut cursor = win_start_idx;
+            while cursor + duration_min <= win_end_idx {
+                let local = midnight + Duration::minutes(cursor as i64);
 
-            let s_idx = max(0, min(start_diff / 60, TOTAL_MINUTES as i64)) as usize;
-            let e_idx = max(0, min(end_diff / 60, TOTAL_MINUTES as i64)) as usize;
+                // Repeated local times offer both instants, skipped ones are shifted forward
+                for slot_utc in local_time::resolve_all(&tz, local) {
+                    let slot_end_utc = slot_utc + Duration::minutes(duration_min as i64);
 
-            for count in &mut minute_counts[s_idx..e_idx] {
-                *count = count.saturating_add(1);
+                    let required_cutoff = if let Some(first_start) = earliest_booking_start {
+                        if slot_utc > first_start {
+                            cutoff_general
+                        } else {
+                            cutoff_first
+                        }
+                    } else {
+                        cutoff_first
+                    };
+
+                    // Check capacity for this specific slot duration
+                    let slot_idx = min((slot_utc - day_start_utc).num_minutes().max(0) as usize, span_minutes);
+                    let slot_end_idx = min(slot_idx + duration_min, span_minutes);
+                    let reserved = reserved_seats(event, self.audience, slot_utc, self.now);
+                    let peak = (slot_idx..slot_end_idx)
+                        .map(|i| occupied(minute_counts[i], invited_counts[i], reserved))
+                        .max()
+                        .unwrap_or(reserved);
+                    let remaining = window_capacity - peak;
+
+                    if slot_utc > required_cutoff
+                        && slot_utc >= event.active_start
+                        && slot_end_utc <= event.active_end
+                    {
+                        details.push(SlotDetail {
+                            start_time: slot_utc,
+                            end_time: slot_end_utc,
+                            capacity: window_capacity,
+                            remaining_seats: remaining.max(0),
+                            location: location.to_string(),
+                            host_name: host_name.to_string(),
+                        });
+                    }
+                }
+                cursor += interval_min;
             }
         }
+
+        sort_details(&mut details);
+        details
     }
+}
 
-    let now_utc = Utc::now();
-    let cutoff_general = now_utc + Duration::minutes(event.min_notice_general as i64);
-    let cutoff_first = now_utc + Duration::minutes(event.min_notice_first as i64);
+/// Free slots of the day as RFC 3339 start times.
+pub fn calculate_slots(
+    event: &Event,
+    date: NaiveDate,
+    existing_bookings: &[Booking],
+    override_rule: Option<&EventOverride>,
+    manual_sessions: Option<&[EventSession]>,
+    audience: Audience,
+) -> Vec<String> {
+    free_slots(&calculate_slot_details(event, date, existing_bookings, override_rule, manual_sessions, audience))
+}
 
-    let mut valid_slots = Vec::new();
-    let duration_min = event.duration_min as usize;
-    let interval_min = event.interval_min as usize;
+/// Start times of the bookable slots among `details`.
+pub fn free_slots(details: &[SlotDetail]) -> Vec<String> {
+    details.iter().filter(|d| d.is_bookable()).map(|d| d.start_time.to_rfc3339()).collect()
+}
 
-    if duration_min == 0 || interval_min == 0 {
-        return Vec::new();
+/// All slots of the day, fully booked ones included.
+pub fn calculate_slot_details(
+    event: &Event,
+    date: NaiveDate,
+    existing_bookings: &[Booking],
+    override_rule: Option<&EventOverride>,
+    manual_sessions: Option<&[EventSession]>,
+    audience: Audience,
+) -> Vec<SlotDetail> {
+    let ctx = SlotContext::new(event, audience);
+    let bookings: Vec<&Booking> = existing_bookings.iter().collect();
+
+    if event.schedule_type == "MANUAL" {
+        let sessions: Vec<&EventSession> = manual_sessions.unwrap_or_default().iter().collect();
+        return ctx.manual_slots(date, &bookings, &sessions);
     }
+    ctx.day_slots(date, &bookings, override_rule)
+}
 
-    let daily_windows = match date.weekday() {
-        chrono::Weekday::Mon => &config.monday,
-        chrono::Weekday::Tue => &config.tuesday,
-        chrono::Weekday::Wed => &config.wednesday,
-        chrono::Weekday::Thu => &config.thursday,
-        chrono::Weekday::Fri => &config.friday,
-        chrono::Weekday::Sat => &config.saturday,
-        chrono::Weekday::Sun => &config.sunday,
-    };
-
-    if let Some(windows) = daily_windows {
-        for window in windows {
-            // Determine capacity for this window.
-            // Hierarchy: Window Specific > Override Specific (Day) > Event Global
-            let window_capacity = window.max_participants.unwrap_or(day_max_capacity);
+/// Slots of one day of a range, fully booked ones included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayAvailability {
+    pub date: NaiveDate,
+    pub slots: Vec<SlotDetail>,
+}
 
-            if let (Ok(start), Ok(end)) = (
-                NaiveTime::parse_from_str(&window.start, "%H:%M"),
-                NaiveTime::parse_from_str(&window.end, "%H:%M")
-            ) {
-                let win_start_idx = (start.hour() * 60 + start.minute()) as usize;
-                let mut win_end_idx = (end.hour() * 60 + end.minute()) as usize;
-                if win_end_idx == 1439 { win_end_idx = 1440; }
-
-                let mut cursor = win_start_idx;
-                while cursor + duration_min <= win_end_idx {
-                    let hour = (cursor / 60) as u32;
-                    let minute = (cursor % 60) as u32;
-
-                    if let Some(nt) = NaiveTime::from_hms_opt(hour, minute, 0)
-                        && let Some(slot_tz) = tz.from_local_datetime(&date.and_time(nt)).single() {
-                            let slot_utc = slot_tz.with_timezone(&Utc);
-                            let slot_end_utc = slot_utc + Duration::minutes(duration_min as i64);
-
-                            let required_cutoff = if let Some(first_start) = earliest_booking_start {
-                                if slot_utc > first_start {
-                                    cutoff_general
-                                } else {
-                                    cutoff_first
-                                }
-                            } else {
-                                cutoff_first
-                            };
-
-                            // Check capacity for this specific slot duration
-                            let mut is_capacity_ok = true;
-                            for i in cursor..(cursor + duration_min) {
-                                if i < TOTAL_MINUTES && minute_counts[i] as i32 >= window_capacity {
-                                    is_capacity_ok = false;
-                                    break;
-                                }
-                            }
-
-                            if slot_utc > required_cutoff
-                                && slot_utc >= event.active_start
-                                && slot_end_utc <= event.active_end
-                                && is_capacity_ok
-                            {
-                                valid_slots.push(slot_utc.to_rfc3339());
-                            }
-                        }
-                    cursor += interval_min;
-                }
-            }
+impl DayAvailability {
+    pub fn free_slots(&self) -> Vec<String> {
+        free_slots(&self.slots)
+    }
+}
+
+/// Computes the slots of every day from `start` to `end` in one pass: the
+/// config is parsed once and the bookings are swept in start order instead of
+/// being filtered again for every day. Gives the same slots as calling
+/// `calculate_slot_details` per day with the bookings of its `availability_span`.
+pub fn calculate_range(
+    event: &Event,
+    start: NaiveDate,
+    end: NaiveDate,
+    bookings: &[Booking],
+    overrides: &[EventOverride],
+    manual_sessions: Option<&[EventSession]>,
+    audience: Audience,
+) -> Vec<DayAvailability> {
+    let ctx = SlotContext::new(event, audience);
+    let is_manual = event.schedule_type == "MANUAL";
+
+    let mut sorted: Vec<&Booking> = bookings.iter().collect();
+    sorted.sort_by_key(|b| b.start_time);
+
+    let overrides_by_date: HashMap<NaiveDate, &EventOverride> = overrides.iter().map(|o| (o.date, o)).collect();
+    let mut sessions_by_date: HashMap<NaiveDate, Vec<&EventSession>> = HashMap::new();
+    for session in manual_sessions.unwrap_or_default() {
+        sessions_by_date.entry(session.start_time.with_timezone(&ctx.tz).date_naive()).or_default().push(session);
+    }
+
+    let mut days = Vec::new();
+    let mut next_booking = 0;
+    let mut active: Vec<&Booking> = Vec::new();
+    let mut date = start;
+
+    while date <= end {
+        let (day_start_utc, _) = day_bounds(&ctx.tz, date);
+        let (_, span_end_utc) = day_bounds(&ctx.tz, date + Duration::days(1));
+
+        // Days are visited in order, so a booking ending before this day never overlaps a later one
+        while next_booking < sorted.len() && sorted[next_booking].start_time < span_end_utc {
+            active.push(sorted[next_booking]);
+            next_booking += 1;
         }
+        active.retain(|b| b.end_time > day_start_utc);
+
+        let slots = if span_end_utc < event.active_start || day_start_utc > event.active_end {
+            Vec::new()
+        } else if is_manual {
+            ctx.manual_slots(date, &active, sessions_by_date.get(&date).map(Vec::as_slice).unwrap_or_default())
+        } else {
+            ctx.day_slots(date, &active, overrides_by_date.get(&date).copied())
+        };
+
+        days.push(DayAvailability { date, slots });
+        date += Duration::days(1);
     }
+    days
+}
+
+/// Picks the slots closest in time to the requested start, in chronological order.
+pub fn nearest_slots(slots: &[String], requested: DateTime<Utc>, limit: usize) -> Vec<String> {
+    let mut candidates: Vec<(i64, &String)> = slots.iter()
+        .filter_map(|slot| {
+            let start = DateTime::parse_from_rfc3339(slot).ok()?.with_timezone(&Utc);
+            (start != requested).then(|| ((start - requested).num_minutes().abs(), slot))
+        })
+        .collect();
+    candidates.sort();
+    candidates.truncate(limit);
 
-    valid_slots.sort();
-    valid_slots.dedup();
-    valid_slots
+    let mut nearest: Vec<String> = candidates.into_iter().map(|(_, slot)| slot.clone()).collect();
+    nearest.sort();
+    nearest.dedup();
+    nearest
 }
\ No newline at end of file
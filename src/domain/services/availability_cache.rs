@@ -0,0 +1,152 @@
+use crate::domain::models::event::Event;
+use crate::domain::services::availability::{Audience, SlotDetail};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+use std::cmp::max;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Slots depend on the current time through the notice periods and reserved seat
+/// releases, so entries are recomputed after this long even without a change.
+pub const ENTRY_TTL: Duration = Duration::from_secs(60);
+
+/// Cached days per tenant. A busy tenant evicts its own entries, never another tenant's.
+pub const MAX_ENTRIES_PER_TENANT: usize = 2048;
+
+/// Tenants with cached days. The least recently used one is dropped beyond this.
+pub const MAX_TENANTS: usize = 256;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+    pub evictions: u64,
+    pub entries: usize,
+}
+
+#[derive(Clone, Hash, PartialEq, Eq)]
+struct CacheKey {
+    event_id: String,
+    audience: Audience,
+    date: NaiveDate,
+}
+
+struct CachedDay {
+    slots: Vec<SlotDetail>,
+    cached_at: Instant,
+}
+
+#[derive(Default)]
+struct TenantCache {
+    entries: HashMap<CacheKey, CachedDay>,
+    stats: CacheStats,
+    last_used: Option<Instant>,
+}
+
+impl TenantCache {
+    fn remove_where(&mut self, predicate: impl Fn(&CacheKey) -> bool) {
+        let before = self.entries.len();
+        self.entries.retain(|key, _| !predicate(key));
+        self.stats.invalidations += (before - self.entries.len()) as u64;
+    }
+}
+
+/// In-process cache of the slots of single days, as served by the public
+/// `/dates` and `/slots` endpoints. Bookings always recompute availability.
+#[derive(Default)]
+pub struct AvailabilityCache {
+    tenants: Mutex<HashMap<String, TenantCache>>,
+}
+
+/// The cache of the tenant, making room for it when the tenant is new.
+fn tenant_cache<'a>(tenants: &'a mut HashMap<String, TenantCache>, tenant_id: &str, now: Instant) -> &'a mut TenantCache {
+    if !tenants.contains_key(tenant_id) && tenants.len() >= MAX_TENANTS {
+        let least_recent = tenants.iter()
+            .min_by_key(|(_, tenant)| tenant.last_used)
+            .map(|(id, _)| id.clone());
+        if let Some(id) = least_recent {
+            tenants.remove(&id);
+        }
+    }
+    let tenant = tenants.entry(tenant_id.to_string()).or_default();
+    tenant.last_used = Some(now);
+    tenant
+}
+
+impl AvailabilityCache {
+    pub fn get(&self, tenant_id: &str, event_id: &str, audience: Audience, date: NaiveDate) -> Option<Vec<SlotDetail>> {
+        let mut tenants = self.tenants.lock().unwrap();
+        let now = Instant::now();
+        let tenant = tenant_cache(&mut tenants, tenant_id, now);
+
+        let key = CacheKey { event_id: event_id.to_string(), audience, date };
+        match tenant.entries.get(&key) {
+            Some(day) if now.duration_since(day.cached_at) < ENTRY_TTL => {
+                tenant.stats.hits += 1;
+                Some(day.slots.clone())
+            }
+            _ => {
+                tenant.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn put(&self, tenant_id: &str, event_id: &str, audience: Audience, date: NaiveDate, slots: Vec<SlotDetail>) {
+        let mut tenants = self.tenants.lock().unwrap();
+        let now = Instant::now();
+        let tenant = tenant_cache(&mut tenants, tenant_id, now);
+
+        let key = CacheKey { event_id: event_id.to_string(), audience, date };
+        if !tenant.entries.contains_key(&key) && tenant.entries.len() >= MAX_ENTRIES_PER_TENANT {
+            let before = tenant.entries.len();
+            tenant.entries.retain(|_, day| now.duration_since(day.cached_at) < ENTRY_TTL);
+            if tenant.entries.len() >= MAX_ENTRIES_PER_TENANT
+                && let Some(oldest) = tenant.entries.iter().min_by_key(|(_, day)| day.cached_at).map(|(key, _)| key.clone())
+            {
+                tenant.entries.remove(&oldest);
+            }
+            tenant.stats.evictions += (before - tenant.entries.len()) as u64;
+        }
+        tenant.entries.insert(key, CachedDay { slots, cached_at: now });
+    }
+
+    /// Drops the given days of an event.
+    pub fn invalidate_dates(&self, tenant_id: &str, event_id: &str, dates: &[NaiveDate]) {
+        if let Some(tenant) = self.tenants.lock().unwrap().get_mut(tenant_id) {
+            tenant.remove_where(|key| key.event_id == event_id && dates.contains(&key.date));
+        }
+    }
+
+    /// Drops every local day of the event touched by the UTC span, e.g. a booking or session,
+    /// and the day before for windows running past midnight.
+    pub fn invalidate_span(&self, tenant_id: &str, event: &Event, start: DateTime<Utc>, end: DateTime<Utc>) {
+        let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
+        let first = start.with_timezone(&tz).date_naive() - Days::new(1);
+        let last = max(first, end.with_timezone(&tz).date_naive());
+        if let Some(tenant) = self.tenants.lock().unwrap().get_mut(tenant_id) {
+            tenant.remove_where(|key| key.event_id == event.id && key.date >= first && key.date <= last);
+        }
+    }
+
+    pub fn invalidate_event(&self, tenant_id: &str, event_id: &str) {
+        if let Some(tenant) = self.tenants.lock().unwrap().get_mut(tenant_id) {
+            tenant.remove_where(|key| key.event_id == event_id);
+        }
+    }
+
+    pub fn invalidate_tenant(&self, tenant_id: &str) {
+        if let Some(tenant) = self.tenants.lock().unwrap().get_mut(tenant_id) {
+            tenant.remove_where(|_| true);
+        }
+    }
+
+    pub fn stats(&self, tenant_id: &str) -> CacheStats {
+        self.tenants.lock().unwrap().get(tenant_id)
+            .map(|tenant| CacheStats { entries: tenant.entries.len(), ..tenant.stats })
+            .unwrap_or_default()
+    }
+}
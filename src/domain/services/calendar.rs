@@ -1,17 +1,20 @@
 use crate::domain::models::{booking::Booking, event::Event};
 use icalendar::{Calendar, Component, Event as IcalEvent, EventLike};
 
-/// Generates an iCalendar (.ics) string for a specific booking
+/// Generates an iCalendar (.ics) string for a specific booking. Sent as a request, calendars
+/// replace an entry with the same uid by one with a higher sequence, the booking's revision.
 pub fn generate_ics(event: &Event, booking: &Booking) -> String {
     let mut calendar = Calendar::new();
+    calendar.append_property(("METHOD", "REQUEST"));
     
     let ical_event = IcalEvent::new()
         .summary(&event.title_en)
         .description(&event.desc_en)
-        .location(&event.location)
+        .location(booking.location.as_deref().unwrap_or(&event.location))
         .starts(booking.start_time)
         .ends(booking.end_time)
         .uid(&booking.id)
+        .sequence(booking.revision.max(0) as u32)
         .done(); 
 
     calendar.push(ical_event);
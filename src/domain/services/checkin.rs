@@ -0,0 +1,76 @@
+use hmac::{Hmac, Mac};
+use qrcode::{Color, QrCode};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Signature bytes kept in the code. 12 bytes keep the QR small while
+/// still being infeasible to guess.
+const SIGNATURE_LEN: usize = 12;
+
+fn mac(secret: &str, booking_id: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(b"checkin:");
+    mac.update(booking_id.as_bytes());
+    mac
+}
+
+/// Creates the check-in code printed on the ticket: `<booking_id>.<signature>`.
+pub fn sign_code(secret: &str, booking_id: &str) -> String {
+    let signature = mac(secret, booking_id).finalize().into_bytes();
+    format!("{}.{}", booking_id, hex::encode(&signature[..SIGNATURE_LEN]))
+}
+
+/// Verifies a scanned code and returns the booking id it belongs to.
+pub fn verify_code(secret: &str, code: &str) -> Option<String> {
+    let (booking_id, signature) = code.trim().rsplit_once('.')?;
+    let signature = hex::decode(signature).ok()?;
+    if signature.len() != SIGNATURE_LEN {
+        return None;
+    }
+
+    mac(secret, booking_id).verify_truncated_left(&signature).ok()?;
+    Some(booking_id.to_string())
+}
+
+/// Renders the code as a QR matrix built from table cells. Unlike images,
+/// tables are displayed by every mail client without remote content or attachments.
+/// Runs of equal modules are merged with `colspan` to keep the markup small.
+pub fn render_qr_html(data: &str) -> String {
+    const MODULE_PX: usize = 4;
+    const QUIET_ZONE: usize = 4;
+
+    let code = match QrCode::new(data.as_bytes()) {
+        Ok(code) => code,
+        Err(_) => return String::new(),
+    };
+    let width = code.width();
+    let colors = code.to_colors();
+    let size = width + 2 * QUIET_ZONE;
+    let is_dark = |x: usize, y: usize| {
+        (QUIET_ZONE..width + QUIET_ZONE).contains(&x)
+            && (QUIET_ZONE..width + QUIET_ZONE).contains(&y)
+            && colors[(y - QUIET_ZONE) * width + (x - QUIET_ZONE)] == Color::Dark
+    };
+
+    let mut html = format!(
+        "<table role=\"presentation\" cellpadding=\"0\" cellspacing=\"0\" border=\"0\" width=\"{}\" style=\"border-collapse:collapse;margin:0 auto;\">",
+        size * MODULE_PX
+    );
+    for y in 0..size {
+        html.push_str(&format!("<tr height=\"{}\">", MODULE_PX));
+        let mut x = 0;
+        while x < size {
+            let dark = is_dark(x, y);
+            let run = (x..size).take_while(|&i| is_dark(i, y) == dark).count();
+            html.push_str(&format!(
+                "<td colspan=\"{}\" width=\"{}\" height=\"{}\" bgcolor=\"{}\"></td>",
+                run, run * MODULE_PX, MODULE_PX, if dark { "#000000" } else { "#ffffff" }
+            ));
+            x += run;
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</table>");
+    html
+}
@@ -5,6 +5,11 @@ pub fn get_default_template(name: &str) -> String {
         "cancellation" => include_str!("../../templates/defaults/cancellation.mjml").to_string(),
         "reschedule" => include_str!("../../templates/defaults/reschedule.mjml").to_string(),
         "invitation" => include_str!("../../templates/defaults/invitation.mjml").to_string(),
+        "booking_blocked" => include_str!("../../templates/defaults/booking_blocked.mjml").to_string(),
+        "duplicate_booking" => include_str!("../../templates/defaults/duplicate_booking.mjml").to_string(),
+        "portal_link" => include_str!("../../templates/defaults/portal_link.mjml").to_string(),
+        "session_cancelled" => include_str!("../../templates/defaults/session_cancelled.mjml").to_string(),
+        "event_updated" => include_str!("../../templates/defaults/event_updated.mjml").to_string(),
         _ => format!("<mjml><mj-body><mj-text>Default template for {} not found.</mj-text></mj-body></mjml>", name),
     }
 }
@@ -14,6 +19,11 @@ pub const DEFAULT_REMINDER_SUBJECT: &str = "Reminder: {{ event_title }} starts s
 pub const DEFAULT_CANCELLATION_SUBJECT: &str = "Cancelled: {{ event_title }}";
 pub const DEFAULT_RESCHEDULE_SUBJECT: &str = "Rescheduled: {{ event_title }}";
 pub const DEFAULT_INVITATION_SUBJECT: &str = "Invitation: {{ event_title }}";
+pub const DEFAULT_BOOKING_BLOCKED_SUBJECT: &str = "Booking not possible: {{ event_title }}";
+pub const DEFAULT_DUPLICATE_BOOKING_SUBJECT: &str = "You are already booked: {{ event_title }}";
+pub const DEFAULT_PORTAL_LINK_SUBJECT: &str = "Your bookings at {{ tenant_name }}";
+pub const DEFAULT_SESSION_CANCELLED_SUBJECT: &str = "Session cancelled: {{ event_title }}";
+pub const DEFAULT_EVENT_UPDATED_SUBJECT: &str = "Updated details: {{ event_title }}";
 
 #[cfg(test)]
 mod tests {
@@ -38,6 +48,26 @@ mod tests {
         assert!(invite.contains("You are invited"), "Invitation content mismatch");
         assert!(invite.contains("Book Your Slot"), "Invitation button mismatch");
 
+        let blocked = get_default_template("booking_blocked");
+        assert!(blocked.contains("Booking Not Possible"), "Blocked content mismatch");
+        assert!(blocked.contains("{{ blocked_until }}"), "Blocked template misses the block end");
+
+        let duplicate = get_default_template("duplicate_booking");
+        assert!(duplicate.contains("Already Booked"), "Duplicate content mismatch");
+        assert!(duplicate.contains("{{ manage_link }}"), "Duplicate template misses the manage link");
+
+        let portal = get_default_template("portal_link");
+        assert!(portal.contains("Your Bookings"), "Portal content mismatch");
+        assert!(portal.contains("{{ portal_link }}"), "Portal template misses the link");
+
+        let session_cancelled = get_default_template("session_cancelled");
+        assert!(session_cancelled.contains("Session Cancelled"), "Session cancelled content mismatch");
+        assert!(session_cancelled.contains("{{ rebook_link }}"), "Session cancelled template misses the rebooking link");
+
+        let updated = get_default_template("event_updated");
+        assert!(updated.contains("Details Updated"), "Event updated content mismatch");
+        assert!(updated.contains("{% for change in changes %}"), "Event updated template misses the changes");
+
         let missing = get_default_template("non_existent");
         assert!(missing.contains("Default template for non_existent not found"));
     }
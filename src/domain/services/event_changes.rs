@@ -0,0 +1,72 @@
+use crate::domain::models::{booking::Booking, event::Event};
+use serde::{Deserialize, Serialize};
+
+const LOCATION: &str = "Location";
+
+/// A detail participants were sent with their booking that changed afterwards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// Location and host a day override or session sets, `None` leaving the event's in place.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Venue {
+    pub location: Option<String>,
+    pub host_name: Option<String>,
+}
+
+fn push_change(changes: &mut Vec<FieldChange>, field: &str, old: &str, new: &str) {
+    if old != new {
+        changes.push(FieldChange { field: field.to_string(), old: old.to_string(), new: new.to_string() });
+    }
+}
+
+/// Changes of the details shown in confirmations and calendar entries. Payout, access and
+/// scheduling settings do not concern bookings already made, nor does the duration: bookings
+/// keep the end time they were made with.
+pub fn event_changes(old: &Event, new: &Event) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    push_change(&mut changes, "Title", &old.title_en, &new.title_en);
+    push_change(&mut changes, "Title (German)", &old.title_de, &new.title_de);
+    push_change(&mut changes, LOCATION, &old.location, &new.location);
+    push_change(&mut changes, "Host", &old.host_name, &new.host_name);
+    push_change(&mut changes, "Time zone", &old.timezone, &new.timezone);
+    push_change(&mut changes, "Description", &old.desc_en, &new.desc_en);
+    push_change(&mut changes, "Description (German)", &old.desc_de, &new.desc_de);
+    changes
+}
+
+/// The changes concerning `booking`. Bookings at a location of their own keep it when the
+/// event's location changes.
+pub fn affecting(changes: &[FieldChange], booking: &Booking) -> Vec<FieldChange> {
+    changes.iter()
+        .filter(|change| change.field != LOCATION || booking.location.is_none())
+        .cloned()
+        .collect()
+}
+
+/// Changes of the effective location and host when a day or session moves from `old` to `new`.
+pub fn venue_changes(event: &Event, old: &Venue, new: &Venue) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    push_change(
+        &mut changes,
+        LOCATION,
+        old.location.as_deref().unwrap_or(&event.location),
+        new.location.as_deref().unwrap_or(&event.location),
+    );
+    push_change(
+        &mut changes,
+        "Host",
+        old.host_name.as_deref().unwrap_or(&event.host_name),
+        new.host_name.as_deref().unwrap_or(&event.host_name),
+    );
+    changes
+}
+
+/// The location stored on a booking at `location`. Bookings at the event's own store none.
+pub fn booked_location(event: &Event, location: Option<&str>) -> Option<String> {
+    location.filter(|loc| *loc != event.location).map(str::to_string)
+}
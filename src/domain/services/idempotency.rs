@@ -0,0 +1,28 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// How long a completed request can be replayed with its key.
+pub const KEY_TTL_HOURS: i64 = 24;
+
+/// How long a key stays reserved for a request that never completed, e.g. after a crash.
+pub const PENDING_TTL_SECONDS: i64 = 300;
+
+/// Longest accepted `Idempotency-Key` header value.
+pub const MAX_KEY_LEN: usize = 255;
+
+/// Fingerprint of a request body, so a reused key can be told apart from a replay.
+pub fn request_hash<T: Serialize>(request: &T) -> String {
+    let body = serde_json::to_vec(request).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(&body);
+    format!("sha256:{}", hex::encode(hasher.finalize()))
+}
+
+pub fn expired_before(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::hours(KEY_TTL_HOURS)
+}
+
+pub fn abandoned_before(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::seconds(PENDING_TTL_SECONDS)
+}
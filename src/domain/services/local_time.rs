@@ -0,0 +1,50 @@
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Which instant a local time repeated by a DST fall-back resolves to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overlap {
+    Earliest,
+    Latest,
+}
+
+/// Instant shown as `local` in `tz`. Local times skipped by a DST gap are shifted
+/// forward by the length of the gap, e.g. 00:30 on a day whose midnight jumps to
+/// 01:00 becomes 01:30. Repeated local times follow `overlap`.
+pub fn resolve(tz: &Tz, local: NaiveDateTime, overlap: Overlap) -> DateTime<Utc> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(dt) => dt.with_timezone(&Utc),
+        LocalResult::Ambiguous(earliest, latest) => match overlap {
+            Overlap::Earliest => earliest.with_timezone(&Utc),
+            Overlap::Latest => latest.with_timezone(&Utc),
+        },
+        LocalResult::None => shift_forward(tz, local),
+    }
+}
+
+/// Every instant shown as `local` in `tz`: both of a repeated local time, and the
+/// shifted one of a skipped local time.
+pub fn resolve_all(tz: &Tz, local: NaiveDateTime) -> Vec<DateTime<Utc>> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(dt) => vec![dt.with_timezone(&Utc)],
+        LocalResult::Ambiguous(earliest, latest) => vec![earliest.with_timezone(&Utc), latest.with_timezone(&Utc)],
+        LocalResult::None => vec![shift_forward(tz, local)],
+    }
+}
+
+/// Reads the local time with the offset in effect before the gap. Transitions are
+/// far more than a day apart, so the offset a day earlier is the one before the gap.
+fn shift_forward(tz: &Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    let offset = tz.offset_from_utc_datetime(&(local - Duration::days(1))).fix();
+    (local - Duration::seconds(offset.local_minus_utc() as i64)).and_utc()
+}
+
+/// First instant of the local day, later than midnight when midnight is skipped.
+pub fn day_start(tz: &Tz, date: NaiveDate) -> DateTime<Utc> {
+    resolve(tz, date.and_time(NaiveTime::MIN), Overlap::Earliest)
+}
+
+/// Last second of the local day, the second occurrence when the evening is repeated.
+pub fn day_end(tz: &Tz, date: NaiveDate) -> DateTime<Utc> {
+    resolve(tz, date.and_hms_opt(23, 59, 59).unwrap(), Overlap::Latest)
+}
@@ -1,5 +1,16 @@
 pub mod availability;
+pub mod availability_cache;
 pub mod calendar;
 pub mod auth_service;
 pub mod communication_service;
-pub mod defaults;
\ No newline at end of file
+pub mod defaults;
+pub mod payout;
+pub mod sepa;
+pub mod checkin;
+pub mod participant;
+pub mod privacy;
+pub mod idempotency;
+pub mod access;
+pub mod local_time;
+pub mod session_pattern;
+pub mod event_changes;
\ No newline at end of file
@@ -0,0 +1,102 @@
+use crate::domain::models::{booking::Booking, participant::Participant, tenant::Tenant};
+use crate::domain::services::payout::PayoutLine;
+use chrono::{DateTime, Months, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ParticipantStats {
+    pub bookings: usize,
+    pub attended: usize,
+    pub no_shows: usize,
+    pub cancelled: usize,
+    pub total_payout: i64,
+    pub last_booking_at: Option<DateTime<Utc>>,
+    pub blocked_until: Option<DateTime<Utc>>,
+}
+
+/// Tenant rule: `threshold` no-shows within `window_months` block booking for `block_months`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoShowPolicy {
+    pub threshold: usize,
+    pub window_months: u32,
+    pub block_months: u32,
+}
+
+impl NoShowPolicy {
+    pub fn from_tenant(tenant: &Tenant) -> Option<Self> {
+        let threshold = tenant.noshow_block_threshold.filter(|t| *t > 0)?;
+        Some(Self {
+            threshold: threshold as usize,
+            window_months: tenant.noshow_window_months.max(1) as u32,
+            block_months: tenant.noshow_block_months.max(1) as u32,
+        })
+    }
+
+    /// The block starts at the no-show that reached the threshold and is
+    /// extended by every further no-show that keeps it reached.
+    pub fn blocked_until(&self, no_shows: &[DateTime<Utc>], now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut times = no_shows.to_vec();
+        times.sort();
+
+        let until = times.iter().enumerate()
+            .filter(|(i, at)| {
+                let window_start = at.checked_sub_months(Months::new(self.window_months)).unwrap_or(**at);
+                times[..=*i].iter().filter(|t| **t > window_start).count() >= self.threshold
+            })
+            .filter_map(|(_, at)| at.checked_add_months(Months::new(self.block_months)))
+            .max()?;
+
+        (until > now).then_some(until)
+    }
+
+    /// The no-show that last extended the block ending at `until`.
+    pub fn block_extended_at(&self, until: DateTime<Utc>) -> DateTime<Utc> {
+        until.checked_sub_months(Months::new(self.block_months)).unwrap_or(until)
+    }
+}
+
+fn has_label(booking: &Booking, label_id: &Option<String>) -> bool {
+    label_id.is_some() && booking.label_id == *label_id
+}
+
+fn is_no_show(booking: &Booking, tenant: &Tenant) -> bool {
+    booking.status != "CANCELLED"
+        && booking.checked_in_at.is_none()
+        && has_label(booking, &tenant.noshow_label_id)
+}
+
+/// Returns the end of the participant's booking block, if the tenant policy currently applies.
+pub fn booking_blocked_until(participant: &Participant, bookings: &[Booking], tenant: &Tenant, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if participant.noshow_exempt {
+        return None;
+    }
+    let policy = NoShowPolicy::from_tenant(tenant)?;
+    let no_shows: Vec<DateTime<Utc>> = bookings.iter()
+        .filter(|b| is_no_show(b, tenant))
+        .map(|b| b.start_time)
+        .collect();
+    policy.blocked_until(&no_shows, now)
+}
+
+/// Aggregates a participant's history. A booking counts as attended when it was
+/// checked in or carries the tenant's Show label; the Noshow label counts as no-show.
+/// `ledger` must only contain lines of the given bookings.
+pub fn compute_stats(participant: &Participant, bookings: &[Booking], tenant: &Tenant, ledger: &[PayoutLine]) -> ParticipantStats {
+    let mut stats = ParticipantStats::default();
+    for booking in bookings {
+        if booking.status == "CANCELLED" {
+            stats.cancelled += 1;
+            continue;
+        }
+        stats.bookings += 1;
+        if booking.checked_in_at.is_some() || has_label(booking, &tenant.show_label_id) {
+            stats.attended += 1;
+        } else if is_no_show(booking, tenant) {
+            stats.no_shows += 1;
+        }
+        stats.last_booking_at = stats.last_booking_at.max(Some(booking.start_time));
+    }
+    stats.total_payout = ledger.iter().map(|l| l.amount as i64).sum();
+    stats.blocked_until = booking_blocked_until(participant, bookings, tenant, Utc::now());
+    stats
+}
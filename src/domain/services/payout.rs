@@ -0,0 +1,235 @@
+use crate::domain::models::{booking::{Booking, BookingLabel}, event::Event, payout::PayoutEntry};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Parses the free-text event payout (e.g. "15€", "15 EUR", "15,00") into whole euros.
+/// Text without a number pays nothing. Cents and several numbers cannot be booked and give `None`.
+pub fn parse_amount(raw: &str) -> Option<i32> {
+    let Some(start) = raw.find(|c: char| c.is_ascii_digit()) else {
+        return Some(0);
+    };
+    let rest = &raw[start..];
+    let (whole, mut tail) = rest.split_at(rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len()));
+
+    // "15,00", "15.-" and "15," are whole amounts, "12,50" is not
+    if let Some(fraction) = tail.strip_prefix([',', '.']) {
+        let (cents, after) = fraction.split_at(fraction.find(|c: char| !c.is_ascii_digit()).unwrap_or(fraction.len()));
+        if cents.len() > 2 || cents.chars().any(|c| c != '0') {
+            return None;
+        }
+        tail = after;
+    }
+    if tail.contains(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    whole.parse().ok()
+}
+
+/// Resolves the effective payout of a booking.
+/// Hierarchy: Booking Override > Label > Event
+pub fn resolve_payout(booking: &Booking, label: Option<&BookingLabel>, event: Option<&Event>) -> (i32, &'static str) {
+    if let Some(amount) = booking.payout {
+        return (amount, "BOOKING");
+    }
+    if let Some(label) = label {
+        return (label.payout, "LABEL");
+    }
+    match event {
+        // Events saved before payouts were validated may hold text that cannot be read
+        Some(event) => (parse_amount(&event.payout).unwrap_or(0), "EVENT"),
+        None => (0, "EVENT"),
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PayoutLine {
+    pub booking_id: String,
+    pub event_id: String,
+    pub event_title: String,
+    pub customer_name: String,
+    pub customer_email: String,
+    pub start_time: DateTime<Utc>,
+    pub label_id: Option<String>,
+    pub label_name: Option<String>,
+    pub source: String,
+    pub amount: i32,
+    pub status: String, // UNPAID, EXPORTED, PAID
+    pub payment_reference: Option<String>,
+    pub paid_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PayoutSummary {
+    pub key: String,
+    pub name: String,
+    pub bookings: usize,
+    pub total: i64,
+    pub paid: i64,
+    pub exported: i64,
+    pub unpaid: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoutGrouping {
+    Participant,
+    Event,
+    Period,
+}
+
+impl PayoutGrouping {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "participant" => Some(Self::Participant),
+            "event" => Some(Self::Event),
+            "period" => Some(Self::Period),
+            _ => None,
+        }
+    }
+}
+
+/// Builds one ledger line per non-cancelled booking. Paid entries keep the
+/// amount that was settled, even if labels or overrides changed afterwards.
+pub fn build_ledger(
+    bookings: &[Booking],
+    events: &HashMap<String, Event>,
+    labels: &HashMap<String, BookingLabel>,
+    entries: &HashMap<String, PayoutEntry>,
+) -> Vec<PayoutLine> {
+    let mut lines: Vec<PayoutLine> = bookings.iter()
+        .filter(|b| b.status != "CANCELLED")
+        .map(|booking| {
+            let event = events.get(&booking.event_id);
+            let label = booking.label_id.as_ref().and_then(|id| labels.get(id));
+            let (computed, source) = resolve_payout(booking, label, event);
+            let entry = entries.get(&booking.id);
+
+            PayoutLine {
+                booking_id: booking.id.clone(),
+                event_id: booking.event_id.clone(),
+                event_title: event.map(|e| e.title_en.clone()).unwrap_or_default(),
+                customer_name: booking.customer_name.clone(),
+                customer_email: booking.customer_email.clone(),
+                start_time: booking.start_time,
+                label_id: booking.label_id.clone(),
+                label_name: label.map(|l| l.name.clone()),
+                source: source.to_string(),
+                amount: entry.map(|e| e.amount).unwrap_or(computed),
+                status: entry.map(|e| e.status.clone()).unwrap_or_else(|| "UNPAID".to_string()),
+                payment_reference: entry.and_then(|e| e.payment_reference.clone()),
+                paid_at: entry.and_then(|e| e.paid_at),
+            }
+        })
+        .collect();
+
+    lines.sort_by_key(|l| l.start_time);
+    lines
+}
+
+pub fn summarize(lines: &[PayoutLine], grouping: PayoutGrouping) -> Vec<PayoutSummary> {
+    let mut groups: BTreeMap<String, PayoutSummary> = BTreeMap::new();
+
+    for line in lines {
+        let (key, name) = match grouping {
+            PayoutGrouping::Participant => (line.customer_email.trim().to_lowercase(), line.customer_name.clone()),
+            PayoutGrouping::Event => (line.event_id.clone(), line.event_title.clone()),
+            PayoutGrouping::Period => {
+                let period = line.start_time.format("%Y-%m").to_string();
+                (period.clone(), period)
+            }
+        };
+
+        let summary = groups.entry(key.clone()).or_insert_with(|| PayoutSummary {
+            key,
+            name,
+            bookings: 0,
+            total: 0,
+            paid: 0,
+            exported: 0,
+            unpaid: 0,
+        });
+
+        summary.bookings += 1;
+        summary.total += line.amount as i64;
+        match line.status.as_str() {
+            "PAID" => summary.paid += line.amount as i64,
+            "EXPORTED" => summary.exported += line.amount as i64,
+            _ => summary.unpaid += line.amount as i64,
+        }
+    }
+
+    groups.into_values().collect()
+}
+
+fn csv_field(value: &str) -> String {
+    // Spreadsheets run cells starting with these as formulas
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', ';']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+pub fn ledger_to_csv(lines: &[PayoutLine]) -> String {
+    let mut out = String::from("booking_id,event,participant,email,start_time,label,source,amount,status,payment_reference,paid_at\n");
+    for line in lines {
+        let row = [
+            csv_field(&line.booking_id),
+            csv_field(&line.event_title),
+            csv_field(&line.customer_name),
+            csv_field(&line.customer_email),
+            line.start_time.to_rfc3339(),
+            csv_field(line.label_name.as_deref().unwrap_or("")),
+            line.source.clone(),
+            line.amount.to_string(),
+            line.status.clone(),
+            csv_field(line.payment_reference.as_deref().unwrap_or("")),
+            line.paid_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        ];
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+pub fn summary_to_csv(summaries: &[PayoutSummary]) -> String {
+    let mut out = String::from("key,name,bookings,total,paid,exported,unpaid\n");
+    for s in summaries {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            csv_field(&s.key), csv_field(&s.name), s.bookings, s.total, s.paid, s.exported, s.unpaid
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("15€"), Some(15));
+        assert_eq!(parse_amount("25,00 €"), Some(25));
+        assert_eq!(parse_amount("15.-"), Some(15));
+        assert_eq!(parse_amount("Vouchers"), Some(0));
+        assert_eq!(parse_amount("12,50€"), None);
+        assert_eq!(parse_amount("15 or 20 EUR"), None);
+    }
+
+    #[test]
+    fn test_csv_field_defuses_formulas() {
+        assert_eq!(csv_field("Ada"), "Ada");
+        assert_eq!(csv_field("Doe, Jane"), "\"Doe, Jane\"");
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(csv_field("+49 123"), "'+49 123");
+        assert_eq!(csv_field("-1"), "'-1");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("\tcmd"), "'\tcmd");
+    }
+}
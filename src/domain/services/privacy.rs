@@ -0,0 +1,48 @@
+use crate::domain::models::{consent::MAX_PURPOSE_LEN, participant::normalize_email};
+use crate::error::AppError;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use zip::write::SimpleFileOptions;
+
+pub const ERASED_NAME: &str = "Erased";
+pub const ERASED_EMAIL: &str = "erased@invalid";
+
+/// Stable, non-reversible reference to a data subject for audit logs.
+pub fn subject_hash(email: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(normalize_email(email).as_bytes());
+    format!("sha256:{}", hex::encode(hasher.finalize()))
+}
+
+/// Trims and deduplicates the purposes agreed to in a booking form.
+pub fn consent_purposes(purposes: &[String]) -> Result<Vec<String>, AppError> {
+    let mut normalized: Vec<String> = Vec::new();
+    for purpose in purposes {
+        let purpose = purpose.trim();
+        if purpose.is_empty() || purpose.len() > MAX_PURPOSE_LEN {
+            return Err(AppError::Validation(format!("Consent purposes must be 1 to {} characters", MAX_PURPOSE_LEN)));
+        }
+        if !normalized.iter().any(|p| p == purpose) {
+            normalized.push(purpose.to_string());
+        }
+    }
+    Ok(normalized)
+}
+
+/// Packs each top-level section of the export into its own JSON file.
+pub fn build_archive(export: &Value) -> Result<Vec<u8>, AppError> {
+    let zip_err = |e: zip::result::ZipError| AppError::InternalWithMsg(format!("Zip error: {}", e));
+
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let sections = export.as_object().cloned().unwrap_or_default();
+    for (name, section) in sections {
+        writer.start_file(format!("{}.json", name), options).map_err(zip_err)?;
+        let json = serde_json::to_vec_pretty(&section).map_err(|_| AppError::Internal)?;
+        writer.write_all(&json).map_err(|_| AppError::Internal)?;
+    }
+
+    Ok(writer.finish().map_err(zip_err)?.into_inner())
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Uppercases and strips spaces from a user-entered IBAN or BIC.
+pub fn normalize(raw: &str) -> String {
+    raw.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase()
+}
+
+/// Validates structure and the ISO 7064 mod-97 checksum of a normalized IBAN.
+pub fn is_valid_iban(iban: &str) -> bool {
+    if iban.len() < 15 || iban.len() > 34 || !iban.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return false;
+    }
+    let (country, check) = (&iban[0..2], &iban[2..4]);
+    if !country.chars().all(|c| c.is_ascii_uppercase()) || !check.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let rearranged = iban[4..].chars().chain(iban[..4].chars());
+    let mut remainder: u32 = 0;
+    for c in rearranged {
+        let value = match c.to_digit(36) {
+            Some(v) => v,
+            None => return false,
+        };
+        remainder = if value >= 10 {
+            (remainder * 100 + value) % 97
+        } else {
+            (remainder * 10 + value) % 97
+        };
+    }
+    remainder == 1
+}
+
+pub fn is_valid_bic(bic: &str) -> bool {
+    bic.is_ascii()
+        && (bic.len() == 8 || bic.len() == 11)
+        && bic[..6].chars().all(|c| c.is_ascii_uppercase())
+        && bic[6..].chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+/// Reduces text to the SEPA Latin character set, which needs no further XML escaping.
+fn sepa_text(raw: &str, max_len: usize) -> String {
+    let mut out = String::new();
+    for c in raw.chars() {
+        match c {
+            'ä' => out.push_str("ae"),
+            'ö' => out.push_str("oe"),
+            'ü' => out.push_str("ue"),
+            'Ä' => out.push_str("Ae"),
+            'Ö' => out.push_str("Oe"),
+            'Ü' => out.push_str("Ue"),
+            'ß' => out.push_str("ss"),
+            c if c.is_ascii_alphanumeric() || " /-?:().,'+".contains(c) => out.push(c),
+            _ => out.push(' '),
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ").chars().take(max_len).collect()
+}
+
+pub struct SepaParty {
+    pub name: String,
+    pub iban: String,
+    pub bic: Option<String>,
+}
+
+pub struct SepaTransfer {
+    pub end_to_end_id: String,
+    pub creditor: SepaParty,
+    pub amount: i32,
+    pub remittance: String,
+}
+
+fn format_amount(euros: i64) -> String {
+    format!("{}.00", euros)
+}
+
+fn agent_xml(bic: &Option<String>) -> String {
+    match bic {
+        Some(bic) => format!("<FinInstnId><BIC>{}</BIC></FinInstnId>", bic),
+        None => "<FinInstnId><Othr><Id>NOTPROVIDED</Id></Othr></FinInstnId>".to_string(),
+    }
+}
+
+/// Builds a pain.001.001.03 credit transfer initiation with a single payment block.
+pub fn build_pain001(
+    message_id: &str,
+    created_at: DateTime<Utc>,
+    execution_date: NaiveDate,
+    debtor: &SepaParty,
+    transfers: &[SepaTransfer],
+) -> String {
+    let count = transfers.len();
+    let control_sum = format_amount(transfers.iter().map(|t| t.amount as i64).sum());
+    let debtor_name = sepa_text(&debtor.name, 70);
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<Document xmlns=\"urn:iso:std:iso:20022:tech:xsd:pain.001.001.03\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\n");
+    xml.push_str("  <CstmrCdtTrfInitn>\n");
+    xml.push_str(&format!(
+        "    <GrpHdr><MsgId>{}</MsgId><CreDtTm>{}</CreDtTm><NbOfTxs>{}</NbOfTxs><CtrlSum>{}</CtrlSum><InitgPty><Nm>{}</Nm></InitgPty></GrpHdr>\n",
+        message_id, created_at.format("%Y-%m-%dT%H:%M:%S"), count, control_sum, debtor_name
+    ));
+    xml.push_str("    <PmtInf>\n");
+    xml.push_str(&format!(
+        "      <PmtInfId>{}</PmtInfId><PmtMtd>TRF</PmtMtd><BtchBookg>true</BtchBookg><NbOfTxs>{}</NbOfTxs><CtrlSum>{}</CtrlSum>\n",
+        message_id, count, control_sum
+    ));
+    xml.push_str("      <PmtTpInf><SvcLvl><Cd>SEPA</Cd></SvcLvl></PmtTpInf>\n");
+    xml.push_str(&format!("      <ReqdExctnDt>{}</ReqdExctnDt>\n", execution_date.format("%Y-%m-%d")));
+    xml.push_str(&format!("      <Dbtr><Nm>{}</Nm></Dbtr>\n", debtor_name));
+    xml.push_str(&format!("      <DbtrAcct><Id><IBAN>{}</IBAN></Id></DbtrAcct>\n", debtor.iban));
+    xml.push_str(&format!("      <DbtrAgt>{}</DbtrAgt>\n", agent_xml(&debtor.bic)));
+    xml.push_str("      <ChrgBr>SLEV</ChrgBr>\n");
+
+    for t in transfers {
+        xml.push_str("      <CdtTrfTxInf>\n");
+        xml.push_str(&format!("        <PmtId><EndToEndId>{}</EndToEndId></PmtId>\n", t.end_to_end_id));
+        xml.push_str(&format!("        <Amt><InstdAmt Ccy=\"EUR\">{}</InstdAmt></Amt>\n", format_amount(t.amount as i64)));
+        if t.creditor.bic.is_some() {
+            xml.push_str(&format!("        <CdtrAgt>{}</CdtrAgt>\n", agent_xml(&t.creditor.bic)));
+        }
+        xml.push_str(&format!("        <Cdtr><Nm>{}</Nm></Cdtr>\n", sepa_text(&t.creditor.name, 70)));
+        xml.push_str(&format!("        <CdtrAcct><Id><IBAN>{}</IBAN></Id></CdtrAcct>\n", t.creditor.iban));
+        xml.push_str(&format!("        <RmtInf><Ustrd>{}</Ustrd></RmtInf>\n", sepa_text(&t.remittance, 140)));
+        xml.push_str("      </CdtTrfTxInf>\n");
+    }
+
+    xml.push_str("    </PmtInf>\n");
+    xml.push_str("  </CstmrCdtTrfInitn>\n");
+    xml.push_str("</Document>\n");
+    xml
+}
@@ -0,0 +1,75 @@
+use crate::domain::models::{event::Event, session::EventSession};
+use crate::domain::services::local_time::{self, Overlap};
+use crate::error::AppError;
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Sessions one pattern may generate, keeping the transaction and the preview small.
+pub const MAX_PATTERN_SESSIONS: usize = 500;
+
+/// Longest session a pattern may describe, one day.
+pub const MAX_SESSION_MINUTES: i64 = 24 * 60;
+
+/// Sessions at every start time on the listed weekdays from `start_date` to `end_date`,
+/// in the local time of the event.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SessionPattern {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub weekdays: Vec<Weekday>,
+    pub start_times: Vec<String>,
+    pub duration_min: i64,
+    pub max_participants: i32,
+    pub location: Option<String>,
+    pub host_name: Option<String>,
+}
+
+impl SessionPattern {
+    /// The sessions of the pattern in start order. Nothing is stored.
+    pub fn expand(&self, event: &Event) -> Result<Vec<EventSession>, AppError> {
+        if self.end_date < self.start_date {
+            return Err(AppError::Validation("End date must be after start date".into()));
+        }
+        if self.weekdays.is_empty() || self.start_times.is_empty() {
+            return Err(AppError::Validation("At least one weekday and start time required".into()));
+        }
+        if self.duration_min <= 0 {
+            return Err(AppError::Validation("Duration must be positive".into()));
+        }
+        if self.duration_min > MAX_SESSION_MINUTES {
+            return Err(AppError::Validation(format!("Duration may be at most {} minutes", MAX_SESSION_MINUTES)));
+        }
+        if self.max_participants <= 0 {
+            return Err(AppError::Validation("Capacity must be positive".into()));
+        }
+
+        let mut times = self.start_times.iter()
+            .map(|t| NaiveTime::parse_from_str(t, "%H:%M").map_err(|_| AppError::Validation(format!("Invalid start time '{}'", t))))
+            .collect::<Result<Vec<_>, _>>()?;
+        times.sort();
+        times.dedup();
+
+        let tz: Tz = event.timezone.parse().unwrap_or(chrono_tz::UTC);
+        let location = self.location.clone().filter(|loc| !loc.is_empty());
+        let host_name = self.host_name.clone().filter(|host| !host.is_empty());
+
+        let mut sessions = Vec::new();
+        for date in self.start_date.iter_days().take_while(|date| *date <= self.end_date) {
+            if !self.weekdays.contains(&date.weekday()) {
+                continue;
+            }
+            for time in &times {
+                if sessions.len() == MAX_PATTERN_SESSIONS {
+                    return Err(AppError::Validation(format!("A pattern may generate at most {} sessions", MAX_PATTERN_SESSIONS)));
+                }
+                let start = local_time::resolve(&tz, date.and_time(*time), Overlap::Earliest);
+                let mut session = EventSession::new(event.id.clone(), start, start + Duration::minutes(self.duration_min), self.max_participants);
+                session.location = location.clone();
+                session.host_name = host_name.clone();
+                sessions.push(session);
+            }
+        }
+        Ok(sessions)
+    }
+}
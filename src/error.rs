@@ -19,6 +19,9 @@ pub enum AppError {
     Forbidden(String),
     #[error("Conflict: {0}")]
     Conflict(String),
+    /// The requested slot is taken; carries free slots to offer instead.
+    #[error("Conflict: {message}")]
+    SlotConflict { message: String, alternatives: Vec<String> },
     #[error("Invalid input: {0}")]
     Validation(String),
     #[error("Internal server error")]
@@ -51,6 +54,12 @@ impl IntoResponse for AppError {
             AppError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized".to_string()),
             AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg.clone()),
             AppError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
+            AppError::SlotConflict { message, alternatives } => {
+                return (
+                    StatusCode::CONFLICT,
+                    Json(json!({ "error": message, "alternatives": alternatives }))
+                ).into_response();
+            }
             AppError::Validation(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
             AppError::Internal => (StatusCode::INTERNAL_SERVER_ERROR, "Internal error".to_string()),
             AppError::InternalWithMsg(msg) => {
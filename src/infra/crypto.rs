@@ -0,0 +1,51 @@
+use aes_gcm::{aead::{Aead, AeadCore, KeyInit, OsRng}, Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use sha2::{Digest, Sha256};
+use crate::config::Config;
+use crate::error::AppError;
+
+const NONCE_LEN: usize = 12;
+
+/// AES-256-GCM cipher for sensitive values stored in the database.
+/// The configured secret is hashed to a 256-bit key, so any passphrase length works.
+pub struct FieldCipher {
+    cipher: Aes256Gcm,
+}
+
+impl FieldCipher {
+    pub fn new(secret: &str) -> Self {
+        let key = Sha256::digest(secret.as_bytes());
+        Self { cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)) }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self, AppError> {
+        config.payment_encryption_key.as_deref()
+            .map(Self::new)
+            .ok_or(AppError::Validation("Bank transfer payouts are not enabled.".into()))
+    }
+
+    /// Returns base64(nonce || ciphertext).
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, AppError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self.cipher.encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| AppError::InternalWithMsg("Encryption failed".into()))?;
+
+        let mut out = nonce.to_vec();
+        out.extend_from_slice(&ciphertext);
+        Ok(STANDARD.encode(out))
+    }
+
+    pub fn decrypt(&self, encoded: &str) -> Result<String, AppError> {
+        let data = STANDARD.decode(encoded)
+            .map_err(|_| AppError::InternalWithMsg("Malformed encrypted value".into()))?;
+        if data.len() <= NONCE_LEN {
+            return Err(AppError::InternalWithMsg("Malformed encrypted value".into()));
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let plaintext = self.cipher.decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| AppError::InternalWithMsg("Decryption failed (wrong PAYMENT_ENCRYPTION_KEY?)".into()))?;
+
+        String::from_utf8(plaintext).map_err(|_| AppError::Internal)
+    }
+}
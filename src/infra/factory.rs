@@ -12,19 +12,26 @@ use crate::state::AppState;
 use crate::infra::email::http_email_service::HttpEmailService;
 use crate::infra::ai::gemini_service::GeminiService;
 use crate::domain::services::auth_service::AuthService;
+use crate::domain::services::availability_cache::AvailabilityCache;
 use crate::infra::repositories::{
     postgres_booking_repo::PostgresBookingRepo, postgres_event_repo::PostgresEventRepo,
     postgres_invitee_repo::PostgresInviteeRepo, postgres_tenant_repo::PostgresTenantRepo,
     postgres_user_repo::PostgresUserRepo, postgres_job_repo::PostgresJobRepo,
     postgres_event_override_repo::PostgresEventOverrideRepo, postgres_auth_repo::PostgresAuthRepo,
     postgres_label_repo::PostgresLabelRepo, postgres_session_repo::PostgresSessionRepo,
-    postgres_communication_repo::PostgresCommunicationRepo,
+    postgres_communication_repo::PostgresCommunicationRepo, postgres_payout_repo::PostgresPayoutRepo,
+    postgres_participant_repo::PostgresParticipantRepo, postgres_audit_repo::PostgresAuditRepo,
+    postgres_privacy_repo::PostgresPrivacyRepo, postgres_retention_repo::PostgresRetentionRepo,
+    postgres_idempotency_repo::PostgresIdempotencyRepo, postgres_portal_repo::PostgresPortalRepo,
     sqlite_booking_repo::SqliteBookingRepo, sqlite_event_repo::SqliteEventRepo,
     sqlite_invitee_repo::SqliteInviteeRepo, sqlite_tenant_repo::SqliteTenantRepo,
     sqlite_user_repo::SqliteUserRepo, sqlite_job_repo::SqliteJobRepo,
     sqlite_event_override_repo::SqliteEventOverrideRepo, sqlite_auth_repo::SqliteAuthRepo,
     sqlite_label_repo::SqliteLabelRepo, sqlite_session_repo::SqliteSessionRepo,
-    sqlite_communication_repo::SqliteCommunicationRepo,
+    sqlite_communication_repo::SqliteCommunicationRepo, sqlite_payout_repo::SqlitePayoutRepo,
+    sqlite_participant_repo::SqliteParticipantRepo, sqlite_audit_repo::SqliteAuditRepo,
+    sqlite_privacy_repo::SqlitePrivacyRepo, sqlite_retention_repo::SqliteRetentionRepo,
+    sqlite_idempotency_repo::SqliteIdempotencyRepo, sqlite_portal_repo::SqlitePortalRepo,
 };
 
 pub async fn bootstrap_state(config: &Config) -> AppState {
@@ -74,10 +81,18 @@ pub async fn bootstrap_state(config: &Config) -> AppState {
             label_repo: Arc::new(PostgresLabelRepo::new(pool.clone())),
             session_repo: Arc::new(PostgresSessionRepo::new(pool.clone())),
             communication_repo: Arc::new(PostgresCommunicationRepo::new(pool.clone())),
+            payout_repo: Arc::new(PostgresPayoutRepo::new(pool.clone())),
+            participant_repo: Arc::new(PostgresParticipantRepo::new(pool.clone())),
+            audit_repo: Arc::new(PostgresAuditRepo::new(pool.clone())),
+            privacy_repo: Arc::new(PostgresPrivacyRepo::new(pool.clone())),
+            retention_repo: Arc::new(PostgresRetentionRepo::new(pool.clone())),
+            idempotency_repo: Arc::new(PostgresIdempotencyRepo::new(pool.clone())),
+            portal_repo: Arc::new(PostgresPortalRepo::new(pool.clone())),
             auth_service,
             email_service,
             llm_service,
             templates,
+            availability_cache: Arc::new(AvailabilityCache::default()),
         }
     } else {
         info!("Initializing SQLite connection with WAL Mode...");
@@ -114,10 +129,18 @@ pub async fn bootstrap_state(config: &Config) -> AppState {
             label_repo: Arc::new(SqliteLabelRepo::new(pool.clone())),
             session_repo: Arc::new(SqliteSessionRepo::new(pool.clone())),
             communication_repo: Arc::new(SqliteCommunicationRepo::new(pool.clone())),
+            payout_repo: Arc::new(SqlitePayoutRepo::new(pool.clone())),
+            participant_repo: Arc::new(SqliteParticipantRepo::new(pool.clone())),
+            audit_repo: Arc::new(SqliteAuditRepo::new(pool.clone())),
+            privacy_repo: Arc::new(SqlitePrivacyRepo::new(pool.clone())),
+            retention_repo: Arc::new(SqliteRetentionRepo::new(pool.clone())),
+            idempotency_repo: Arc::new(SqliteIdempotencyRepo::new(pool.clone())),
+            portal_repo: Arc::new(SqlitePortalRepo::new(pool.clone())),
             auth_service,
             email_service,
             llm_service,
             templates,
+            availability_cache: Arc::new(AvailabilityCache::default()),
         }
     }
 }
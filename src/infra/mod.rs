@@ -2,4 +2,5 @@ pub mod db;
 pub mod factory;
 pub mod repositories;
 pub mod email;
-pub mod ai;
\ No newline at end of file
+pub mod ai;
+pub mod crypto;
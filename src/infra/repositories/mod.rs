@@ -21,4 +21,18 @@ pub mod postgres_session_repo;
 pub mod sqlite_auth_repo;
 pub mod postgres_auth_repo;
 pub mod postgres_communication_repo;
-pub mod sqlite_communication_repo;
\ No newline at end of file
+pub mod sqlite_communication_repo;
+pub mod sqlite_payout_repo;
+pub mod postgres_payout_repo;
+pub mod sqlite_participant_repo;
+pub mod postgres_participant_repo;
+pub mod sqlite_audit_repo;
+pub mod postgres_audit_repo;
+pub mod sqlite_privacy_repo;
+pub mod postgres_privacy_repo;
+pub mod sqlite_retention_repo;
+pub mod postgres_retention_repo;
+pub mod sqlite_idempotency_repo;
+pub mod postgres_idempotency_repo;
+pub mod sqlite_portal_repo;
+pub mod postgres_portal_repo;
\ No newline at end of file
@@ -0,0 +1,46 @@
+use crate::domain::{models::audit::AuditLog, ports::AuditRepository};
+use crate::error::AppError;
+use async_trait::async_trait;
+use sqlx::{PgConnection, PgPool};
+
+pub struct PostgresAuditRepo {
+    pool: PgPool,
+}
+
+impl PostgresAuditRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Records `entry` within the caller's transaction.
+pub(crate) async fn insert_audit_log(conn: &mut PgConnection, entry: &AuditLog) -> Result<(), AppError> {
+    sqlx::query("INSERT INTO audit_logs (id, tenant_id, actor, action, subject, details, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7)")
+        .bind(&entry.id)
+        .bind(&entry.tenant_id)
+        .bind(&entry.actor)
+        .bind(&entry.action)
+        .bind(&entry.subject)
+        .bind(&entry.details)
+        .bind(entry.created_at)
+        .execute(&mut *conn)
+        .await
+        .map_err(AppError::Database)?;
+    Ok(())
+}
+
+#[async_trait]
+impl AuditRepository for PostgresAuditRepo {
+    async fn record(&self, entry: &AuditLog) -> Result<(), AppError> {
+        let mut conn = self.pool.acquire().await.map_err(AppError::Database)?;
+        insert_audit_log(&mut conn, entry).await
+    }
+
+    async fn list(&self, tenant_id: &str) -> Result<Vec<AuditLog>, AppError> {
+        sqlx::query_as::<_, AuditLog>("SELECT * FROM audit_logs WHERE tenant_id = $1 ORDER BY created_at DESC")
+            .bind(tenant_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+}
@@ -63,6 +63,18 @@ impl EventOverrideRepository for PostgresEventOverrideRepo {
             .map_err(AppError::Database)
     }
 
+    async fn list_by_tenant_range(&self, tenant_id: &str, start: NaiveDate, end: NaiveDate) -> Result<Vec<EventOverride>, AppError> {
+        sqlx::query_as::<_, EventOverride>(
+            "SELECT o.* FROM event_overrides o JOIN events e ON e.id = o.event_id WHERE e.tenant_id = $1 AND o.date >= $2 AND o.date <= $3"
+        )
+            .bind(tenant_id)
+            .bind(start)
+            .bind(end)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
     async fn delete(&self, event_id: &str, date: NaiveDate) -> Result<(), AppError> {
         let res = sqlx::query("DELETE FROM event_overrides WHERE event_id = $1 AND date = $2")
             .bind(event_id)
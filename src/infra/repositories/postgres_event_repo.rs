@@ -1,7 +1,9 @@
-use crate::domain::{models::event::Event, ports::EventRepository};
+use crate::domain::{models::{event::Event, job::Job}, ports::EventRepository};
 use crate::error::AppError;
+use crate::infra::repositories::postgres_booking_repo::bump_revision;
+use crate::infra::repositories::postgres_job_repo::insert_job;
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 pub struct PostgresEventRepo {
     pool: PgPool,
@@ -13,6 +15,55 @@ impl PostgresEventRepo {
     }
 }
 
+async fn update_row(conn: &mut PgConnection, event: &Event) -> Result<Event, AppError> {
+    sqlx::query_as::<_, Event>(
+        r#"UPDATE events SET
+            slug=$1, title_en=$2, title_de=$3, desc_en=$4, desc_de=$5,
+            location=$6, payout=$7, host_name=$8, timezone=$9,
+            min_notice_general=$10, min_notice_first=$11,
+            active_start=$12, active_end=$13, duration_min=$14, interval_min=$15,
+            max_participants=$16, image_url=$17, config_json=$18, access_mode=$19, schedule_type=$20,
+            allow_customer_cancel=$21, allow_customer_reschedule=$22,
+            booking_limit=$23, series_key=$24, reserved_capacity=$25, reserved_release_hours=$26,
+            allowed_domains=$27, denied_domains=$28, passcode=$29
+           WHERE id=$30 AND tenant_id=$31 RETURNING *"#
+    )
+        .bind(&event.slug)
+        .bind(&event.title_en)
+        .bind(&event.title_de)
+        .bind(&event.desc_en)
+        .bind(&event.desc_de)
+        .bind(&event.location)
+        .bind(&event.payout)
+        .bind(&event.host_name)
+        .bind(&event.timezone)
+        .bind(event.min_notice_general)
+        .bind(event.min_notice_first)
+        .bind(event.active_start)
+        .bind(event.active_end)
+        .bind(event.duration_min)
+        .bind(event.interval_min)
+        .bind(event.max_participants)
+        .bind(&event.image_url)
+        .bind(&event.config_json)
+        .bind(&event.access_mode)
+        .bind(&event.schedule_type)
+        .bind(event.allow_customer_cancel)
+        .bind(event.allow_customer_reschedule)
+        .bind(&event.booking_limit)
+        .bind(&event.series_key)
+        .bind(event.reserved_capacity)
+        .bind(event.reserved_release_hours)
+        .bind(&event.allowed_domains)
+        .bind(&event.denied_domains)
+        .bind(&event.passcode)
+        .bind(&event.id)
+        .bind(&event.tenant_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(AppError::Database)
+}
+
 #[async_trait]
 impl EventRepository for PostgresEventRepo {
     async fn create(&self, event: &Event) -> Result<Event, AppError> {
@@ -21,8 +72,10 @@ impl EventRepository for PostgresEventRepo {
                 id, tenant_id, slug, title_en, title_de, desc_en, desc_de,
                 location, payout, host_name, timezone, min_notice_general, min_notice_first,
                 active_start, active_end, duration_min, interval_min, max_participants,
-                image_url, config_json, access_mode, schedule_type, allow_customer_cancel, allow_customer_reschedule, created_at
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25)
+                image_url, config_json, access_mode, schedule_type, allow_customer_cancel, allow_customer_reschedule,
+                booking_limit, series_key, reserved_capacity, reserved_release_hours,
+                allowed_domains, denied_domains, passcode, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32)
             RETURNING *"#
         )
             .bind(&event.id)
@@ -49,6 +102,13 @@ impl EventRepository for PostgresEventRepo {
             .bind(&event.schedule_type)
             .bind(event.allow_customer_cancel)
             .bind(event.allow_customer_reschedule)
+            .bind(&event.booking_limit)
+            .bind(&event.series_key)
+            .bind(event.reserved_capacity)
+            .bind(event.reserved_release_hours)
+            .bind(&event.allowed_domains)
+            .bind(&event.denied_domains)
+            .bind(&event.passcode)
             .bind(event.created_at)
             .fetch_one(&self.pool)
             .await
@@ -88,43 +148,19 @@ impl EventRepository for PostgresEventRepo {
     }
 
     async fn update(&self, event: &Event) -> Result<Event, AppError> {
-        sqlx::query_as::<_, Event>(
-            r#"UPDATE events SET
-                slug=$1, title_en=$2, title_de=$3, desc_en=$4, desc_de=$5,
-                location=$6, payout=$7, host_name=$8, timezone=$9,
-                min_notice_general=$10, min_notice_first=$11,
-                active_start=$12, active_end=$13, duration_min=$14, interval_min=$15,
-                max_participants=$16, image_url=$17, config_json=$18, access_mode=$19, schedule_type=$20,
-                allow_customer_cancel=$21, allow_customer_reschedule=$22
-               WHERE id=$23 AND tenant_id=$24 RETURNING *"#
-        )
-            .bind(&event.slug)
-            .bind(&event.title_en)
-            .bind(&event.title_de)
-            .bind(&event.desc_en)
-            .bind(&event.desc_de)
-            .bind(&event.location)
-            .bind(&event.payout)
-            .bind(&event.host_name)
-            .bind(&event.timezone)
-            .bind(event.min_notice_general)
-            .bind(event.min_notice_first)
-            .bind(event.active_start)
-            .bind(event.active_end)
-            .bind(event.duration_min)
-            .bind(event.interval_min)
-            .bind(event.max_participants)
-            .bind(&event.image_url)
-            .bind(&event.config_json)
-            .bind(&event.access_mode)
-            .bind(&event.schedule_type)
-            .bind(event.allow_customer_cancel)
-            .bind(event.allow_customer_reschedule)
-            .bind(&event.id)
-            .bind(&event.tenant_id)
-            .fetch_one(&self.pool)
-            .await
-            .map_err(AppError::Database)
+        let mut conn = self.pool.acquire().await.map_err(AppError::Database)?;
+        update_row(&mut conn, event).await
+    }
+
+    async fn update_with_notices(&self, event: &Event, jobs: &[Job]) -> Result<Event, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        let updated = update_row(&mut tx, event).await?;
+        for job in jobs {
+            bump_revision(&mut tx, &job.payload.tenant_id, &job.payload.booking_id).await?;
+            insert_job(&mut tx, job).await?;
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(updated)
     }
 
     async fn delete(&self, tenant_id: &str, id: &str) -> Result<(), AppError> {
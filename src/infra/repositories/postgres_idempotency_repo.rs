@@ -0,0 +1,63 @@
+use crate::domain::{models::idempotency::IdempotencyRecord, ports::IdempotencyRepository, services::idempotency};
+use crate::error::AppError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub struct PostgresIdempotencyRepo {
+    pool: PgPool,
+}
+
+impl PostgresIdempotencyRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepository for PostgresIdempotencyRepo {
+    async fn reserve(&self, record: &IdempotencyRecord) -> Result<Option<IdempotencyRecord>, AppError> {
+        let inserted = sqlx::query(
+            "INSERT INTO idempotency_keys (scope, idempotency_key, request_hash, status_code, response_body, booking_id, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             ON CONFLICT(scope, idempotency_key) DO UPDATE SET
+                 request_hash = EXCLUDED.request_hash, status_code = EXCLUDED.status_code,
+                 response_body = EXCLUDED.response_body, booking_id = EXCLUDED.booking_id, created_at = EXCLUDED.created_at
+             WHERE idempotency_keys.created_at < $8 OR (idempotency_keys.status_code IS NULL AND idempotency_keys.created_at < $9)"
+        )
+            .bind(&record.scope).bind(&record.idempotency_key).bind(&record.request_hash)
+            .bind(record.status_code).bind(&record.response_body).bind(&record.booking_id).bind(record.created_at)
+            .bind(idempotency::expired_before(record.created_at)).bind(idempotency::abandoned_before(record.created_at))
+            .execute(&self.pool).await.map_err(AppError::Database)?;
+
+        // Also counts a stale record taken over by this one
+        if inserted.rows_affected() > 0 {
+            return Ok(None);
+        }
+
+        sqlx::query_as::<_, IdempotencyRecord>("SELECT * FROM idempotency_keys WHERE scope = $1 AND idempotency_key = $2")
+            .bind(&record.scope).bind(&record.idempotency_key)
+            .fetch_optional(&self.pool).await.map_err(AppError::Database)
+    }
+
+    async fn complete(&self, scope: &str, key: &str, status_code: i32, response_body: &str, booking_id: Option<&str>) -> Result<(), AppError> {
+        sqlx::query("UPDATE idempotency_keys SET status_code = $1, response_body = $2, booking_id = $3 WHERE scope = $4 AND idempotency_key = $5")
+            .bind(status_code).bind(response_body).bind(booking_id).bind(scope).bind(key)
+            .execute(&self.pool).await.map_err(AppError::Database)?;
+        Ok(())
+    }
+
+    async fn release(&self, scope: &str, key: &str) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE scope = $1 AND idempotency_key = $2")
+            .bind(scope).bind(key)
+            .execute(&self.pool).await.map_err(AppError::Database)?;
+        Ok(())
+    }
+
+    async fn purge(&self, before: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM idempotency_keys WHERE created_at < $1")
+            .bind(before)
+            .execute(&self.pool).await.map_err(AppError::Database)?;
+        Ok(result.rows_affected())
+    }
+}
@@ -1,7 +1,8 @@
 use crate::domain::{models::invitee::Invitee, ports::InviteeRepository};
 use crate::error::AppError;
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{Row, PgConnection, PgPool};
+use std::collections::HashMap;
 
 pub struct PostgresInviteeRepo {
     pool: PgPool,
@@ -11,69 +12,148 @@ impl PostgresInviteeRepo {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    async fn attach_events(&self, mut invitees: Vec<Invitee>) -> Result<Vec<Invitee>, AppError> {
+        let Some(first) = invitees.first() else { return Ok(invitees) };
+        let rows = sqlx::query(
+            "SELECT ie.invitee_id, ie.event_id FROM invitee_events ie
+             JOIN invitees i ON i.id = ie.invitee_id
+             WHERE i.tenant_id = $1
+             ORDER BY ie.event_id"
+        )
+            .bind(&first.tenant_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+
+        let mut events: HashMap<String, Vec<String>> = HashMap::new();
+        for row in rows {
+            events.entry(row.get("invitee_id")).or_default().push(row.get("event_id"));
+        }
+        for invitee in &mut invitees {
+            invitee.event_ids = events.remove(&invitee.id).unwrap_or_default();
+        }
+        Ok(invitees)
+    }
+
+    async fn attach_one(&self, invitee: Option<Invitee>) -> Result<Option<Invitee>, AppError> {
+        let Some(mut invitee) = invitee else { return Ok(None) };
+        let rows = sqlx::query("SELECT event_id FROM invitee_events WHERE invitee_id = $1 ORDER BY event_id")
+            .bind(&invitee.id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        invitee.event_ids = rows.iter().map(|r| r.get("event_id")).collect();
+        Ok(Some(invitee))
+    }
+}
+
+async fn replace_events(conn: &mut PgConnection, invitee: &Invitee) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM invitee_events WHERE invitee_id = $1")
+        .bind(&invitee.id)
+        .execute(&mut *conn)
+        .await
+        .map_err(AppError::Database)?;
+    for event_id in invitee.event_ids.iter().filter(|id| **id != invitee.event_id) {
+        sqlx::query("INSERT INTO invitee_events (invitee_id, event_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+            .bind(&invitee.id)
+            .bind(event_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(AppError::Database)?;
+    }
+    Ok(())
+}
+
+/// Stores `invitee` with its additional events within the caller's transaction.
+pub(crate) async fn insert_invitee(conn: &mut PgConnection, invitee: &Invitee) -> Result<Invitee, AppError> {
+    let mut created = sqlx::query_as::<_, Invitee>(
+        "INSERT INTO invitees (id, tenant_id, event_id, token, email, status, created_at, expires_at, max_uses, use_count)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *",
+    )
+        .bind(&invitee.id)
+        .bind(&invitee.tenant_id)
+        .bind(&invitee.event_id)
+        .bind(&invitee.token)
+        .bind(&invitee.email)
+        .bind(&invitee.status)
+        .bind(invitee.created_at)
+        .bind(invitee.expires_at)
+        .bind(invitee.max_uses)
+        .bind(invitee.use_count)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(AppError::Database)?;
+    replace_events(conn, invitee).await?;
+    created.event_ids = invitee.event_ids.iter().filter(|id| **id != invitee.event_id).cloned().collect();
+    Ok(created)
 }
 
 #[async_trait]
 impl InviteeRepository for PostgresInviteeRepo {
     async fn create(&self, invitee: &Invitee) -> Result<Invitee, AppError> {
-        sqlx::query_as::<_, Invitee>(
-            "INSERT INTO invitees (id, tenant_id, event_id, token, email, status, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id, tenant_id, event_id, token, email, status, created_at",
-        )
-            .bind(&invitee.id)
-            .bind(&invitee.tenant_id)
-            .bind(&invitee.event_id)
-            .bind(&invitee.token)
-            .bind(&invitee.email)
-            .bind(&invitee.status)
-            .bind(invitee.created_at)
-            .fetch_one(&self.pool)
-            .await
-            .map_err(AppError::Database)
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        let created = insert_invitee(&mut tx, invitee).await?;
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(created)
     }
 
     async fn find_by_token(&self, token: &str) -> Result<Option<Invitee>, AppError> {
-        sqlx::query_as::<_, Invitee>(
-            "SELECT id, tenant_id, event_id, token, email, status, created_at FROM invitees WHERE token = $1",
+        let invitee = sqlx::query_as::<_, Invitee>(
+            "SELECT * FROM invitees WHERE token = $1",
         )
             .bind(token)
             .fetch_optional(&self.pool)
             .await
-            .map_err(AppError::Database)
+            .map_err(AppError::Database)?;
+        self.attach_one(invitee).await
     }
 
     async fn find_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<Invitee>, AppError> {
-        sqlx::query_as::<_, Invitee>(
-            "SELECT id, tenant_id, event_id, token, email, status, created_at FROM invitees WHERE tenant_id = $1 AND id = $2",
+        let invitee = sqlx::query_as::<_, Invitee>(
+            "SELECT * FROM invitees WHERE tenant_id = $1 AND id = $2",
         )
             .bind(tenant_id)
             .bind(id)
             .fetch_optional(&self.pool)
             .await
-            .map_err(AppError::Database)
+            .map_err(AppError::Database)?;
+        self.attach_one(invitee).await
     }
 
     async fn list_by_event(&self, tenant_id: &str, event_id: &str) -> Result<Vec<Invitee>, AppError> {
-        sqlx::query_as::<_, Invitee>(
-            "SELECT id, tenant_id, event_id, token, email, status, created_at FROM invitees WHERE tenant_id = $1 AND event_id = $2",
+        let invitees = sqlx::query_as::<_, Invitee>(
+            "SELECT * FROM invitees WHERE tenant_id = $1
+             AND (event_id = $2 OR id IN (SELECT invitee_id FROM invitee_events WHERE event_id = $3))",
         )
             .bind(tenant_id)
             .bind(event_id)
+            .bind(event_id)
             .fetch_all(&self.pool)
             .await
-            .map_err(AppError::Database)
+            .map_err(AppError::Database)?;
+        self.attach_events(invitees).await
     }
 
     async fn update(&self, invitee: &Invitee) -> Result<Invitee, AppError> {
-        sqlx::query_as::<_, Invitee>(
-            "UPDATE invitees SET status=$1, email=$2 WHERE id=$3 AND tenant_id=$4 RETURNING *"
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        let mut updated = sqlx::query_as::<_, Invitee>(
+            "UPDATE invitees SET status=$1, email=$2, expires_at=$3, max_uses=$4 WHERE id=$5 AND tenant_id=$6 RETURNING *"
         )
             .bind(&invitee.status)
             .bind(&invitee.email)
+            .bind(invitee.expires_at)
+            .bind(invitee.max_uses)
             .bind(&invitee.id)
             .bind(&invitee.tenant_id)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
             .await
-            .map_err(AppError::Database)
+            .map_err(AppError::Database)?;
+        replace_events(&mut tx, invitee).await?;
+        tx.commit().await.map_err(AppError::Database)?;
+
+        updated.event_ids = invitee.event_ids.iter().filter(|id| **id != invitee.event_id).cloned().collect();
+        Ok(updated)
     }
 
     async fn delete(&self, tenant_id: &str, id: &str) -> Result<(), AppError> {
@@ -89,4 +169,4 @@ impl InviteeRepository for PostgresInviteeRepo {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -1,8 +1,8 @@
 use crate::domain::{models::job::Job, models::booking::Booking, ports::JobRepository};
 use crate::error::AppError;
 use async_trait::async_trait;
-use sqlx::PgPool;
-use chrono::Utc;
+use sqlx::{PgPool, PgConnection};
+use chrono::{DateTime, Utc};
 
 pub struct PostgresJobRepo {
     pool: PgPool,
@@ -12,6 +12,16 @@ impl PostgresJobRepo {
     pub fn new(pool: PgPool) -> Self { Self { pool } }
 }
 
+/// Inserts `job` within the caller's transaction.
+pub(crate) async fn insert_job(conn: &mut PgConnection, job: &Job) -> Result<(), AppError> {
+    sqlx::query("INSERT INTO jobs (id, job_type, payload, execute_at, status, error_message, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7)")
+        .bind(&job.id).bind(&job.job_type).bind(&job.payload).bind(job.execute_at).bind(&job.status).bind(&job.error_message).bind(job.created_at)
+        .execute(&mut *conn)
+        .await
+        .map_err(AppError::Database)?;
+    Ok(())
+}
+
 #[async_trait]
 impl JobRepository for PostgresJobRepo {
     async fn create(&self, job: &Job) -> Result<Job, AppError> {
@@ -86,6 +96,22 @@ impl JobRepository for PostgresJobRepo {
         Ok(())
     }
 
+    async fn create_unless_recent(&self, job: &Job, since: DateTime<Utc>) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        // Serializes requests queueing the same notice, the check below alone would let both through
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(format!("job:{}:{}", job.job_type, job.payload.booking_id))
+            .execute(&mut *tx).await.map_err(AppError::Database)?;
+        let recent: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM jobs WHERE job_type = $1 AND payload->>'booking_id' = $2 AND created_at > $3)")
+            .bind(&job.job_type).bind(&job.payload.booking_id).bind(since)
+            .fetch_one(&mut *tx).await.map_err(AppError::Database)?;
+        if !recent {
+            insert_job(&mut tx, job).await?;
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(!recent)
+    }
+
     async fn delete_jobs_by_type_and_event(&self, event_id: &str, job_type: &str) -> Result<(), AppError> {
         let query = r#"
             DELETE FROM jobs
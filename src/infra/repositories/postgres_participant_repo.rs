@@ -0,0 +1,122 @@
+use crate::domain::{models::participant::{Participant, normalize_email}, ports::ParticipantRepository};
+use crate::error::AppError;
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgConnection, PgPool, Row};
+
+pub struct PostgresParticipantRepo {
+    pool: PgPool,
+}
+
+impl PostgresParticipantRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ParticipantRepository for PostgresParticipantRepo {
+    async fn find_or_create(&self, tenant_id: &str, email: &str, name: &str) -> Result<Participant, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        let participant_id = link_participant(&mut tx, tenant_id, email, name).await?;
+        tx.commit().await.map_err(AppError::Database)?;
+        self.find_by_id(tenant_id, &participant_id).await?
+            .ok_or_else(|| AppError::InternalWithMsg("Participant disappeared during creation".into()))
+    }
+
+    async fn find_by_email(&self, tenant_id: &str, email: &str) -> Result<Option<Participant>, AppError> {
+        sqlx::query_as::<_, Participant>(
+            "SELECT p.* FROM participants p
+             JOIN participant_emails pe ON pe.participant_id = p.id
+             WHERE pe.tenant_id = $1 AND pe.email = $2"
+        )
+            .bind(tenant_id)
+            .bind(normalize_email(email))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    async fn find_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<Participant>, AppError> {
+        sqlx::query_as::<_, Participant>("SELECT * FROM participants WHERE tenant_id = $1 AND id = $2")
+            .bind(tenant_id).bind(id)
+            .fetch_optional(&self.pool).await.map_err(AppError::Database)
+    }
+
+    async fn list(&self, tenant_id: &str) -> Result<Vec<Participant>, AppError> {
+        sqlx::query_as::<_, Participant>("SELECT * FROM participants WHERE tenant_id = $1 ORDER BY name ASC")
+            .bind(tenant_id)
+            .fetch_all(&self.pool).await.map_err(AppError::Database)
+    }
+
+    async fn list_emails(&self, tenant_id: &str, participant_id: &str) -> Result<Vec<String>, AppError> {
+        let rows = sqlx::query("SELECT email FROM participant_emails WHERE tenant_id = $1 AND participant_id = $2 ORDER BY email ASC")
+            .bind(tenant_id).bind(participant_id)
+            .fetch_all(&self.pool).await.map_err(AppError::Database)?;
+        Ok(rows.iter().map(|r| r.get::<String, _>("email")).collect())
+    }
+
+    async fn update(&self, participant: &Participant) -> Result<Participant, AppError> {
+        sqlx::query_as::<_, Participant>(
+            "UPDATE participants SET name = $1, notes = $2, noshow_exempt = $3, updated_at = $4
+             WHERE id = $5 AND tenant_id = $6
+             RETURNING *"
+        )
+            .bind(&participant.name).bind(&participant.notes).bind(participant.noshow_exempt).bind(Utc::now())
+            .bind(&participant.id).bind(&participant.tenant_id)
+            .fetch_one(&self.pool).await.map_err(AppError::Database)
+    }
+
+    async fn merge(&self, tenant_id: &str, target_id: &str, source_ids: &[String]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        for source_id in source_ids {
+            sqlx::query("UPDATE bookings SET participant_id = $1 WHERE tenant_id = $2 AND participant_id = $3")
+                .bind(target_id).bind(tenant_id).bind(source_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            sqlx::query("UPDATE participant_emails SET participant_id = $1 WHERE tenant_id = $2 AND participant_id = $3")
+                .bind(target_id).bind(tenant_id).bind(source_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            sqlx::query("DELETE FROM participants WHERE tenant_id = $1 AND id = $2")
+                .bind(tenant_id).bind(source_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+        }
+        sqlx::query("UPDATE participants SET updated_at = $1 WHERE tenant_id = $2 AND id = $3")
+            .bind(Utc::now()).bind(tenant_id).bind(target_id)
+            .execute(&mut *tx).await.map_err(AppError::Database)?;
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(())
+    }
+}
+
+async fn find_linked(conn: &mut PgConnection, tenant_id: &str, email: &str) -> Result<Option<String>, AppError> {
+    sqlx::query_scalar::<_, String>("SELECT participant_id FROM participant_emails WHERE tenant_id = $1 AND email = $2")
+        .bind(tenant_id).bind(email)
+        .fetch_optional(conn).await.map_err(AppError::Database)
+}
+
+/// Id of the participant booking with `email`, registering a new one on first use. Runs inside
+/// the caller's transaction so a failed booking leaves no profile behind.
+pub(crate) async fn link_participant(conn: &mut PgConnection, tenant_id: &str, email: &str, name: &str) -> Result<String, AppError> {
+    let email = normalize_email(email);
+    if let Some(existing) = find_linked(&mut *conn, tenant_id, &email).await? {
+        return Ok(existing);
+    }
+
+    let participant = Participant::new(tenant_id.to_string(), &email, name.to_string());
+    sqlx::query("INSERT INTO participants (id, tenant_id, email, name, notes, noshow_exempt, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)")
+        .bind(&participant.id).bind(&participant.tenant_id).bind(&participant.email).bind(&participant.name)
+        .bind(&participant.notes).bind(participant.noshow_exempt).bind(participant.created_at).bind(participant.updated_at)
+        .execute(&mut *conn).await.map_err(AppError::Database)?;
+    let linked = sqlx::query("INSERT INTO participant_emails (tenant_id, email, participant_id) VALUES ($1, $2, $3) ON CONFLICT(tenant_id, email) DO NOTHING")
+        .bind(tenant_id).bind(&email).bind(&participant.id)
+        .execute(&mut *conn).await.map_err(AppError::Database)?;
+
+    if linked.rows_affected() == 0 {
+        // A concurrent booking registered the email first
+        sqlx::query("DELETE FROM participants WHERE id = $1").bind(&participant.id)
+            .execute(&mut *conn).await.map_err(AppError::Database)?;
+        return find_linked(&mut *conn, tenant_id, &email).await?
+            .ok_or_else(|| AppError::InternalWithMsg("Participant disappeared during creation".into()));
+    }
+    Ok(participant.id)
+}
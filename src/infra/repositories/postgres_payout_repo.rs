@@ -0,0 +1,103 @@
+use crate::domain::{models::payout::{PayoutEntry, BankDetails}, ports::PayoutRepository};
+use crate::error::AppError;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PostgresPayoutRepo {
+    pool: PgPool,
+}
+
+impl PostgresPayoutRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PayoutRepository for PostgresPayoutRepo {
+    async fn list_by_tenant(&self, tenant_id: &str) -> Result<Vec<PayoutEntry>, AppError> {
+        sqlx::query_as::<_, PayoutEntry>(
+            "SELECT * FROM payout_entries WHERE tenant_id = $1 ORDER BY created_at ASC"
+        )
+            .bind(tenant_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    async fn claim_many(&self, entries: &[PayoutEntry]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        for entry in entries {
+            let claimed = sqlx::query(
+                r#"INSERT INTO payout_entries (booking_id, tenant_id, amount, status, payment_reference, paid_at, created_at)
+                   VALUES ($1, $2, $3, $4, $5, $6, $7)
+                   ON CONFLICT(booking_id) DO UPDATE SET
+                   amount=excluded.amount,
+                   status=excluded.status,
+                   payment_reference=excluded.payment_reference,
+                   paid_at=excluded.paid_at
+                   WHERE payout_entries.status = 'EXPORTED' AND excluded.status = 'PAID'"#
+            )
+                .bind(&entry.booking_id)
+                .bind(&entry.tenant_id)
+                .bind(entry.amount)
+                .bind(&entry.status)
+                .bind(&entry.payment_reference)
+                .bind(entry.paid_at)
+                .bind(entry.created_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::Database)?;
+            if claimed.rows_affected() == 0 {
+                tx.rollback().await.map_err(AppError::Database)?;
+                return Err(AppError::Conflict(format!("Booking {} has already been exported or paid", entry.booking_id)));
+            }
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(())
+    }
+
+    async fn upsert_bank_details(&self, details: &BankDetails) -> Result<BankDetails, AppError> {
+        sqlx::query_as::<_, BankDetails>(
+            r#"INSERT INTO bank_details (booking_id, tenant_id, account_holder, iban_encrypted, bic_encrypted, iban_last4, updated_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               ON CONFLICT(booking_id) DO UPDATE SET
+               account_holder=excluded.account_holder,
+               iban_encrypted=excluded.iban_encrypted,
+               bic_encrypted=excluded.bic_encrypted,
+               iban_last4=excluded.iban_last4,
+               updated_at=excluded.updated_at
+               RETURNING *"#
+        )
+            .bind(&details.booking_id)
+            .bind(&details.tenant_id)
+            .bind(&details.account_holder)
+            .bind(&details.iban_encrypted)
+            .bind(&details.bic_encrypted)
+            .bind(&details.iban_last4)
+            .bind(details.updated_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    async fn find_bank_details(&self, booking_id: &str) -> Result<Option<BankDetails>, AppError> {
+        sqlx::query_as::<_, BankDetails>(
+            "SELECT * FROM bank_details WHERE booking_id = $1"
+        )
+            .bind(booking_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    async fn list_bank_details(&self, tenant_id: &str) -> Result<Vec<BankDetails>, AppError> {
+        sqlx::query_as::<_, BankDetails>(
+            "SELECT * FROM bank_details WHERE tenant_id = $1"
+        )
+            .bind(tenant_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+}
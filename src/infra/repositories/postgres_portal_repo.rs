@@ -0,0 +1,53 @@
+use crate::domain::{models::{job::Job, portal::PortalToken}, ports::PortalRepository};
+use crate::error::AppError;
+use crate::infra::repositories::postgres_job_repo::insert_job;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub struct PostgresPortalRepo {
+    pool: PgPool,
+}
+
+impl PostgresPortalRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PortalRepository for PostgresPortalRepo {
+    async fn issue(&self, token: &PortalToken, job: &Job, since: DateTime<Utc>) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(format!("portal:{}", token.participant_id))
+            .execute(&mut *tx).await.map_err(AppError::Database)?;
+        let result = sqlx::query(
+            "INSERT INTO portal_tokens (token, tenant_id, participant_id, expires_at, created_at)
+             SELECT $1, $2, $3, $4, $5
+             WHERE NOT EXISTS (SELECT 1 FROM portal_tokens WHERE tenant_id = $2 AND participant_id = $3 AND created_at > $6)"
+        )
+            .bind(&token.token).bind(&token.tenant_id).bind(&token.participant_id)
+            .bind(token.expires_at).bind(token.created_at).bind(since)
+            .execute(&mut *tx).await.map_err(AppError::Database)?;
+        let issued = result.rows_affected() > 0;
+        if issued {
+            insert_job(&mut tx, job).await?;
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(issued)
+    }
+
+    async fn find_valid(&self, token: &str, now: DateTime<Utc>) -> Result<Option<PortalToken>, AppError> {
+        sqlx::query_as::<_, PortalToken>("SELECT * FROM portal_tokens WHERE token = $1 AND expires_at > $2")
+            .bind(token).bind(now)
+            .fetch_optional(&self.pool).await.map_err(AppError::Database)
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM portal_tokens WHERE expires_at <= $1")
+            .bind(now)
+            .execute(&self.pool).await.map_err(AppError::Database)?;
+        Ok(result.rows_affected())
+    }
+}
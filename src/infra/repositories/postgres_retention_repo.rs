@@ -0,0 +1,161 @@
+use crate::domain::{models::retention::{RetentionPolicy, RetentionReport}, ports::RetentionRepository};
+use crate::domain::services::privacy::{ERASED_EMAIL, ERASED_NAME};
+use crate::error::AppError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub struct PostgresRetentionRepo {
+    pool: PgPool,
+}
+
+impl PostgresRetentionRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RetentionRepository for PostgresRetentionRepo {
+    async fn find_policy(&self, tenant_id: &str) -> Result<Option<RetentionPolicy>, AppError> {
+        sqlx::query_as::<_, RetentionPolicy>("SELECT * FROM retention_policies WHERE tenant_id = $1")
+            .bind(tenant_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    async fn upsert_policy(&self, policy: &RetentionPolicy) -> Result<RetentionPolicy, AppError> {
+        sqlx::query_as::<_, RetentionPolicy>(
+            r#"INSERT INTO retention_policies (tenant_id, anonymize_after_months, mail_logs_after_months, invitees_after_months, last_run_at, updated_at)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT(tenant_id) DO UPDATE SET
+               anonymize_after_months=excluded.anonymize_after_months,
+               mail_logs_after_months=excluded.mail_logs_after_months,
+               invitees_after_months=excluded.invitees_after_months,
+               updated_at=excluded.updated_at
+               RETURNING *"#
+        )
+            .bind(&policy.tenant_id)
+            .bind(policy.anonymize_after_months)
+            .bind(policy.mail_logs_after_months)
+            .bind(policy.invitees_after_months)
+            .bind(policy.last_run_at)
+            .bind(policy.updated_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    async fn list_policies(&self) -> Result<Vec<RetentionPolicy>, AppError> {
+        sqlx::query_as::<_, RetentionPolicy>("SELECT * FROM retention_policies")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    async fn report(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<RetentionReport, AppError> {
+        let tenant_id = &policy.tenant_id;
+        let mut report = RetentionReport::new(policy, now, true);
+        let count = |count: i64| count as u64;
+
+        if let Some(cutoff) = report.anonymize_before {
+            report.bank_details_deleted = count(sqlx::query_scalar("SELECT COUNT(*) FROM bank_details WHERE tenant_id = $1 AND booking_id IN (SELECT id FROM bookings WHERE tenant_id = $1 AND end_time < $2)")
+                .bind(tenant_id).bind(cutoff)
+                .fetch_one(&self.pool).await.map_err(AppError::Database)?);
+            report.bookings_anonymized = count(sqlx::query_scalar("SELECT COUNT(*) FROM bookings WHERE tenant_id = $1 AND end_time < $2 AND customer_email != $3")
+                .bind(tenant_id).bind(cutoff).bind(ERASED_EMAIL)
+                .fetch_one(&self.pool).await.map_err(AppError::Database)?);
+            report.participants_anonymized = count(sqlx::query_scalar(
+                "SELECT COUNT(*) FROM participants p WHERE p.tenant_id = $1 AND p.email != $2 AND p.created_at < $3
+                 AND NOT EXISTS (SELECT 1 FROM bookings b WHERE b.participant_id = p.id AND b.end_time >= $3)"
+            )
+                .bind(tenant_id).bind(ERASED_EMAIL).bind(cutoff)
+                .fetch_one(&self.pool).await.map_err(AppError::Database)?);
+        }
+
+        if let Some(cutoff) = report.mail_logs_before {
+            report.mail_logs_deleted = count(sqlx::query_scalar("SELECT COUNT(*) FROM mail_logs WHERE sent_at < $1 AND job_id IN (SELECT id FROM jobs WHERE payload->>'tenant_id' = $2)")
+                .bind(cutoff).bind(tenant_id)
+                .fetch_one(&self.pool).await.map_err(AppError::Database)?);
+        }
+
+        if let Some(cutoff) = report.invitees_before {
+            report.invitees_deleted = count(sqlx::query_scalar("SELECT COUNT(*) FROM invitees WHERE tenant_id = $1 AND created_at < $2")
+                .bind(tenant_id).bind(cutoff)
+                .fetch_one(&self.pool).await.map_err(AppError::Database)?);
+        }
+
+        Ok(report)
+    }
+
+    async fn apply(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<RetentionReport, AppError> {
+        let tenant_id = &policy.tenant_id;
+        let mut report = RetentionReport::new(policy, now, false);
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+
+        if let Some(cutoff) = report.anonymize_before {
+            let deleted = sqlx::query("DELETE FROM bank_details WHERE tenant_id = $1 AND booking_id IN (SELECT id FROM bookings WHERE tenant_id = $2 AND end_time < $3)")
+                .bind(tenant_id).bind(tenant_id).bind(cutoff)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            report.bank_details_deleted = deleted.rows_affected();
+
+            let bookings = sqlx::query(
+                "UPDATE bookings SET customer_name = $1, customer_email = $2, customer_note = NULL, token = NULL
+                 WHERE tenant_id = $3 AND end_time < $4 AND customer_email != $5"
+            )
+                .bind(ERASED_NAME).bind(ERASED_EMAIL).bind(tenant_id).bind(cutoff).bind(ERASED_EMAIL)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            report.bookings_anonymized = bookings.rows_affected();
+
+            // Participants without any booking after the cutoff
+            sqlx::query(
+                "DELETE FROM participant_emails WHERE participant_id IN (
+                    SELECT id FROM participants p WHERE p.tenant_id = $1 AND p.email != $2 AND p.created_at < $3
+                    AND NOT EXISTS (SELECT 1 FROM bookings b WHERE b.participant_id = p.id AND b.end_time >= $3))"
+            )
+                .bind(tenant_id).bind(ERASED_EMAIL).bind(cutoff)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            let participants = sqlx::query(
+                "UPDATE participants SET name = $1, email = $2, notes = NULL, updated_at = $3 WHERE id IN (
+                    SELECT id FROM participants p WHERE p.tenant_id = $4 AND p.email != $2 AND p.created_at < $5
+                    AND NOT EXISTS (SELECT 1 FROM bookings b WHERE b.participant_id = p.id AND b.end_time >= $5))"
+            )
+                .bind(ERASED_NAME).bind(ERASED_EMAIL).bind(now).bind(tenant_id).bind(cutoff)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            report.participants_anonymized = participants.rows_affected();
+        }
+
+        if let Some(cutoff) = report.mail_logs_before {
+            let deleted = sqlx::query("DELETE FROM mail_logs WHERE sent_at < $1 AND job_id IN (SELECT id FROM jobs WHERE payload->>'tenant_id' = $2)")
+                .bind(cutoff).bind(tenant_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            report.mail_logs_deleted = deleted.rows_affected();
+        }
+
+        if let Some(cutoff) = report.invitees_before {
+            sqlx::query("UPDATE bookings SET invitee_id = NULL WHERE tenant_id = $1 AND invitee_id IN (SELECT id FROM invitees WHERE tenant_id = $2 AND created_at < $3)")
+                .bind(tenant_id).bind(tenant_id).bind(cutoff)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            let deleted = sqlx::query("DELETE FROM invitees WHERE tenant_id = $1 AND created_at < $2")
+                .bind(tenant_id).bind(cutoff)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            report.invitees_deleted = deleted.rows_affected();
+        }
+
+        sqlx::query("UPDATE retention_policies SET last_run_at = $1 WHERE tenant_id = $2")
+            .bind(now).bind(tenant_id)
+            .execute(&mut *tx).await.map_err(AppError::Database)?;
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(report)
+    }
+
+    async fn purge_expired_refresh_tokens(&self, now: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM refresh_tokens WHERE expires_at < $1")
+            .bind(now)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        Ok(result.rows_affected())
+    }
+}
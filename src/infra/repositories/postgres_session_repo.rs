@@ -1,7 +1,9 @@
-use crate::domain::{models::session::EventSession, ports::SessionRepository};
+use crate::domain::{models::{booking::Booking, invitee::Invitee, job::Job, session::EventSession}, ports::SessionRepository};
 use crate::error::AppError;
+use crate::infra::repositories::postgres_invitee_repo::insert_invitee;
+use crate::infra::repositories::postgres_job_repo::insert_job;
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgPool, PgConnection, Postgres, QueryBuilder};
 use chrono::{DateTime, Utc};
 
 pub struct PostgresSessionRepo {
@@ -14,6 +16,62 @@ impl PostgresSessionRepo {
     }
 }
 
+/// Ids of the sessions overlapping another session of their event or another one of
+/// `sessions`, checked in one query. Stored sessions among them count at their new times.
+async fn find_overlaps(conn: &mut PgConnection, sessions: &[EventSession]) -> Result<Vec<String>, AppError> {
+    if sessions.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut query = QueryBuilder::<Postgres>::new("WITH batch (id, event_id, start_time, end_time) AS (");
+    query.push_values(sessions, |mut row, session| {
+        row.push_bind(&session.id).push_bind(&session.event_id).push_bind(session.start_time).push_bind(session.end_time);
+    });
+    query.push(
+        ") SELECT b.id FROM batch b
+         WHERE EXISTS (SELECT 1 FROM event_sessions s WHERE s.event_id = b.event_id AND s.id NOT IN (SELECT id FROM batch)
+                       AND s.start_time < b.end_time AND s.end_time > b.start_time)
+         OR EXISTS (SELECT 1 FROM batch o WHERE o.event_id = b.event_id AND o.id != b.id
+                    AND o.start_time < b.end_time AND o.end_time > b.start_time)"
+    );
+    query.build_query_scalar::<String>()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(AppError::Database)
+}
+
+/// Serializes batch changes to the sessions of the same events, so their overlap checks
+/// see each other's writes.
+async fn lock_events(conn: &mut PgConnection, sessions: &[EventSession]) -> Result<(), AppError> {
+    let mut event_ids: Vec<&str> = sessions.iter().map(|s| s.event_id.as_str()).collect();
+    event_ids.sort_unstable();
+    event_ids.dedup();
+    for event_id in event_ids {
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(format!("sessions:{}", event_id))
+            .execute(&mut *conn).await.map_err(AppError::Database)?;
+    }
+    Ok(())
+}
+
+/// Fails when one of the sessions has an active booking at its stored times. Bulk changes
+/// select unbooked sessions beforehand; this catches bookings made since.
+async fn check_unbooked(conn: &mut PgConnection, ids: &[String]) -> Result<(), AppError> {
+    for id in ids {
+        let booked: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM bookings b JOIN event_sessions s ON s.event_id = b.event_id
+             WHERE s.id = $1 AND b.status != 'CANCELLED' AND b.start_time < s.end_time AND b.end_time > s.start_time"
+        )
+            .bind(id)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(AppError::Database)?;
+        if booked > 0 {
+            return Err(AppError::Conflict("Session has been booked in the meantime".into()));
+        }
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl SessionRepository for PostgresSessionRepo {
     async fn create(&self, session: &EventSession) -> Result<EventSession, AppError> {
@@ -67,6 +125,18 @@ impl SessionRepository for PostgresSessionRepo {
             .map_err(AppError::Database)
     }
 
+    async fn list_by_tenant_range(&self, tenant_id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<EventSession>, AppError> {
+        sqlx::query_as::<_, EventSession>(
+            "SELECT s.* FROM event_sessions s JOIN events e ON e.id = s.event_id WHERE e.tenant_id = $1 AND s.start_time < $2 AND s.end_time > $3 ORDER BY s.start_time ASC"
+        )
+            .bind(tenant_id)
+            .bind(end)
+            .bind(start)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
     async fn update(&self, session: &EventSession) -> Result<EventSession, AppError> {
         sqlx::query_as::<_, EventSession>(
             r#"UPDATE event_sessions SET max_participants=$1, location=$2, host_name=$3 WHERE id=$4 RETURNING *"#
@@ -104,4 +174,112 @@ impl SessionRepository for PostgresSessionRepo {
             .await
             .map_err(AppError::Database)
     }
+
+    async fn create_many(&self, sessions: &[EventSession], dry_run: bool) -> Result<Vec<EventSession>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        lock_events(&mut tx, sessions).await?;
+        let overlapping = find_overlaps(&mut tx, sessions).await?;
+        if dry_run || !overlapping.is_empty() {
+            let mut conflicts: Vec<EventSession> = sessions.iter().filter(|s| overlapping.contains(&s.id)).cloned().collect();
+            conflicts.sort_by_key(|s| s.start_time);
+            return Ok(conflicts);
+        }
+        for session in sessions {
+            sqlx::query(
+                r#"INSERT INTO event_sessions (id, event_id, start_time, end_time, max_participants, location, host_name, created_at)
+                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#
+            )
+                .bind(&session.id)
+                .bind(&session.event_id)
+                .bind(session.start_time)
+                .bind(session.end_time)
+                .bind(session.max_participants)
+                .bind(&session.location)
+                .bind(&session.host_name)
+                .bind(session.created_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::Database)?;
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(Vec::new())
+    }
+
+    async fn update_times(&self, sessions: &[EventSession]) -> Result<Vec<EventSession>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        lock_events(&mut tx, sessions).await?;
+        let ids: Vec<String> = sessions.iter().map(|s| s.id.clone()).collect();
+        check_unbooked(&mut tx, &ids).await?;
+        if !find_overlaps(&mut tx, sessions).await?.is_empty() {
+            return Err(AppError::Conflict("Session overlaps with an existing session".into()));
+        }
+        let mut updated = Vec::with_capacity(sessions.len());
+        for session in sessions {
+            updated.push(sqlx::query_as::<_, EventSession>(
+                "UPDATE event_sessions SET start_time = $1, end_time = $2 WHERE id = $3 RETURNING *"
+            )
+                .bind(session.start_time)
+                .bind(session.end_time)
+                .bind(&session.id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(AppError::Database)?);
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(updated)
+    }
+
+    async fn delete_many(&self, ids: &[String]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        check_unbooked(&mut tx, ids).await?;
+        for id in ids {
+            sqlx::query("DELETE FROM event_sessions WHERE id = $1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::Database)?;
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(())
+    }
+
+    async fn cancel_with_bookings(&self, session: &EventSession, bookings: &[Booking], invitees: &[Invitee], jobs: Vec<Job>) -> Result<Vec<Booking>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        let mut cancelled = Vec::with_capacity(bookings.len());
+        for booking in bookings {
+            cancelled.push(sqlx::query_as::<_, Booking>("UPDATE bookings SET status = 'CANCELLED' WHERE id = $1 RETURNING *")
+                .bind(&booking.id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(AppError::Database)?);
+            sqlx::query("UPDATE jobs SET status = 'CANCELLED' WHERE payload->>'booking_id' = $1 AND status = 'PENDING'")
+                .bind(&booking.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::Database)?;
+            if let Some(invitee_id) = &booking.invitee_id {
+                sqlx::query("UPDATE invitees SET use_count = CASE WHEN use_count > 0 THEN use_count - 1 ELSE 0 END, status = CASE WHEN status = 'USED' THEN 'ACTIVE' ELSE status END WHERE id = $1")
+                    .bind(invitee_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(AppError::Database)?;
+            }
+        }
+        // Bookings made since they were listed would be left without their session
+        check_unbooked(&mut tx, std::slice::from_ref(&session.id)).await?;
+
+        for invitee in invitees {
+            insert_invitee(&mut tx, invitee).await?;
+        }
+        for job in &jobs {
+            insert_job(&mut tx, job).await?;
+        }
+        sqlx::query("DELETE FROM event_sessions WHERE id = $1")
+            .bind(&session.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Database)?;
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(cancelled)
+    }
 }
\ No newline at end of file
@@ -53,11 +53,16 @@ impl TenantRepository for PostgresTenantRepo {
 
     async fn update(&self, tenant: &Tenant) -> Result<Tenant, AppError> {
         sqlx::query_as::<_, Tenant>(
-            "UPDATE tenants SET name=$1, logo_url=$2, ai_api_key=$3 WHERE id=$4 RETURNING *"
+            "UPDATE tenants SET name=$1, logo_url=$2, ai_api_key=$3, show_label_id=$4, noshow_label_id=$5, noshow_block_threshold=$6, noshow_window_months=$7, noshow_block_months=$8 WHERE id=$9 RETURNING *"
         )
             .bind(&tenant.name)
             .bind(&tenant.logo_url)
             .bind(&tenant.ai_api_key)
+            .bind(&tenant.show_label_id)
+            .bind(&tenant.noshow_label_id)
+            .bind(tenant.noshow_block_threshold)
+            .bind(tenant.noshow_window_months)
+            .bind(tenant.noshow_block_months)
             .bind(&tenant.id)
             .fetch_one(&self.pool)
             .await
@@ -1,7 +1,10 @@
-use crate::domain::{models::{booking::Booking, job::Job}, ports::BookingRepository};
+use crate::domain::{models::booking::{Booking, BookingLimit, BookingOutcome, BookingWrites}, ports::BookingRepository};
 use crate::error::AppError;
+use crate::infra::repositories::sqlite_audit_repo::insert_audit_log;
+use crate::infra::repositories::sqlite_job_repo::insert_job;
+use crate::infra::repositories::sqlite_participant_repo::link_participant;
 use async_trait::async_trait;
-use sqlx::{SqlitePool, Row};
+use sqlx::{SqliteConnection, SqlitePool, Row};
 use chrono::{DateTime, Utc};
 
 pub struct SqliteBookingRepo {
@@ -14,33 +17,63 @@ impl SqliteBookingRepo {
     }
 }
 
+/// Raises the revision of a booking within the caller's transaction, returning the new one.
+pub(crate) async fn bump_revision(conn: &mut SqliteConnection, tenant_id: &str, id: &str) -> Result<i32, AppError> {
+    sqlx::query_scalar("UPDATE bookings SET revision = revision + 1 WHERE id = ? AND tenant_id = ? RETURNING revision").bind(id).bind(tenant_id).fetch_one(&mut *conn).await.map_err(AppError::Database)
+}
+
 #[async_trait]
 impl BookingRepository for SqliteBookingRepo {
     async fn create(&self, booking: &Booking) -> Result<Booking, AppError> {
-        self.create_with_token(booking, None, vec![]).await
+        match self.create_with_token(booking, None, BookingWrites::default(), None).await? {
+            BookingOutcome::Created(created) | BookingOutcome::Duplicate(created) => Ok(created),
+        }
     }
-    async fn create_with_token(&self, booking: &Booking, token_to_burn: Option<String>, jobs: Vec<Job>) -> Result<Booking, AppError> {
-        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+    async fn create_with_token(&self, booking: &Booking, token_to_burn: Option<String>, writes: BookingWrites, limit: Option<&BookingLimit>) -> Result<BookingOutcome, AppError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await.map_err(AppError::Database)?;
+        let participant_id = match &booking.participant_id {
+            Some(id) => id.clone(),
+            None => link_participant(&mut tx, &booking.tenant_id, &booking.customer_email, &booking.customer_name).await?,
+        };
         if let Some(token) = token_to_burn {
-            let result = sqlx::query("UPDATE invitees SET status = 'USED' WHERE token = ? AND status = 'ACTIVE'").bind(token).execute(&mut *tx).await.map_err(AppError::Database)?;
+            let result = sqlx::query(
+                "UPDATE invitees SET use_count = use_count + 1,
+                     status = CASE WHEN use_count + 1 >= max_uses THEN 'USED' ELSE status END
+                 WHERE token = ? AND status = 'ACTIVE' AND use_count < max_uses AND (expires_at IS NULL OR expires_at > ?)"
+            ).bind(token).bind(Utc::now()).execute(&mut *tx).await.map_err(AppError::Database)?;
             if result.rows_affected() == 0 { return Err(AppError::Conflict("Token invalid or already used".to_string())); }
         }
         let created = sqlx::query_as::<_, Booking>(
-            "INSERT INTO bookings (id, tenant_id, event_id, invitee_id, start_time, end_time, customer_name, customer_email, customer_note, location, label_id, status, management_token, token, payout, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "INSERT INTO bookings (id, tenant_id, event_id, invitee_id, start_time, end_time, customer_name, customer_email, customer_note, location, label_id, status, management_token, token, payout, participant_id, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
              RETURNING *"
         )
             .bind(&booking.id).bind(&booking.tenant_id).bind(&booking.event_id).bind(&booking.invitee_id)
             .bind(booking.start_time).bind(booking.end_time).bind(&booking.customer_name).bind(&booking.customer_email)
             .bind(&booking.customer_note).bind(&booking.location).bind(&booking.label_id).bind(&booking.status)
-            .bind(&booking.management_token).bind(&booking.token).bind(booking.payout).bind(booking.created_at)
+            .bind(&booking.management_token).bind(&booking.token).bind(booking.payout).bind(&participant_id).bind(booking.created_at)
             .fetch_one(&mut *tx).await.map_err(AppError::Database)?;
 
-        for job in jobs {
-            sqlx::query("INSERT INTO jobs (id, job_type, payload, execute_at, status, error_message, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)").bind(&job.id).bind(&job.job_type).bind(&job.payload).bind(job.execute_at).bind(&job.status).bind(&job.error_message).bind(job.created_at).execute(&mut *tx).await.map_err(AppError::Database)?;
+        // The insert holds the write lock, so the check sees every committed booking
+        if let Some(limit) = limit
+            && let Some(existing) = find_active_duplicate(&mut tx, limit, &created).await? {
+            tx.rollback().await.map_err(AppError::Database)?;
+            return Ok(BookingOutcome::Duplicate(existing));
+        }
+
+        for job in &writes.jobs {
+            insert_job(&mut tx, job).await?;
+        }
+        for consent in &writes.consents {
+            sqlx::query("INSERT INTO consent_records (id, tenant_id, booking_id, purpose, granted_at) VALUES (?, ?, ?, ?, ?)")
+                .bind(&consent.id).bind(&consent.tenant_id).bind(&consent.booking_id).bind(&consent.purpose).bind(consent.granted_at)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+        }
+        if let Some(entry) = &writes.audit {
+            insert_audit_log(&mut tx, entry).await?;
         }
         tx.commit().await.map_err(AppError::Database)?;
-        Ok(created)
+        Ok(BookingOutcome::Created(created))
     }
     async fn find_by_id(&self, tenant_id: &str, id: &str) -> Result<Option<Booking>, AppError> {
         sqlx::query_as::<_, Booking>("SELECT * FROM bookings WHERE tenant_id = ? AND id = ?").bind(tenant_id).bind(id).fetch_optional(&self.pool).await.map_err(AppError::Database)
@@ -54,27 +87,55 @@ impl BookingRepository for SqliteBookingRepo {
     async fn list_by_tenant(&self, tenant_id: &str) -> Result<Vec<Booking>, AppError> {
         sqlx::query_as::<_, Booking>("SELECT * FROM bookings WHERE tenant_id = ? ORDER BY start_time ASC").bind(tenant_id).fetch_all(&self.pool).await.map_err(AppError::Database)
     }
+    async fn list_by_participant(&self, tenant_id: &str, participant_id: &str) -> Result<Vec<Booking>, AppError> {
+        sqlx::query_as::<_, Booking>("SELECT * FROM bookings WHERE tenant_id = ? AND participant_id = ? ORDER BY start_time ASC").bind(tenant_id).bind(participant_id).fetch_all(&self.pool).await.map_err(AppError::Database)
+    }
     async fn list_by_range(&self, event_id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Booking>, AppError> {
         sqlx::query_as::<_, Booking>("SELECT * FROM bookings WHERE event_id = ? AND start_time < ? AND end_time > ? AND status != 'CANCELLED'").bind(event_id).bind(end).bind(start).fetch_all(&self.pool).await.map_err(AppError::Database)
     }
+    async fn list_by_tenant_range(&self, tenant_id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Booking>, AppError> {
+        sqlx::query_as::<_, Booking>("SELECT * FROM bookings WHERE tenant_id = ? AND start_time < ? AND end_time > ? AND status != 'CANCELLED'").bind(tenant_id).bind(end).bind(start).fetch_all(&self.pool).await.map_err(AppError::Database)
+    }
     async fn update(&self, booking: &Booking) -> Result<Booking, AppError> {
         sqlx::query_as::<_, Booking>(
-            "UPDATE bookings SET start_time=?, end_time=?, customer_name=?, customer_email=?, location=?, label_id=?, token=?, payout=?
+            "UPDATE bookings SET start_time=?, end_time=?, customer_name=?, customer_email=?, location=?, label_id=?, token=?, payout=?, participant_id=?
              WHERE id=? AND tenant_id=?
              RETURNING *"
         )
             .bind(booking.start_time).bind(booking.end_time).bind(&booking.customer_name).bind(&booking.customer_email)
-            .bind(&booking.location).bind(&booking.label_id).bind(&booking.token).bind(booking.payout)
+            .bind(&booking.location).bind(&booking.label_id).bind(&booking.token).bind(booking.payout).bind(&booking.participant_id)
             .bind(&booking.id).bind(&booking.tenant_id)
             .fetch_one(&self.pool).await.map_err(AppError::Database)
     }
+    async fn next_revision(&self, tenant_id: &str, id: &str) -> Result<i32, AppError> {
+        let mut conn = self.pool.acquire().await.map_err(AppError::Database)?;
+        bump_revision(&mut conn, tenant_id, id).await
+    }
     async fn cancel(&self, booking: &Booking) -> Result<Booking, AppError> {
         let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
         let cancelled = sqlx::query_as::<_, Booking>("UPDATE bookings SET status = 'CANCELLED' WHERE id = ? RETURNING *").bind(&booking.id).fetch_one(&mut *tx).await.map_err(AppError::Database)?;
-        if let Some(invitee_id) = &booking.invitee_id { sqlx::query("UPDATE invitees SET status = 'ACTIVE' WHERE id = ?").bind(invitee_id).execute(&mut *tx).await.map_err(AppError::Database)?; }
+        if let Some(invitee_id) = &booking.invitee_id { sqlx::query("UPDATE invitees SET use_count = CASE WHEN use_count > 0 THEN use_count - 1 ELSE 0 END, status = CASE WHEN status = 'USED' THEN 'ACTIVE' ELSE status END WHERE id = ?").bind(invitee_id).execute(&mut *tx).await.map_err(AppError::Database)?; }
         tx.commit().await.map_err(AppError::Database)?;
         Ok(cancelled)
     }
+    async fn check_in(&self, tenant_id: &str, id: &str, at: DateTime<Utc>, label_id: Option<&str>) -> Result<Option<Booking>, AppError> {
+        sqlx::query_as::<_, Booking>(
+            "UPDATE bookings SET checked_in_at = ?, label_id = COALESCE(?, label_id)
+             WHERE id = ? AND tenant_id = ? AND checked_in_at IS NULL AND status != 'CANCELLED'
+             RETURNING *"
+        )
+            .bind(at).bind(label_id).bind(id).bind(tenant_id)
+            .fetch_optional(&self.pool).await.map_err(AppError::Database)
+    }
+    async fn mark_noshow(&self, tenant_id: &str, id: &str, label_id: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE bookings SET label_id = ?
+             WHERE id = ? AND tenant_id = ? AND checked_in_at IS NULL AND label_id IS NULL AND status != 'CANCELLED'"
+        )
+            .bind(label_id).bind(id).bind(tenant_id)
+            .execute(&self.pool).await.map_err(AppError::Database)?;
+        Ok(result.rows_affected() > 0)
+    }
     async fn delete(&self, tenant_id: &str, id: &str) -> Result<(), AppError> {
         let result = sqlx::query("DELETE FROM bookings WHERE id = ? AND tenant_id = ?").bind(id).bind(tenant_id).execute(&self.pool).await.map_err(AppError::Database)?;
         if result.rows_affected() == 0 { return Err(AppError::NotFound("Booking not found".into())); }
@@ -95,4 +156,25 @@ impl BookingRepository for SqliteBookingRepo {
             .await
             .map_err(AppError::Database)
     }
-}
\ No newline at end of file
+}
+
+/// Earliest active booking other than `created` that counts against the limit.
+async fn find_active_duplicate(conn: &mut SqliteConnection, limit: &BookingLimit, created: &Booking) -> Result<Option<Booking>, AppError> {
+    let query = match limit {
+        BookingLimit::Email { event_id } => sqlx::query_as::<_, Booking>(
+            "SELECT * FROM bookings WHERE event_id = ? AND participant_id = ? AND status != 'CANCELLED' AND end_time > ? AND id != ?
+             ORDER BY created_at ASC LIMIT 1"
+        ).bind(event_id).bind(&created.participant_id),
+        BookingLimit::Invitee { event_id, invitee_id } => sqlx::query_as::<_, Booking>(
+            "SELECT * FROM bookings WHERE event_id = ? AND invitee_id = ? AND status != 'CANCELLED' AND end_time > ? AND id != ?
+             ORDER BY created_at ASC LIMIT 1"
+        ).bind(event_id).bind(invitee_id),
+        BookingLimit::Series { tenant_id, series_key } => sqlx::query_as::<_, Booking>(
+            "SELECT b.* FROM bookings b
+             JOIN events e ON e.id = b.event_id
+             WHERE e.tenant_id = ? AND e.series_key = ? AND b.participant_id = ? AND b.status != 'CANCELLED' AND b.end_time > ? AND b.id != ?
+             ORDER BY b.created_at ASC LIMIT 1"
+        ).bind(tenant_id).bind(series_key).bind(&created.participant_id),
+    };
+    query.bind(created.created_at).bind(&created.id).fetch_optional(conn).await.map_err(AppError::Database)
+}
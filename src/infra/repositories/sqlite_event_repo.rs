@@ -1,7 +1,9 @@
-use crate::domain::{models::event::Event, ports::EventRepository};
+use crate::domain::{models::{event::Event, job::Job}, ports::EventRepository};
 use crate::error::AppError;
+use crate::infra::repositories::sqlite_booking_repo::bump_revision;
+use crate::infra::repositories::sqlite_job_repo::insert_job;
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 
 pub struct SqliteEventRepo {
     pool: SqlitePool,
@@ -13,6 +15,55 @@ impl SqliteEventRepo {
     }
 }
 
+async fn update_row(conn: &mut SqliteConnection, event: &Event) -> Result<Event, AppError> {
+    sqlx::query_as::<_, Event>(
+        r#"UPDATE events SET
+            slug=?, title_en=?, title_de=?, desc_en=?, desc_de=?,
+            location=?, payout=?, host_name=?, timezone=?,
+            min_notice_general=?, min_notice_first=?,
+            active_start=?, active_end=?, duration_min=?, interval_min=?,
+            max_participants=?, image_url=?, config_json=?, access_mode=?, schedule_type=?,
+            allow_customer_cancel=?, allow_customer_reschedule=?,
+            booking_limit=?, series_key=?, reserved_capacity=?, reserved_release_hours=?,
+            allowed_domains=?, denied_domains=?, passcode=?
+           WHERE id=? AND tenant_id=? RETURNING *"#
+    )
+        .bind(&event.slug)
+        .bind(&event.title_en)
+        .bind(&event.title_de)
+        .bind(&event.desc_en)
+        .bind(&event.desc_de)
+        .bind(&event.location)
+        .bind(&event.payout)
+        .bind(&event.host_name)
+        .bind(&event.timezone)
+        .bind(event.min_notice_general)
+        .bind(event.min_notice_first)
+        .bind(event.active_start)
+        .bind(event.active_end)
+        .bind(event.duration_min)
+        .bind(event.interval_min)
+        .bind(event.max_participants)
+        .bind(&event.image_url)
+        .bind(&event.config_json)
+        .bind(&event.access_mode)
+        .bind(&event.schedule_type)
+        .bind(event.allow_customer_cancel)
+        .bind(event.allow_customer_reschedule)
+        .bind(&event.booking_limit)
+        .bind(&event.series_key)
+        .bind(event.reserved_capacity)
+        .bind(event.reserved_release_hours)
+        .bind(&event.allowed_domains)
+        .bind(&event.denied_domains)
+        .bind(&event.passcode)
+        .bind(&event.id)
+        .bind(&event.tenant_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(AppError::Database)
+}
+
 #[async_trait]
 impl EventRepository for SqliteEventRepo {
     async fn create(&self, event: &Event) -> Result<Event, AppError> {
@@ -21,8 +72,10 @@ impl EventRepository for SqliteEventRepo {
                 id, tenant_id, slug, title_en, title_de, desc_en, desc_de,
                 location, payout, host_name, timezone, min_notice_general, min_notice_first,
                 active_start, active_end, duration_min, interval_min, max_participants,
-                image_url, config_json, access_mode, schedule_type, allow_customer_cancel, allow_customer_reschedule, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                image_url, config_json, access_mode, schedule_type, allow_customer_cancel, allow_customer_reschedule,
+                booking_limit, series_key, reserved_capacity, reserved_release_hours,
+                allowed_domains, denied_domains, passcode, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             RETURNING *"#
         )
             .bind(&event.id)
@@ -49,6 +102,13 @@ impl EventRepository for SqliteEventRepo {
             .bind(&event.schedule_type)
             .bind(event.allow_customer_cancel)
             .bind(event.allow_customer_reschedule)
+            .bind(&event.booking_limit)
+            .bind(&event.series_key)
+            .bind(event.reserved_capacity)
+            .bind(event.reserved_release_hours)
+            .bind(&event.allowed_domains)
+            .bind(&event.denied_domains)
+            .bind(&event.passcode)
             .bind(event.created_at)
             .fetch_one(&self.pool)
             .await
@@ -88,43 +148,19 @@ impl EventRepository for SqliteEventRepo {
     }
 
     async fn update(&self, event: &Event) -> Result<Event, AppError> {
-        sqlx::query_as::<_, Event>(
-            r#"UPDATE events SET
-                slug=?, title_en=?, title_de=?, desc_en=?, desc_de=?,
-                location=?, payout=?, host_name=?, timezone=?,
-                min_notice_general=?, min_notice_first=?,
-                active_start=?, active_end=?, duration_min=?, interval_min=?,
-                max_participants=?, image_url=?, config_json=?, access_mode=?, schedule_type=?,
-                allow_customer_cancel=?, allow_customer_reschedule=?
-               WHERE id=? AND tenant_id=? RETURNING *"#
-        )
-            .bind(&event.slug)
-            .bind(&event.title_en)
-            .bind(&event.title_de)
-            .bind(&event.desc_en)
-            .bind(&event.desc_de)
-            .bind(&event.location)
-            .bind(&event.payout)
-            .bind(&event.host_name)
-            .bind(&event.timezone)
-            .bind(event.min_notice_general)
-            .bind(event.min_notice_first)
-            .bind(event.active_start)
-            .bind(event.active_end)
-            .bind(event.duration_min)
-            .bind(event.interval_min)
-            .bind(event.max_participants)
-            .bind(&event.image_url)
-            .bind(&event.config_json)
-            .bind(&event.access_mode)
-            .bind(&event.schedule_type)
-            .bind(event.allow_customer_cancel)
-            .bind(event.allow_customer_reschedule)
-            .bind(&event.id)
-            .bind(&event.tenant_id)
-            .fetch_one(&self.pool)
-            .await
-            .map_err(AppError::Database)
+        let mut conn = self.pool.acquire().await.map_err(AppError::Database)?;
+        update_row(&mut conn, event).await
+    }
+
+    async fn update_with_notices(&self, event: &Event, jobs: &[Job]) -> Result<Event, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        let updated = update_row(&mut tx, event).await?;
+        for job in jobs {
+            bump_revision(&mut tx, &job.payload.tenant_id, &job.payload.booking_id).await?;
+            insert_job(&mut tx, job).await?;
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(updated)
     }
 
     async fn delete(&self, tenant_id: &str, id: &str) -> Result<(), AppError> {
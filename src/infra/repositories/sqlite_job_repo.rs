@@ -1,8 +1,8 @@
 use crate::domain::{models::job::Job, ports::JobRepository, models::booking::Booking};
 use crate::error::AppError;
 use async_trait::async_trait;
-use sqlx::SqlitePool;
-use chrono::Utc;
+use sqlx::{SqlitePool, SqliteConnection};
+use chrono::{DateTime, Utc};
 
 pub struct SqliteJobRepo {
     pool: SqlitePool,
@@ -12,6 +12,16 @@ impl SqliteJobRepo {
     pub fn new(pool: SqlitePool) -> Self { Self { pool } }
 }
 
+/// Inserts `job` within the caller's transaction.
+pub(crate) async fn insert_job(conn: &mut SqliteConnection, job: &Job) -> Result<(), AppError> {
+    sqlx::query("INSERT INTO jobs (id, job_type, payload, execute_at, status, error_message, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)")
+        .bind(&job.id).bind(&job.job_type).bind(&job.payload).bind(job.execute_at).bind(&job.status).bind(&job.error_message).bind(job.created_at)
+        .execute(&mut *conn)
+        .await
+        .map_err(AppError::Database)?;
+    Ok(())
+}
+
 #[async_trait]
 impl JobRepository for SqliteJobRepo {
     async fn create(&self, job: &Job) -> Result<Job, AppError> {
@@ -72,6 +82,21 @@ impl JobRepository for SqliteJobRepo {
         Ok(())
     }
 
+    async fn create_unless_recent(&self, job: &Job, since: DateTime<Utc>) -> Result<bool, AppError> {
+        // One statement, so the check and the insert happen under the same write lock
+        let result = sqlx::query(
+            "INSERT INTO jobs (id, job_type, payload, execute_at, status, error_message, created_at)
+             SELECT ?, ?, ?, ?, ?, ?, ?
+             WHERE NOT EXISTS (SELECT 1 FROM jobs WHERE job_type = ? AND json_extract(payload, '$.booking_id') = ? AND created_at > ?)"
+        )
+            .bind(&job.id).bind(&job.job_type).bind(&job.payload).bind(job.execute_at).bind(&job.status).bind(&job.error_message).bind(job.created_at)
+            .bind(&job.job_type).bind(&job.payload.booking_id).bind(since)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn delete_jobs_by_type_and_event(&self, event_id: &str, job_type: &str) -> Result<(), AppError> {
         let query = r#"
             DELETE FROM jobs
@@ -0,0 +1,51 @@
+use crate::domain::{models::{job::Job, portal::PortalToken}, ports::PortalRepository};
+use crate::error::AppError;
+use crate::infra::repositories::sqlite_job_repo::insert_job;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+pub struct SqlitePortalRepo {
+    pool: SqlitePool,
+}
+
+impl SqlitePortalRepo {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PortalRepository for SqlitePortalRepo {
+    async fn issue(&self, token: &PortalToken, job: &Job, since: DateTime<Utc>) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        let result = sqlx::query(
+            "INSERT INTO portal_tokens (token, tenant_id, participant_id, expires_at, created_at)
+             SELECT ?, ?, ?, ?, ?
+             WHERE NOT EXISTS (SELECT 1 FROM portal_tokens WHERE tenant_id = ? AND participant_id = ? AND created_at > ?)"
+        )
+            .bind(&token.token).bind(&token.tenant_id).bind(&token.participant_id)
+            .bind(token.expires_at).bind(token.created_at)
+            .bind(&token.tenant_id).bind(&token.participant_id).bind(since)
+            .execute(&mut *tx).await.map_err(AppError::Database)?;
+        let issued = result.rows_affected() > 0;
+        if issued {
+            insert_job(&mut tx, job).await?;
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(issued)
+    }
+
+    async fn find_valid(&self, token: &str, now: DateTime<Utc>) -> Result<Option<PortalToken>, AppError> {
+        sqlx::query_as::<_, PortalToken>("SELECT * FROM portal_tokens WHERE token = ? AND expires_at > ?")
+            .bind(token).bind(now)
+            .fetch_optional(&self.pool).await.map_err(AppError::Database)
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM portal_tokens WHERE expires_at <= ?")
+            .bind(now)
+            .execute(&self.pool).await.map_err(AppError::Database)?;
+        Ok(result.rows_affected())
+    }
+}
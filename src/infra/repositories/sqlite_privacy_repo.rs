@@ -0,0 +1,130 @@
+use crate::domain::{models::{audit::ErasureSummary, communication::MailLog, consent::ConsentRecord, invitee::Invitee}, ports::PrivacyRepository};
+use crate::domain::services::privacy::{ERASED_EMAIL, ERASED_NAME};
+use crate::error::AppError;
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+pub struct SqlitePrivacyRepo {
+    pool: SqlitePool,
+}
+
+impl SqlitePrivacyRepo {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PrivacyRepository for SqlitePrivacyRepo {
+    async fn find_invitees(&self, tenant_id: &str, emails: &[String]) -> Result<Vec<Invitee>, AppError> {
+        let mut invitees = Vec::new();
+        for email in emails {
+            let mut found = sqlx::query_as::<_, Invitee>("SELECT * FROM invitees WHERE tenant_id = ? AND LOWER(TRIM(email)) = ?")
+                .bind(tenant_id).bind(email)
+                .fetch_all(&self.pool).await.map_err(AppError::Database)?;
+            invitees.append(&mut found);
+        }
+        Ok(invitees)
+    }
+
+    async fn find_mail_logs(&self, tenant_id: &str, emails: &[String]) -> Result<Vec<MailLog>, AppError> {
+        let mut logs = Vec::new();
+        for email in emails {
+            let mut found = sqlx::query_as::<_, MailLog>(
+                "SELECT ml.* FROM mail_logs ml
+                 JOIN jobs j ON ml.job_id = j.id
+                 WHERE json_extract(j.payload, '$.tenant_id') = ? AND LOWER(TRIM(ml.recipient)) = ?
+                 ORDER BY ml.sent_at ASC"
+            )
+                .bind(tenant_id).bind(email)
+                .fetch_all(&self.pool).await.map_err(AppError::Database)?;
+            logs.append(&mut found);
+        }
+        Ok(logs)
+    }
+
+    async fn find_consents(&self, tenant_id: &str, booking_ids: &[String]) -> Result<Vec<ConsentRecord>, AppError> {
+        let mut consents = Vec::new();
+        for booking_id in booking_ids {
+            let mut found = sqlx::query_as::<_, ConsentRecord>("SELECT * FROM consent_records WHERE tenant_id = ? AND booking_id = ? ORDER BY granted_at ASC")
+                .bind(tenant_id).bind(booking_id)
+                .fetch_all(&self.pool).await.map_err(AppError::Database)?;
+            consents.append(&mut found);
+        }
+        Ok(consents)
+    }
+
+    async fn erase_subject(&self, tenant_id: &str, emails: &[String], booking_ids: &[String], participant_id: Option<&str>) -> Result<ErasureSummary, AppError> {
+        let mut summary = ErasureSummary::default();
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+
+        for booking_id in booking_ids {
+            let cancelled = sqlx::query("UPDATE bookings SET status = 'CANCELLED' WHERE id = ? AND tenant_id = ? AND start_time > ? AND status != 'CANCELLED'")
+                .bind(booking_id).bind(tenant_id).bind(Utc::now())
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            summary.bookings_cancelled += cancelled.rows_affected();
+
+            let anonymized = sqlx::query("UPDATE bookings SET customer_name = ?, customer_email = ?, customer_note = NULL, token = NULL WHERE id = ? AND tenant_id = ?")
+                .bind(ERASED_NAME).bind(ERASED_EMAIL).bind(booking_id).bind(tenant_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            summary.bookings_anonymized += anonymized.rows_affected();
+
+            sqlx::query("UPDATE jobs SET status = 'CANCELLED' WHERE json_extract(payload, '$.booking_id') = ? AND status = 'PENDING'")
+                .bind(booking_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+
+            let deleted = sqlx::query("DELETE FROM bank_details WHERE booking_id = ? AND tenant_id = ?")
+                .bind(booking_id).bind(tenant_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            summary.bank_details_deleted += deleted.rows_affected();
+
+            // Replayable responses hold the booking as it was, name and email included
+            let replays = sqlx::query("DELETE FROM idempotency_keys WHERE booking_id = ?")
+                .bind(booking_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            summary.idempotency_records_deleted += replays.rows_affected();
+        }
+
+        for email in emails {
+            let invitees = sqlx::query("UPDATE invitees SET email = NULL WHERE tenant_id = ? AND LOWER(TRIM(email)) = ?")
+                .bind(tenant_id).bind(email)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            summary.invitees_anonymized += invitees.rows_affected();
+
+            let logs = sqlx::query(
+                "UPDATE mail_logs SET recipient = ?
+                 WHERE LOWER(TRIM(recipient)) = ?
+                 AND job_id IN (SELECT id FROM jobs WHERE json_extract(payload, '$.tenant_id') = ?)"
+            )
+                .bind(ERASED_EMAIL).bind(email).bind(tenant_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            summary.mail_logs_anonymized += logs.rows_affected();
+
+            sqlx::query("DELETE FROM participant_emails WHERE tenant_id = ? AND email = ?")
+                .bind(tenant_id).bind(email)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+        }
+
+        // The profile stays so its bookings keep counting towards statistics
+        if let Some(participant_id) = participant_id {
+            // Notices to the participant itself carry its id instead of a booking's
+            sqlx::query("UPDATE jobs SET status = 'CANCELLED' WHERE job_type IN ('BOOKING_BLOCKED', 'PORTAL_LINK') AND json_extract(payload, '$.booking_id') = ? AND status = 'PENDING'")
+                .bind(participant_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            sqlx::query("UPDATE jobs SET payload = json_remove(payload, '$.data.email') WHERE job_type = 'PORTAL_LINK' AND json_extract(payload, '$.booking_id') = ?")
+                .bind(participant_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            let tokens = sqlx::query("DELETE FROM portal_tokens WHERE participant_id = ? AND tenant_id = ?")
+                .bind(participant_id).bind(tenant_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            summary.portal_tokens_deleted += tokens.rows_affected();
+            sqlx::query("UPDATE participants SET name = ?, email = ?, notes = NULL, updated_at = ? WHERE id = ? AND tenant_id = ?")
+                .bind(ERASED_NAME).bind(ERASED_EMAIL).bind(Utc::now()).bind(participant_id).bind(tenant_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+        }
+
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(summary)
+    }
+}
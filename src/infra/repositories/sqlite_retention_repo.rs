@@ -0,0 +1,154 @@
+use crate::domain::{models::retention::{RetentionPolicy, RetentionReport}, ports::RetentionRepository};
+use crate::domain::services::privacy::{ERASED_EMAIL, ERASED_NAME};
+use crate::error::AppError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+/// Participants without any booking after the cutoff; binds tenant, erased email and the cutoff twice.
+const STALE_PARTICIPANTS: &str = "SELECT id FROM participants p WHERE p.tenant_id = ? AND p.email != ? AND p.created_at < ?
+                                  AND NOT EXISTS (SELECT 1 FROM bookings b WHERE b.participant_id = p.id AND b.end_time >= ?)";
+
+pub struct SqliteRetentionRepo {
+    pool: SqlitePool,
+}
+
+impl SqliteRetentionRepo {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RetentionRepository for SqliteRetentionRepo {
+    async fn find_policy(&self, tenant_id: &str) -> Result<Option<RetentionPolicy>, AppError> {
+        sqlx::query_as::<_, RetentionPolicy>("SELECT * FROM retention_policies WHERE tenant_id = ?")
+            .bind(tenant_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    async fn upsert_policy(&self, policy: &RetentionPolicy) -> Result<RetentionPolicy, AppError> {
+        sqlx::query_as::<_, RetentionPolicy>(
+            r#"INSERT INTO retention_policies (tenant_id, anonymize_after_months, mail_logs_after_months, invitees_after_months, last_run_at, updated_at)
+               VALUES (?, ?, ?, ?, ?, ?)
+               ON CONFLICT(tenant_id) DO UPDATE SET
+               anonymize_after_months=excluded.anonymize_after_months,
+               mail_logs_after_months=excluded.mail_logs_after_months,
+               invitees_after_months=excluded.invitees_after_months,
+               updated_at=excluded.updated_at
+               RETURNING *"#
+        )
+            .bind(&policy.tenant_id)
+            .bind(policy.anonymize_after_months)
+            .bind(policy.mail_logs_after_months)
+            .bind(policy.invitees_after_months)
+            .bind(policy.last_run_at)
+            .bind(policy.updated_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    async fn list_policies(&self) -> Result<Vec<RetentionPolicy>, AppError> {
+        sqlx::query_as::<_, RetentionPolicy>("SELECT * FROM retention_policies")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    async fn report(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<RetentionReport, AppError> {
+        let tenant_id = &policy.tenant_id;
+        let mut report = RetentionReport::new(policy, now, true);
+        let count = |count: i64| count as u64;
+
+        if let Some(cutoff) = report.anonymize_before {
+            report.bank_details_deleted = count(sqlx::query_scalar("SELECT COUNT(*) FROM bank_details WHERE tenant_id = ? AND booking_id IN (SELECT id FROM bookings WHERE tenant_id = ? AND end_time < ?)")
+                .bind(tenant_id).bind(tenant_id).bind(cutoff)
+                .fetch_one(&self.pool).await.map_err(AppError::Database)?);
+            report.bookings_anonymized = count(sqlx::query_scalar("SELECT COUNT(*) FROM bookings WHERE tenant_id = ? AND end_time < ? AND customer_email != ?")
+                .bind(tenant_id).bind(cutoff).bind(ERASED_EMAIL)
+                .fetch_one(&self.pool).await.map_err(AppError::Database)?);
+            report.participants_anonymized = count(sqlx::query_scalar(&format!("SELECT COUNT(*) FROM ({})", STALE_PARTICIPANTS))
+                .bind(tenant_id).bind(ERASED_EMAIL).bind(cutoff).bind(cutoff)
+                .fetch_one(&self.pool).await.map_err(AppError::Database)?);
+        }
+
+        if let Some(cutoff) = report.mail_logs_before {
+            report.mail_logs_deleted = count(sqlx::query_scalar("SELECT COUNT(*) FROM mail_logs WHERE sent_at < ? AND job_id IN (SELECT id FROM jobs WHERE json_extract(payload, '$.tenant_id') = ?)")
+                .bind(cutoff).bind(tenant_id)
+                .fetch_one(&self.pool).await.map_err(AppError::Database)?);
+        }
+
+        if let Some(cutoff) = report.invitees_before {
+            report.invitees_deleted = count(sqlx::query_scalar("SELECT COUNT(*) FROM invitees WHERE tenant_id = ? AND created_at < ?")
+                .bind(tenant_id).bind(cutoff)
+                .fetch_one(&self.pool).await.map_err(AppError::Database)?);
+        }
+
+        Ok(report)
+    }
+
+    async fn apply(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<RetentionReport, AppError> {
+        let tenant_id = &policy.tenant_id;
+        let mut report = RetentionReport::new(policy, now, false);
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+
+        if let Some(cutoff) = report.anonymize_before {
+            let deleted = sqlx::query("DELETE FROM bank_details WHERE tenant_id = ? AND booking_id IN (SELECT id FROM bookings WHERE tenant_id = ? AND end_time < ?)")
+                .bind(tenant_id).bind(tenant_id).bind(cutoff)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            report.bank_details_deleted = deleted.rows_affected();
+
+            let bookings = sqlx::query(
+                "UPDATE bookings SET customer_name = ?, customer_email = ?, customer_note = NULL, token = NULL
+                 WHERE tenant_id = ? AND end_time < ? AND customer_email != ?"
+            )
+                .bind(ERASED_NAME).bind(ERASED_EMAIL).bind(tenant_id).bind(cutoff).bind(ERASED_EMAIL)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            report.bookings_anonymized = bookings.rows_affected();
+
+            sqlx::query(&format!("DELETE FROM participant_emails WHERE participant_id IN ({})", STALE_PARTICIPANTS))
+                .bind(tenant_id).bind(ERASED_EMAIL).bind(cutoff).bind(cutoff)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            let participants = sqlx::query(&format!("UPDATE participants SET name = ?, email = ?, notes = NULL, updated_at = ? WHERE id IN ({})", STALE_PARTICIPANTS))
+                .bind(ERASED_NAME).bind(ERASED_EMAIL).bind(now)
+                .bind(tenant_id).bind(ERASED_EMAIL).bind(cutoff).bind(cutoff)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            report.participants_anonymized = participants.rows_affected();
+        }
+
+        if let Some(cutoff) = report.mail_logs_before {
+            let deleted = sqlx::query("DELETE FROM mail_logs WHERE sent_at < ? AND job_id IN (SELECT id FROM jobs WHERE json_extract(payload, '$.tenant_id') = ?)")
+                .bind(cutoff).bind(tenant_id)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            report.mail_logs_deleted = deleted.rows_affected();
+        }
+
+        if let Some(cutoff) = report.invitees_before {
+            sqlx::query("UPDATE bookings SET invitee_id = NULL WHERE tenant_id = ? AND invitee_id IN (SELECT id FROM invitees WHERE tenant_id = ? AND created_at < ?)")
+                .bind(tenant_id).bind(tenant_id).bind(cutoff)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            let deleted = sqlx::query("DELETE FROM invitees WHERE tenant_id = ? AND created_at < ?")
+                .bind(tenant_id).bind(cutoff)
+                .execute(&mut *tx).await.map_err(AppError::Database)?;
+            report.invitees_deleted = deleted.rows_affected();
+        }
+
+        sqlx::query("UPDATE retention_policies SET last_run_at = ? WHERE tenant_id = ?")
+            .bind(now).bind(tenant_id)
+            .execute(&mut *tx).await.map_err(AppError::Database)?;
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(report)
+    }
+
+    async fn purge_expired_refresh_tokens(&self, now: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM refresh_tokens WHERE expires_at < ?")
+            .bind(now)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        Ok(result.rows_affected())
+    }
+}
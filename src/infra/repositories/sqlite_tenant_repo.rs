@@ -53,11 +53,16 @@ impl TenantRepository for SqliteTenantRepo {
 
     async fn update(&self, tenant: &Tenant) -> Result<Tenant, AppError> {
         sqlx::query_as::<_, Tenant>(
-            "UPDATE tenants SET name=?, logo_url=?, ai_api_key=? WHERE id=? RETURNING *"
+            "UPDATE tenants SET name=?, logo_url=?, ai_api_key=?, show_label_id=?, noshow_label_id=?, noshow_block_threshold=?, noshow_window_months=?, noshow_block_months=? WHERE id=? RETURNING *"
         )
             .bind(&tenant.name)
             .bind(&tenant.logo_url)
             .bind(&tenant.ai_api_key)
+            .bind(&tenant.show_label_id)
+            .bind(&tenant.noshow_label_id)
+            .bind(tenant.noshow_block_threshold)
+            .bind(tenant.noshow_window_months)
+            .bind(tenant.noshow_block_months)
             .bind(&tenant.id)
             .fetch_one(&self.pool)
             .await
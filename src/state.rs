@@ -3,9 +3,11 @@ use crate::domain::ports::{
     BookingRepository, EventRepository, InviteeRepository, TenantRepository,
     UserRepository, JobRepository, EmailService, EventOverrideRepository,
     AuthRepository, BookingLabelRepository, SessionRepository, CommunicationRepository,
-    LlmService
+    LlmService, PayoutRepository, ParticipantRepository, AuditRepository, PrivacyRepository,
+    RetentionRepository, IdempotencyRepository, PortalRepository
 };
 use crate::domain::services::auth_service::AuthService;
+use crate::domain::services::availability_cache::AvailabilityCache;
 use crate::config::Config;
 use tera::Tera;
 
@@ -23,8 +25,16 @@ pub struct AppState {
     pub label_repo: Arc<dyn BookingLabelRepository>,
     pub session_repo: Arc<dyn SessionRepository>,
     pub communication_repo: Arc<dyn CommunicationRepository>,
+    pub payout_repo: Arc<dyn PayoutRepository>,
+    pub participant_repo: Arc<dyn ParticipantRepository>,
+    pub audit_repo: Arc<dyn AuditRepository>,
+    pub privacy_repo: Arc<dyn PrivacyRepository>,
+    pub retention_repo: Arc<dyn RetentionRepository>,
+    pub idempotency_repo: Arc<dyn IdempotencyRepository>,
+    pub portal_repo: Arc<dyn PortalRepository>,
     pub auth_service: Arc<AuthService>,
     pub email_service: Arc<dyn EmailService>,
     pub llm_service: Arc<dyn LlmService>,
     pub templates: Arc<Tera>,
+    pub availability_cache: Arc<AvailabilityCache>,
 }
\ No newline at end of file
@@ -14,8 +14,16 @@ use booking_backend::{
         sqlite_label_repo::SqliteLabelRepo,
         sqlite_session_repo::SqliteSessionRepo,
         sqlite_communication_repo::SqliteCommunicationRepo,
+        sqlite_payout_repo::SqlitePayoutRepo,
+        sqlite_participant_repo::SqliteParticipantRepo,
+        sqlite_audit_repo::SqliteAuditRepo,
+        sqlite_privacy_repo::SqlitePrivacyRepo,
+        sqlite_retention_repo::SqliteRetentionRepo,
+        sqlite_idempotency_repo::SqliteIdempotencyRepo,
+        sqlite_portal_repo::SqlitePortalRepo,
     },
     domain::services::auth_service::AuthService,
+    domain::services::availability_cache::AvailabilityCache,
     domain::ports::{EmailService, LlmService},
     background::start_background_worker,
     error::AppError,
@@ -113,6 +121,8 @@ impl TestApp {
             jwt_public_key: pub_key_pem.to_string(),
             auth_issuer: "test-issuer".to_string(),
             frontend_url: "http://localhost:3000".to_string(),
+            payment_encryption_key: Some("test-payment-key".to_string()),
+            checkin_secret: "test-checkin-secret".to_string(),
         };
 
         let auth_repo = Arc::new(SqliteAuthRepo::new(pool.clone()));
@@ -130,11 +140,19 @@ impl TestApp {
             label_repo: Arc::new(SqliteLabelRepo::new(pool.clone())),
             session_repo: Arc::new(SqliteSessionRepo::new(pool.clone())),
             communication_repo: Arc::new(SqliteCommunicationRepo::new(pool.clone())),
+            payout_repo: Arc::new(SqlitePayoutRepo::new(pool.clone())),
+            participant_repo: Arc::new(SqliteParticipantRepo::new(pool.clone())),
+            audit_repo: Arc::new(SqliteAuditRepo::new(pool.clone())),
+            privacy_repo: Arc::new(SqlitePrivacyRepo::new(pool.clone())),
+            retention_repo: Arc::new(SqliteRetentionRepo::new(pool.clone())),
+            idempotency_repo: Arc::new(SqliteIdempotencyRepo::new(pool.clone())),
+            portal_repo: Arc::new(SqlitePortalRepo::new(pool.clone())),
             auth_repo,
             auth_service,
             email_service: Arc::new(MockEmailService),
             llm_service: Arc::new(MockLlmService),
             templates,
+            availability_cache: Arc::new(AvailabilityCache::default()),
         });
 
         // Start Background Worker
@@ -0,0 +1,122 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_domain_and_passcode_access_modes() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Campus Lab", "slug": "campus-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+    let event = |slug: &str, access: Value| {
+        let mut body = json!({
+            "slug": slug, "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+            "location": "Lab", "payout": "15", "host_name": "H", "timezone": "UTC",
+            "active_start": Utc::now().to_rfc3339(),
+            "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+            "duration_min": 60, "interval_min": 60, "max_participants": 5, "image_url": ".",
+            "config": { "monday": [{"start":"08:00", "end":"18:00"}] }
+        });
+        body.as_object_mut().unwrap().extend(access.as_object().unwrap().clone());
+        body
+    };
+
+    // 2. Validation
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), event("code", json!({"access_mode": "PASSCODE"})))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), event("campus", json!({"access_mode": "DOMAIN", "allowed_domains": ["not a domain"]})))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), event("campus", json!({
+        "access_mode": "DOMAIN", "allowed_domains": ["@KIT.edu"], "denied_domains": ["alumni.kit.edu"]
+    })))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(parse_body(res).await["allowed_domains"], "kit.edu");
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), event("code", json!({"access_mode": "PASSCODE", "passcode": " wave-7 "})))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let book = |slug: &str, email: &str, passcode: Option<&str>| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, slug))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": "09:00", "name": "Ada", "email": email, "passcode": passcode}).to_string())).unwrap()
+    };
+    let get = |uri: String| Request::builder().method("GET").uri(uri).body(Body::empty()).unwrap();
+
+    // 3. Domain lists: subdomains match, the deny list wins
+    let res = app.router.clone().oneshot(book("campus", "ada@gmail.com", None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(book("campus", "old@alumni.kit.edu", None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(book("campus", "uxxxx@student.KIT.edu", None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(book("campus", "prof@kit.edu", None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 4. The passcode guards the event page, the slots and booking
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/code", tid))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/code?passcode=guess", tid))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/code?passcode=wave-7", tid))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let public_event = parse_body(res).await;
+    assert!(public_event.get("passcode").is_none());
+
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/code/slots?date={}", tid, date))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/code/slots?date={}&passcode=wave-7", tid, date))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(!parse_body(res).await["slots"].as_array().unwrap().is_empty());
+
+    let res = app.router.clone().oneshot(book("code", "ada@test.com", None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(book("code", "ada@test.com", Some("guess"))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(book("code", "ada@test.com", Some("wave-7"))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let token = parse_body(res).await["management_token"].as_str().unwrap().to_string();
+
+    // Nor does the booking's management page give it away
+    let res = app.router.clone().oneshot(get(format!("/api/v1/bookings/manage/{}", token))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let managed = parse_body(res).await;
+    assert_eq!(managed["event"]["slug"], "code");
+    assert!(managed["event"].get("passcode").is_none());
+
+    // 5. Admins still see the passcode
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/events/code", tid), Value::Null)).await.unwrap();
+    assert_eq!(parse_body(res).await["passcode"], "wave-7");
+}
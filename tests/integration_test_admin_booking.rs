@@ -0,0 +1,125 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_admin_booking_on_behalf_with_overrides() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Phone Lab", "slug": "phone-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    // Every slot lies inside the notice window, the next Monday's slots outside the active range
+    for (slug, access_mode) in [("phone", "OPEN"), ("closed", "CLOSED")] {
+        let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+            "slug": slug, "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+            "location": "Lab", "payout": "15", "host_name": "H", "timezone": "UTC",
+            "min_notice_general": 60 * 24 * 60, "min_notice_first": 60 * 24 * 60,
+            "active_start": Utc::now().to_rfc3339(),
+            "active_end": (next_mon + Duration::days(1)).to_rfc3339(),
+            "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+            "config": { "monday": [{"start":"09:00", "end":"12:00"}] },
+            "access_mode": access_mode
+        }))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    let request = |slug: &str, email: &str, date: &str, overrides: Value, suppress: bool| {
+        admin("POST", format!("/api/v1/{}/events/{}/bookings", tid, slug), json!({
+            "date": date, "time": "09:00", "name": "Caller", "email": email,
+            "overrides": overrides, "suppress_confirmation": suppress
+        }))
+    };
+
+    // 2. The endpoint requires a login
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/phone/bookings", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": "09:00", "name": "X", "email": "x@test.com"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    // 3. Without overrides the event rules apply as for the public
+    let res = app.router.clone().oneshot(request("phone", "a@test.com", &date, json!({}), false)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    // 4. Notice override
+    let res = app.router.clone().oneshot(request("phone", "a@test.com", &date, json!({"min_notice": true}), false)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let first = parse_body(res).await;
+
+    // 5. Capacity override
+    let res = app.router.clone().oneshot(request("phone", "b@test.com", &date, json!({"min_notice": true}), false)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    let res = app.router.clone().oneshot(request("phone", "b@test.com", &date, json!({"min_notice": true, "capacity": true}), true)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let second = parse_body(res).await;
+
+    // 6. Access mode override
+    let res = app.router.clone().oneshot(request("closed", "c@test.com", &date, json!({"min_notice": true}), false)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(request("closed", "c@test.com", &date, json!({"min_notice": true, "access_mode": true}), false)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 7. Active range override
+    let later = (next_mon + Duration::days(7)).format("%Y-%m-%d").to_string();
+    let res = app.router.clone().oneshot(request("phone", "d@test.com", &later, json!({"min_notice": true}), false)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res = app.router.clone().oneshot(request("phone", "d@test.com", &later, json!({"min_notice": true, "active_range": true}), false)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 8. The confirmation can be suppressed
+    let confirmations = |booking_id: String| {
+        let pool = app.pool.clone();
+        async move {
+            let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'CONFIRMATION' AND payload LIKE '%' || ? || '%'")
+                .bind(booking_id).fetch_one(&pool).await.unwrap();
+            count
+        }
+    };
+    assert_eq!(confirmations(first["id"].as_str().unwrap().to_string()).await, 1);
+    assert_eq!(confirmations(second["id"].as_str().unwrap().to_string()).await, 0);
+
+    // 9. Every booking on behalf is audited with its overrides
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/audit-logs", tid), Value::Null)).await.unwrap();
+    let logs = parse_body(res).await;
+    let entries: Vec<&Value> = logs.as_array().unwrap().iter().filter(|l| l["action"] == "BOOKING_ON_BEHALF").collect();
+    assert_eq!(entries.len(), 4);
+    let entry = entries.iter().find(|l| l["subject"] == second["id"]).unwrap();
+    assert_eq!(entry["details"]["overrides"]["capacity"], true);
+    assert_eq!(entry["details"]["suppress_confirmation"], true);
+    assert_eq!(entry["details"]["overrides"]["ignore_block"], false);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_conflicts_suggest_alternative_slots() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Busy Lab", "slug": "busy-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "slug": "busy", "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+                "location": "Lab", "payout": "15", "host_name": "H", "timezone": "UTC",
+                "active_start": Utc::now().to_rfc3339(),
+                "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+                "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+                "config": { "monday": [{"start":"09:00", "end":"12:00"}] },
+                "access_mode": "OPEN"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+    let following = (next_mon + Duration::days(7)).format("%Y-%m-%d").to_string();
+
+    let book = |time: &str, email: &str| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/busy/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": time, "name": "Ada", "email": email}).to_string())).unwrap()
+    };
+    let slot = |date: &str, time: &str| format!("{}T{}:00+00:00", date, time);
+
+    // 2. A taken slot suggests the nearest free ones
+    let res = app.router.clone().oneshot(book("10:00", "first@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let first = parse_body(res).await;
+
+    let res = app.router.clone().oneshot(book("10:00", "second@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    let body = parse_body(res).await;
+    assert!(body["error"].as_str().unwrap().contains("not available"));
+    assert_eq!(body["alternatives"], json!([slot(&date, "09:00"), slot(&date, "11:00"), slot(&following, "09:00")]));
+
+    // 3. A fully booked day falls back to the following days
+    for (time, email) in [("09:00", "a@test.com"), ("11:00", "b@test.com")] {
+        let res = app.router.clone().oneshot(book(time, email)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+    let res = app.router.clone().oneshot(book("11:00", "second@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    let body = parse_body(res).await;
+    assert_eq!(body["alternatives"], json!([slot(&following, "09:00"), slot(&following, "10:00"), slot(&following, "11:00")]));
+
+    // 4. Rescheduling into a taken slot suggests alternatives as well
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/bookings/manage/{}/reschedule", first["management_token"].as_str().unwrap()))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": "09:00"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    let body = parse_body(res).await;
+    assert_eq!(body["alternatives"].as_array().unwrap().len(), 3);
+    assert_eq!(body["alternatives"][0], slot(&following, "09:00"));
+}
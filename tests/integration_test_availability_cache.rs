@@ -0,0 +1,157 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use booking_backend::domain::services::availability::{Audience, SlotDetail};
+use booking_backend::domain::services::availability_cache::{AvailabilityCache, MAX_ENTRIES_PER_TENANT};
+use chrono::{Duration, NaiveDate, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_availability_cache_is_invalidated_by_changes() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Cache Lab", "slug": "cache-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": "cached", "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "15", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+        "config": { "monday": [{"start":"09:00", "end":"11:00"}] },
+        "access_mode": "OPEN"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let slots = || async {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("GET").uri(format!("/api/v1/{}/events/cached/slots?date={}", tid, date))
+                .body(Body::empty()).unwrap()
+        ).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        parse_body(res).await["slots"].as_array().unwrap().len()
+    };
+    let stats = || async {
+        let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/availability/cache-stats", tid), Value::Null)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        parse_body(res).await
+    };
+
+    // 2. The second read is served from the cache, also for the date range
+    assert_eq!(slots().await, 2);
+    assert_eq!(slots().await, 2);
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/events/cached/dates?start={}&end={}", tid, date, date))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(parse_body(res).await, json!([date]));
+    let s = stats().await;
+    assert_eq!((s["hits"].as_u64(), s["misses"].as_u64(), s["entries"].as_u64()), (Some(2), Some(1), Some(1)));
+
+    // 3. A booking drops the cached day
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/cached/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": "09:00", "name": "Ada", "email": "ada@test.com"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let booking = parse_body(res).await;
+    assert_eq!(stats().await["invalidations"], 1);
+    assert_eq!(slots().await, 1);
+
+    // 4. So does cancelling it
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/bookings/manage/{}/cancel", booking["management_token"].as_str().unwrap()))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(slots().await, 2);
+
+    // 5. Overrides only drop their own day
+    let following = (next_mon + Duration::days(7)).format("%Y-%m-%d").to_string();
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/cached/overrides", tid), json!({
+        "date": following, "is_unavailable": true, "config": null, "location": null, "host_name": null
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(stats().await["entries"], 1);
+
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/cached/overrides", tid), json!({
+        "date": date, "is_unavailable": true, "config": null, "location": null, "host_name": null
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(slots().await, 0);
+    let res = app.router.clone().oneshot(admin("DELETE", format!("/api/v1/{}/events/cached/overrides/{}", tid, date), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(slots().await, 2);
+
+    // 6. Event updates drop all days of the event
+    let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/events/cached", tid), json!({
+        "config": { "monday": [{"start":"09:00", "end":"12:00"}] }
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(slots().await, 3);
+
+    // 7. Statistics are per tenant and need a login
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/availability/cache-stats", tid)).body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(app.state.availability_cache.stats("other-tenant").entries, 0);
+}
+
+#[test]
+fn test_availability_cache_bound_is_per_tenant() {
+    let cache = AvailabilityCache::default();
+    let start = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+    let slot = SlotDetail {
+        start_time: start.and_hms_opt(9, 0, 0).unwrap().and_utc(),
+        end_time: start.and_hms_opt(10, 0, 0).unwrap().and_utc(),
+        capacity: 1,
+        remaining_seats: 1,
+        location: "Lab".into(),
+        host_name: "H".into(),
+    };
+
+    cache.put("quiet", "event", Audience::Public, start, vec![slot.clone()]);
+    for day in 0..(MAX_ENTRIES_PER_TENANT + 10) as u64 {
+        cache.put("busy", "event", Audience::Public, start + chrono::Days::new(day), Vec::new());
+    }
+
+    let busy = cache.stats("busy");
+    assert_eq!(busy.entries, MAX_ENTRIES_PER_TENANT);
+    assert_eq!(busy.evictions, 10);
+    assert_eq!(cache.stats("quiet").entries, 1);
+    assert_eq!(cache.get("quiet", "event", Audience::Public, start), Some(vec![slot]));
+}
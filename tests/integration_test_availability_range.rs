@@ -0,0 +1,94 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_available_dates_match_daily_slots() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Range Lab", "slug": "range-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let window = json!([{"start": "09:00", "end": "11:00"}]);
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": "range", "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "15", "host_name": "H", "timezone": "Europe/Berlin",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(60)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+        "config": { "monday": window, "wednesday": window, "thursday": window, "friday": window },
+        "access_mode": "OPEN"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let day = |offset: i64| (next_mon + Duration::days(offset)).format("%Y-%m-%d").to_string();
+
+    // 2. Monday fully booked, Tuesday opened and Wednesday closed by overrides
+    for time in ["09:00", "10:00"] {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("POST").uri(format!("/api/v1/{}/events/range/book", tid))
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({"date": day(0), "time": time, "name": "Ada", "email": format!("{}@test.com", &time[..2])}).to_string())).unwrap()
+        ).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+    for (date, is_unavailable, config) in [
+        (day(1), false, json!({"tuesday": [{"start": "13:00", "end": "14:00"}]})),
+        (day(2), true, Value::Null),
+    ] {
+        let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/range/overrides", tid), json!({
+            "date": date, "is_unavailable": is_unavailable, "config": config, "location": null, "host_name": null
+        }))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    // 3. The dates computed in one pass agree with the slots of every single day
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/events/range/dates?start={}&end={}", tid, day(0), day(13)))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let dates: Vec<String> = serde_json::from_value(parse_body(res).await).unwrap();
+    assert_eq!(dates, vec![day(1), day(3), day(4), day(7), day(9), day(10), day(11)]);
+
+    for offset in 0..14 {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("GET").uri(format!("/api/v1/{}/events/range/slots?date={}", tid, day(offset)))
+                .body(Body::empty()).unwrap()
+        ).await.unwrap();
+        let slots = parse_body(res).await;
+        let has_slots = !slots["slots"].as_array().unwrap().is_empty();
+        assert_eq!(has_slots, dates.contains(&day(offset)), "mismatch on {}", day(offset));
+    }
+}
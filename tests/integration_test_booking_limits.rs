@@ -0,0 +1,137 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_booking_limit_per_email_and_series() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Limit Lab", "slug": "limit-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+    let event = |slug: &str, limit: Value, series_key: Value| json!({
+        "slug": slug, "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "15", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 5, "image_url": ".",
+        "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+        "access_mode": "OPEN", "booking_limit": limit, "series_key": series_key
+    });
+
+    // 2. A series limit needs a series key
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), event("wave-a", json!("SERIES"), Value::Null))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), event("wave-a", json!("TWICE"), Value::Null))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    for (slug, limit, series_key) in [("single", "EMAIL", None), ("wave-a", "SERIES", Some("wave")), ("wave-b", "SERIES", Some("wave"))] {
+        let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), event(slug, json!(limit), json!(series_key)))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let book = |slug: &str, time: &str, email: &str| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, slug))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": time, "name": "Ada", "email": email}).to_string())).unwrap()
+    };
+
+    // 3. One active booking per email and event
+    let res = app.router.clone().oneshot(book("single", "09:00", "ada@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let first = parse_body(res).await;
+    let token = first["management_token"].as_str().unwrap();
+
+    let res = app.router.clone().oneshot(book("single", "10:00", " ADA@Test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    let error = parse_body(res).await["error"].as_str().unwrap().to_string();
+    assert!(error.contains("management link"));
+    assert!(!error.contains(token));
+    let (a, b, c) = tokio::join!(
+        app.router.clone().oneshot(book("single", "11:00", "ada@test.com")),
+        app.router.clone().oneshot(book("single", "12:00", "ada@test.com")),
+        app.router.clone().oneshot(book("single", "13:00", "ada@test.com")),
+    );
+    for res in [a, b, c] {
+        assert_eq!(res.unwrap().status(), StatusCode::CONFLICT);
+    }
+
+    let bookings = app.state.booking_repo.list_by_tenant(tid).await.unwrap();
+    assert_eq!(bookings.len(), 1);
+
+    // 4. The existing booking's management link is emailed once to its address
+    for _ in 0..15 {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let pending: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'DUPLICATE_BOOKING' AND status IN ('PENDING', 'PROCESSING')")
+            .fetch_one(&app.pool).await.unwrap();
+        if pending == 0 { break; }
+    }
+    let queued: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'DUPLICATE_BOOKING'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(queued, 1);
+    let sent: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM mail_logs WHERE recipient = 'ada@test.com' AND template_id = 'single - Duplicate Booking' AND status = 'SENT'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(sent, 1);
+
+    // 5. Cancelling frees the limit
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/bookings/manage/{}/cancel", token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(book("single", "10:00", "ada@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 6. Events of a series share the limit
+    let res = app.router.clone().oneshot(book("wave-a", "09:00", "grace@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(book("wave-b", "11:00", "grace@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    let res = app.router.clone().oneshot(book("wave-b", "11:00", "alan@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // A booking that is over no longer counts
+    sqlx::query("UPDATE bookings SET start_time = ?, end_time = ? WHERE customer_email = 'grace@test.com'")
+        .bind(Utc::now() - Duration::days(2)).bind(Utc::now() - Duration::days(2) + Duration::hours(1))
+        .execute(&app.pool).await.unwrap();
+    let res = app.router.clone().oneshot(book("wave-b", "11:00", "grace@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 7. Dropping the limit allows repeat bookings again
+    let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/events/wave-b", tid), json!({"booking_limit": "NONE"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(book("wave-b", "12:00", "grace@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use booking_backend::domain::models::session::EventSession;
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_bulk_session_generation_shift_and_delete() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant and a MANUAL event
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Bulk Lab", "slug": "bulk-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": "manual", "title_en": "Session", "title_de": "Sitzung", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "10", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+        "config": {}, "access_mode": "OPEN", "schedule_type": "MANUAL"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let monday = next_mon.date_naive();
+    let wednesday = monday + Duration::days(2);
+
+    let sessions_uri = format!("/api/v1/{}/events/manual/sessions", tid);
+    let list = || async {
+        let res = app.router.clone().oneshot(admin("GET", sessions_uri.clone(), Value::Null)).await.unwrap();
+        parse_body(res).await.as_array().unwrap().clone()
+    };
+    let pattern = |dry_run: bool| json!({
+        "start_date": monday, "end_date": monday + Duration::days(13),
+        "weekdays": ["Monday", "wed"], "start_times": ["14:00", "09:00"],
+        "duration_min": 60, "max_participants": 3, "location": "Room 1", "host_name": "Dr. Bulk",
+        "dry_run": dry_run
+    });
+
+    // 2. An existing session in the way shows up in the preview and blocks the generation
+    let res = app.router.clone().oneshot(admin("POST", sessions_uri.clone(), json!({
+        "date": monday, "start_time": "09:30", "end_time": "10:00", "max_participants": 1
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let blocker = parse_body(res).await["id"].as_str().unwrap().to_string();
+
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk", sessions_uri), pattern(true))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let preview = parse_body(res).await;
+    assert_eq!(preview["sessions"].as_array().unwrap().len(), 8);
+    assert_eq!(preview["conflicts"].as_array().unwrap().len(), 1);
+    assert_eq!(preview["conflicts"][0]["start_time"], format!("{}T09:00:00Z", monday));
+
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk", sessions_uri), pattern(false))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    assert_eq!(list().await.len(), 1);
+
+    // Sessions of the pattern overlapping each other are reported too
+    let mut crowded = pattern(true);
+    crowded["start_times"] = json!(["11:00", "11:30"]);
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk", sessions_uri), crowded.clone())).await.unwrap();
+    assert_eq!(parse_body(res).await["conflicts"].as_array().unwrap().len(), 8);
+    crowded["dry_run"] = json!(false);
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk", sessions_uri), crowded)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    assert_eq!(list().await.len(), 1);
+
+    // 3. Without it all sessions are created at once
+    let res = app.router.clone().oneshot(admin("DELETE", format!("{}/{}", sessions_uri, blocker), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk", sessions_uri), pattern(false))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let created = parse_body(res).await;
+    assert_eq!(created["dry_run"], false);
+    assert_eq!(created["sessions"][0]["location"], "Room 1");
+    assert_eq!(created["sessions"][0]["host_name"], "Dr. Bulk");
+    assert_eq!(list().await.len(), 8);
+
+    // 4. Booked sessions are skipped when shifting
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/manual/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": monday, "time": "09:00", "name": "Ada", "email": "ada@test.com"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk-shift", sessions_uri), json!({
+        "start_date": monday, "end_date": monday, "shift_minutes": 30
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let shifted = parse_body(res).await;
+    assert_eq!(shifted["sessions"].as_array().unwrap().len(), 1);
+    assert_eq!(shifted["sessions"][0]["start_time"], format!("{}T14:30:00Z", monday));
+    assert_eq!(shifted["skipped"][0]["start_time"], format!("{}T09:00:00Z", monday));
+
+    // Shifting onto a session staying in place is refused
+    let wednesday_morning = list().await.into_iter()
+        .find(|s| s["start_time"] == format!("{}T09:00:00Z", wednesday))
+        .unwrap()["id"].as_str().unwrap().to_string();
+    let shift = |dry_run: bool| admin("POST", format!("{}/bulk-shift", sessions_uri), json!({
+        "session_ids": [wednesday_morning], "shift_minutes": 300, "dry_run": dry_run
+    }));
+    let res = app.router.clone().oneshot(shift(true)).await.unwrap();
+    assert_eq!(parse_body(res).await["conflicts"].as_array().unwrap().len(), 1);
+    let res = app.router.clone().oneshot(shift(false)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    // 5. Bulk deletion keeps the booked session
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk-delete", sessions_uri), json!({
+        "start_date": monday, "end_date": monday + Duration::days(13), "dry_run": true
+    }))).await.unwrap();
+    let preview = parse_body(res).await;
+    assert_eq!((preview["sessions"].as_array().unwrap().len(), preview["skipped"].as_array().unwrap().len()), (7, 1));
+    assert_eq!(list().await.len(), 8);
+
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk-delete", sessions_uri), json!({
+        "start_date": monday, "end_date": monday + Duration::days(13)
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let remaining = list().await;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0]["start_time"], format!("{}T09:00:00Z", monday));
+
+    // 6. Unknown ids and missing selections are rejected
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk-delete", sessions_uri), json!({"session_ids": ["missing"]}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk-delete", sessions_uri), json!({}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    // So are lengths and shifts beyond what a calendar can hold
+    let mut endless = pattern(true);
+    endless["duration_min"] = json!(i64::MAX);
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk", sessions_uri), endless)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/bulk-shift", sessions_uri), json!({
+        "start_date": monday, "end_date": monday, "shift_minutes": i64::MIN + 1
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    // 7. A booking made after the selection still keeps its session
+    let booked = remaining[0]["id"].as_str().unwrap().to_string();
+    assert!(app.state.session_repo.delete_many(std::slice::from_ref(&booked)).await.is_err());
+    let mut moved: EventSession = serde_json::from_value(remaining[0].clone()).unwrap();
+    moved.start_time += Duration::days(1);
+    moved.end_time += Duration::days(1);
+    assert!(app.state.session_repo.update_times(&[moved]).await.is_err());
+    assert_eq!(list().await[0]["start_time"], format!("{}T09:00:00Z", monday));
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use booking_backend::domain::models::booking::{Booking, NewBookingParams};
+use booking_backend::domain::services::availability::{calculate_range, calculate_slots, Audience};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+async fn setup(app: &TestApp, slug: &str, max_participants: i32, config: Value) -> (String, String) {
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Hall", "slug": format!("{}-lab", slug)}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap().to_string();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(&tid, "admin", sec).await;
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "slug": slug, "title_en": "Lecture", "title_de": "Vorlesung", "desc_en": ".", "desc_de": ".",
+                "location": "Hall", "payout": "0", "host_name": "H", "timezone": "UTC",
+                "active_start": Utc::now().to_rfc3339(),
+                "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+                "duration_min": 60, "interval_min": 60, "max_participants": max_participants, "image_url": ".",
+                "config": config,
+                "access_mode": "OPEN"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let event_id = parse_body(res).await["id"].as_str().unwrap().to_string();
+    (tid, event_id)
+}
+
+fn next_monday() -> NaiveDate {
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    (next_mon + Duration::days(7)).date_naive()
+}
+
+fn bookings(tid: &str, event_id: &str, start: DateTime<Utc>, count: usize) -> Vec<Booking> {
+    (0..count).map(|i| Booking::new(NewBookingParams {
+        tenant_id: tid.to_string(),
+        event_id: event_id.to_string(),
+        start,
+        duration_min: 60,
+        name: "Student".into(),
+        email: format!("student{}@test.com", i),
+        note: None,
+        invitee_id: None,
+        location: None,
+        participant_id: None,
+    })).collect()
+}
+
+#[tokio::test]
+async fn test_capacity_beyond_255_participants() {
+    let app = TestApp::new().await;
+    let (tid, event_id) = setup(&app, "lecture", 300, json!({ "monday": [{"start": "09:00", "end": "10:00"}] })).await;
+    let event = app.state.event_repo.find_by_id(&tid, &event_id).await.unwrap().unwrap();
+
+    let date = next_monday();
+    let start = date.and_hms_opt(9, 0, 0).unwrap().and_utc();
+    let slot = start.to_rfc3339();
+
+    let slots_with = |taken: usize| calculate_slots(&event, date, &bookings(&tid, &event_id, start, taken), None, None, Audience::Public);
+    assert_eq!(slots_with(255), vec![slot.clone()]);
+    assert_eq!(slots_with(299), vec![slot.clone()]);
+    assert!(slots_with(300).is_empty());
+
+    let full = bookings(&tid, &event_id, start, 300);
+    assert!(calculate_range(&event, date, date, &full, &[], None, Audience::Public)[0].free_slots().is_empty());
+}
+
+#[tokio::test]
+async fn test_overnight_windows_cross_midnight() {
+    let app = TestApp::new().await;
+    let (tid, _) = setup(&app, "night", 1, json!({
+        "monday": [{"start": "22:00", "end": "02:00"}],
+        "tuesday": [{"start": "18:00", "end": "00:00"}]
+    })).await;
+
+    let monday = next_monday();
+    let tuesday = monday + Duration::days(1);
+    let at = |date: NaiveDate, hour: u32| date.and_hms_opt(hour, 0, 0).unwrap().and_utc().to_rfc3339();
+    let slots = |date: NaiveDate| {
+        Request::builder().method("GET").uri(format!("/api/v1/{}/events/night/slots?date={}", tid, date))
+            .body(Body::empty()).unwrap()
+    };
+
+    // 1. The slots after midnight belong to the day the window starts on
+    let res = app.router.clone().oneshot(slots(monday)).await.unwrap();
+    assert_eq!(parse_body(res).await["slots"], json!([at(monday, 22), at(monday, 23), at(tuesday, 0), at(tuesday, 1)]));
+    let res = app.router.clone().oneshot(slots(tuesday)).await.unwrap();
+    assert_eq!(parse_body(res).await["slots"], json!([at(tuesday, 18), at(tuesday, 19), at(tuesday, 20), at(tuesday, 21), at(tuesday, 22), at(tuesday, 23)]));
+
+    // 2. Booking after midnight takes the slot of the previous evening's window
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/night/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": monday.to_string(), "time": at(tuesday, 1), "name": "Owl", "email": "owl@test.com"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(parse_body(res).await["start_time"], at(tuesday, 1).replace("+00:00", "Z"));
+
+    let res = app.router.clone().oneshot(slots(monday)).await.unwrap();
+    assert_eq!(parse_body(res).await["slots"], json!([at(monday, 22), at(monday, 23), at(tuesday, 0)]));
+
+    // 3. The available dates agree
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/events/night/dates?start={}&end={}", tid, monday, tuesday))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(parse_body(res).await, json!([monday.to_string(), tuesday.to_string()]));
+}
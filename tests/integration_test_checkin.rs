@@ -0,0 +1,132 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_checkin_and_noshow_labelling() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant & Event
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Checkin Lab", "slug": "checkin-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+
+    let tenant = app.state.tenant_repo.find_by_id(tid).await.unwrap().unwrap();
+    let show_id = tenant.show_label_id.expect("Show label not configured");
+    let noshow_id = tenant.noshow_label_id.expect("Noshow label not configured");
+
+    let ev_slug = "lab-session";
+    app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "slug": ev_slug, "title_en": "Lab", "title_de": "Lab", "desc_en": ".", "desc_de": ".",
+                "location": "Room 1", "payout": "10", "host_name": "H", "timezone": "UTC",
+                "active_start": Utc::now().to_rfc3339(),
+                "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+                "duration_min": 60, "interval_min": 60, "max_participants": 5, "image_url": ".",
+                "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+                "access_mode": "OPEN"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let mut bookings = Vec::new();
+    for (name, email) in [("Present", "present@test.com"), ("Absent", "absent@test.com")] {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, ev_slug))
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({"date": date, "time": "10:00", "name": name, "email": email}).to_string())).unwrap()
+        ).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        bookings.push(parse_body(res).await);
+    }
+    let present_id = bookings[0]["id"].as_str().unwrap().to_string();
+    let absent_id = bookings[1]["id"].as_str().unwrap().to_string();
+
+    let jobs = app.state.job_repo.list_jobs(tid).await.unwrap();
+    assert_eq!(jobs.iter().filter(|j| j.job_type == "ATTENDANCE_CHECK").count(), 2);
+
+    // 2. Participant sees their signed code on the management page
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/bookings/manage/{}", bookings[0]["management_token"].as_str().unwrap()))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    let code = parse_body(res).await["checkin_code"].as_str().unwrap().to_string();
+    assert!(code.starts_with(&present_id));
+
+    let checkin = |code: String| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/checkin", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"code": code}).to_string())).unwrap()
+    };
+
+    // 3. Forged codes are rejected
+    let forged = format!("{}.{}", absent_id, code.rsplit_once('.').unwrap().1);
+    let res = app.router.clone().oneshot(checkin(forged)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    // 4. Check-in applies the Show label; scanning again is idempotent
+    let res = app.router.clone().oneshot(checkin(code.clone())).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = parse_body(res).await;
+    assert_eq!(body["already_checked_in"], false);
+    assert_eq!(body["booking"]["label_id"], show_id.as_str());
+    assert!(!body["booking"]["checked_in_at"].is_null());
+
+    let res = app.router.clone().oneshot(checkin(code.clone())).await.unwrap();
+    assert_eq!(parse_body(res).await["already_checked_in"], true);
+
+    // 5. Once the session is over, only the unchecked booking is labelled Noshow
+    let past_start = Utc::now() - Duration::hours(2);
+    let past_end = Utc::now() - Duration::hours(1);
+    for id in [&present_id, &absent_id] {
+        sqlx::query("UPDATE bookings SET start_time = ?, end_time = ? WHERE id = ?")
+            .bind(past_start).bind(past_end).bind(id)
+            .execute(&app.pool).await.unwrap();
+    }
+    sqlx::query("UPDATE jobs SET execute_at = ? WHERE job_type = 'ATTENDANCE_CHECK'")
+        .bind(past_end)
+        .execute(&app.pool).await.unwrap();
+
+    let mut absent_label = None;
+    for _ in 0..15 {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let booking = app.state.booking_repo.find_by_id(tid, &absent_id).await.unwrap().unwrap();
+        if booking.label_id.is_some() {
+            absent_label = booking.label_id;
+            break;
+        }
+    }
+    assert_eq!(absent_label.as_deref(), Some(noshow_id.as_str()));
+
+    let present = app.state.booking_repo.find_by_id(tid, &present_id).await.unwrap().unwrap();
+    assert_eq!(present.label_id.as_deref(), Some(show_id.as_str()));
+}
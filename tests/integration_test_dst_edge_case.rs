@@ -4,7 +4,7 @@ use axum::{
     body::Body,
     http::{header, Request, StatusCode},
 };
-use chrono::{TimeZone, Utc};
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
 use common::TestApp;
 use serde_json::{json, Value};
 use tower::ServiceExt;
@@ -32,7 +32,7 @@ async fn test_dst_spring_forward_gap() {
 
     // 2. Create Event (Open 24h, valid far into future)
     let active_start = Utc::now();
-    let active_end = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+    let active_end = Utc.with_ymd_and_hms(Utc::now().year() + 3, 1, 1, 0, 0, 0).unwrap();
 
     let ev_payload = json!({
         "slug": "dst-event",
@@ -60,7 +60,7 @@ async fn test_dst_spring_forward_gap() {
     ).await.unwrap();
 
     // --- Scenario: Future DST Spring Forward ---
-    // Berlin/CET Spring Forward: March 29, 2026.
+    // Berlin/CET Spring Forward: last Sunday of March (next occurrence).
     // 02:00 Local -> 03:00 Local.
     // 02:00 Local does not exist.
     // Slots are 30min interval.
@@ -68,7 +68,7 @@ async fn test_dst_spring_forward_gap() {
     // 02:00 Local (Skipped).
     // 03:00 Local = 01:00 UTC.
 
-    let date_dst = "2026-03-29";
+    let date_dst = next_spring_forward_berlin().format("%Y-%m-%d").to_string();
 
     let res = app.router.clone().oneshot(
         Request::builder().method("GET")
@@ -89,4 +89,19 @@ async fn test_dst_spring_forward_gap() {
     assert!(has_01_00_utc, "03:00 Local (01:00 UTC) should exist");
 
     assert!(!slots.is_empty());
-}
\ No newline at end of file
+}
+
+fn next_spring_forward_berlin() -> NaiveDate {
+    let today = Utc::now().date_naive();
+    let mut year = today.year();
+    loop {
+        let mut candidate = NaiveDate::from_ymd_opt(year, 3, 31).unwrap();
+        while candidate.weekday() != Weekday::Sun {
+            candidate -= Duration::days(1);
+        }
+        if candidate > today {
+            return candidate;
+        }
+        year += 1;
+    }
+}
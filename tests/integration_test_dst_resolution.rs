@@ -0,0 +1,148 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use booking_backend::domain::services::local_time::{self, Overlap};
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, TimeZone, Utc};
+use chrono_tz::{America::Sao_Paulo, Asia::Beirut};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+fn utc(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+}
+
+#[test]
+fn test_resolution_policy_for_skipped_and_repeated_times() {
+    // Midnight of 2018-11-04 was skipped in Sao Paulo (00:00 -03 -> 01:00 -02)
+    let gap_day = NaiveDate::from_ymd_opt(2018, 11, 4).unwrap();
+    assert_eq!(local_time::day_start(&Sao_Paulo, gap_day), utc("2018-11-04T03:00:00Z"));
+    assert_eq!(local_time::resolve(&Sao_Paulo, gap_day.and_hms_opt(0, 30, 0).unwrap(), Overlap::Earliest), utc("2018-11-04T03:30:00Z"));
+    assert_eq!(local_time::resolve_all(&Sao_Paulo, gap_day.and_hms_opt(0, 30, 0).unwrap()), vec![utc("2018-11-04T03:30:00Z")]);
+
+    // The last hour of 2019-02-16 was repeated (00:00 -02 -> 23:00 -03)
+    let overlap_day = NaiveDate::from_ymd_opt(2019, 2, 16).unwrap();
+    let repeated = overlap_day.and_hms_opt(23, 30, 0).unwrap();
+    assert_eq!(local_time::resolve(&Sao_Paulo, repeated, Overlap::Earliest), utc("2019-02-17T01:30:00Z"));
+    assert_eq!(local_time::resolve(&Sao_Paulo, repeated, Overlap::Latest), utc("2019-02-17T02:30:00Z"));
+    assert_eq!(local_time::resolve_all(&Sao_Paulo, repeated), vec![utc("2019-02-17T01:30:00Z"), utc("2019-02-17T02:30:00Z")]);
+    assert_eq!(local_time::day_end(&Sao_Paulo, overlap_day), utc("2019-02-17T02:59:59Z"));
+    assert_eq!(local_time::day_start(&Sao_Paulo, overlap_day + Duration::days(1)), utc("2019-02-17T03:00:00Z"));
+
+    // Beirut skips midnight in spring
+    let beirut_gap = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+    assert_eq!(local_time::day_start(&Beirut, beirut_gap), utc("2024-03-30T22:00:00Z"));
+    assert_eq!(local_time::day_end(&Beirut, beirut_gap - Duration::days(1)), utc("2024-03-30T21:59:59Z"));
+}
+
+/// First day after tomorrow on which the local time `hms` is skipped or repeated in Beirut.
+fn next_beirut_transition(hms: (u32, u32, u32), repeated: bool) -> NaiveDate {
+    let mut date = Utc::now().date_naive() + Duration::days(2);
+    loop {
+        let local = date.and_hms_opt(hms.0, hms.1, hms.2).unwrap();
+        match Beirut.from_local_datetime(&local) {
+            LocalResult::None if !repeated => return date,
+            LocalResult::Ambiguous(_, _) if repeated => return date,
+            _ => date += Duration::days(1),
+        }
+        assert!(date < Utc::now().date_naive() + Duration::days(400), "no transition found");
+    }
+}
+
+#[tokio::test]
+async fn test_availability_and_booking_across_beirut_transitions() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Beirut Lab", "slug": "beirut-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let window = json!([{"start": "00:00", "end": "02:00"}, {"start": "22:00", "end": "23:59"}]);
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "slug": "dst", "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+                "location": "Lab", "payout": "0", "host_name": "H", "timezone": "Asia/Beirut",
+                "active_start": Utc::now().to_rfc3339(),
+                "active_end": (Utc::now() + Duration::days(420)).to_rfc3339(),
+                "duration_min": 30, "interval_min": 30, "max_participants": 1, "image_url": ".",
+                "config": {
+                    "monday": window, "tuesday": window, "wednesday": window, "thursday": window,
+                    "friday": window, "saturday": window, "sunday": window
+                },
+                "access_mode": "OPEN"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let slots = |date: NaiveDate| {
+        Request::builder().method("GET").uri(format!("/api/v1/{}/events/dst/slots?date={}", tid, date))
+            .body(Body::empty()).unwrap()
+    };
+    let book = |date: NaiveDate, time: &str, email: &str| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/dst/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date.to_string(), "time": time, "name": "Rami", "email": email}).to_string())).unwrap()
+    };
+    let instants = |body: &Value| -> Vec<DateTime<Utc>> {
+        body["slots"].as_array().unwrap().iter().map(|s| utc(s.as_str().unwrap())).collect()
+    };
+
+    // 2. Skipped midnight: 00:00 and 00:30 shift forward onto 01:00 and 01:30 without repeating them
+    let gap_day = next_beirut_transition((0, 0, 0), false);
+    let res = app.router.clone().oneshot(slots(gap_day)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let gap_slots = instants(&parse_body(res).await);
+    let one_am = local_time::day_start(&Beirut, gap_day);
+    assert_eq!(gap_slots[..3], [one_am, one_am + Duration::minutes(30), one_am + Duration::hours(21)]);
+
+    let res = app.router.clone().oneshot(book(gap_day, "00:00", "gap@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(utc(parse_body(res).await["start_time"].as_str().unwrap()), one_am);
+
+    // 3. Repeated evening: both occurrences of 23:00 and 23:30 are offered
+    let overlap_day = next_beirut_transition((23, 59, 59), true);
+    let res = app.router.clone().oneshot(slots(overlap_day)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let overlap_slots = instants(&parse_body(res).await);
+    let first_eleven = local_time::resolve(&Beirut, overlap_day.and_hms_opt(23, 0, 0).unwrap(), Overlap::Earliest);
+    let second_eleven = local_time::resolve(&Beirut, overlap_day.and_hms_opt(23, 0, 0).unwrap(), Overlap::Latest);
+    assert_eq!(second_eleven - first_eleven, Duration::hours(1));
+    assert!(overlap_slots.contains(&first_eleven) && overlap_slots.contains(&second_eleven));
+    assert_eq!(overlap_slots.iter().filter(|s| **s >= first_eleven).count(), 4);
+
+    // A local time picks the earlier occurrence, the later one is booked by its timestamp
+    let res = app.router.clone().oneshot(book(overlap_day, "23:00", "first@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(utc(parse_body(res).await["start_time"].as_str().unwrap()), first_eleven);
+    let res = app.router.clone().oneshot(book(overlap_day, &second_eleven.to_rfc3339(), "second@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 4. Listing dates across both transitions works
+    for day in [gap_day, overlap_day] {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("GET").uri(format!("/api/v1/{}/events/dst/dates?start={}&end={}", tid, day - Duration::days(1), day + Duration::days(1)))
+                .body(Body::empty()).unwrap()
+        ).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(parse_body(res).await.as_array().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,233 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use booking_backend::domain::services::calendar::generate_ics;
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+/// Changes queued for each booking, in the order they were made.
+async fn queued_changes(app: &TestApp, booking_id: &str) -> Vec<Value> {
+    let payloads: Vec<String> = sqlx::query_scalar("SELECT payload FROM jobs WHERE job_type = 'EVENT_UPDATED' ORDER BY created_at")
+        .fetch_all(&app.pool).await.unwrap();
+    payloads.iter()
+        .map(|p| serde_json::from_str::<Value>(p).unwrap())
+        .filter(|p| p["booking_id"] == booking_id)
+        .map(|p| p["data"]["changes"].clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_participants_are_told_about_changed_details() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant and an event with an in-person and an online window
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Moving Lab", "slug": "moving-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": "study", "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "10", "host_name": "Dr. Lab", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 3, "image_url": ".",
+        "config": { "monday": [
+            {"start": "09:00", "end": "10:00"},
+            {"start": "14:00", "end": "15:00", "location": "Online"}
+        ] },
+        "access_mode": "OPEN"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let monday = next_mon.date_naive();
+
+    let book = |slug: &str, time: &str, email: &str| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, slug))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": monday, "time": time, "name": "P", "email": email}).to_string())).unwrap()
+    };
+    let res = app.router.clone().oneshot(book("study", "09:00", "ada@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let ada = parse_body(res).await["id"].as_str().unwrap().to_string();
+    let res = app.router.clone().oneshot(book("study", "14:00", "bob@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let bob = parse_body(res).await["id"].as_str().unwrap().to_string();
+
+    // 2. Moving the event concerns only the booking at its location
+    let event_uri = format!("/api/v1/{}/events/study", tid);
+    let res = app.router.clone().oneshot(admin("PUT", event_uri.clone(), json!({"location": "Annex", "payout": "12"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(queued_changes(&app, &ada).await, vec![json!([{"field": "Location", "old": "Lab", "new": "Annex"}])]);
+    assert!(queued_changes(&app, &bob).await.is_empty());
+
+    let res = app.router.clone().oneshot(admin("PUT", event_uri.clone(), json!({"timezone": "Europe/Berlin", "title_de": "Neue Studie"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(queued_changes(&app, &bob).await, vec![json!([
+        {"field": "Title (German)", "old": "Studie", "new": "Neue Studie"},
+        {"field": "Time zone", "old": "UTC", "new": "Europe/Berlin"}
+    ])]);
+
+    // Settings participants never saw are no news, and bookings keep their length
+    let res = app.router.clone().oneshot(admin("PUT", event_uri.clone(), json!({"payout": "15", "max_participants": 4, "duration_min": 90}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(queued_changes(&app, &ada).await.len(), 2);
+    let booking = app.state.booking_repo.find_by_id(tid, &ada).await.unwrap().unwrap();
+    assert_eq!(booking.end_time - booking.start_time, Duration::minutes(60));
+    assert_eq!(booking.revision, 2);
+
+    // The calendar entry attached to the notices updates the one sent before
+    let event = app.state.event_repo.find_by_slug(tid, "study").await.unwrap().unwrap();
+    let ics = generate_ics(&event, &booking);
+    assert!(ics.contains("METHOD:REQUEST"));
+    assert!(ics.contains(&format!("UID:{}", ada)));
+    assert!(ics.contains("SEQUENCE:2"));
+
+    // 3. An override moving the day takes the booking along
+    let res = app.router.clone().oneshot(admin("POST", format!("{}/overrides", event_uri), json!({
+        "date": monday, "is_unavailable": false, "config": null, "location": "Hall", "host_name": "Dr. Guest"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let booking = app.state.booking_repo.find_by_id(tid, &ada).await.unwrap().unwrap();
+    assert_eq!(booking.location.as_deref(), Some("Hall"));
+    assert_eq!(queued_changes(&app, &ada).await[2], json!([
+        {"field": "Location", "old": "Annex", "new": "Hall"},
+        {"field": "Host", "old": "Dr. Lab", "new": "Dr. Guest"}
+    ]));
+    assert_eq!(queued_changes(&app, &bob).await.len(), 1);
+
+    let res = app.router.clone().oneshot(admin("DELETE", format!("{}/overrides/{}", event_uri, monday), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let booking = app.state.booking_repo.find_by_id(tid, &ada).await.unwrap().unwrap();
+    assert!(booking.location.is_none());
+    assert_eq!(queued_changes(&app, &ada).await.len(), 4);
+    assert_eq!(booking.revision, 4);
+
+    // 4. So does a session changing its room
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": "manual", "title_en": "Session", "title_de": "Sitzung", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "10", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+        "config": {}, "access_mode": "OPEN", "schedule_type": "MANUAL"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let sessions_uri = format!("/api/v1/{}/events/manual/sessions", tid);
+    let res = app.router.clone().oneshot(admin("POST", sessions_uri.clone(), json!({
+        "date": monday, "start_time": "11:00", "end_time": "12:00", "max_participants": 2, "location": "Room 1"
+    }))).await.unwrap();
+    let session_id = parse_body(res).await["id"].as_str().unwrap().to_string();
+    let res = app.router.clone().oneshot(book("manual", "11:00", "cleo@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let cleo = parse_body(res).await["id"].as_str().unwrap().to_string();
+
+    let res = app.router.clone().oneshot(admin("PUT", format!("{}/{}", sessions_uri, session_id), json!({"location": "Room 2"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let booking = app.state.booking_repo.find_by_id(tid, &cleo).await.unwrap().unwrap();
+    assert_eq!(booking.location.as_deref(), Some("Room 2"));
+    assert_eq!(queued_changes(&app, &cleo).await, vec![json!([{"field": "Location", "old": "Room 1", "new": "Room 2"}])]);
+
+    // 5. The notices go out with the event's update template
+    for _ in 0..15 {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let pending: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'EVENT_UPDATED' AND status IN ('PENDING', 'PROCESSING')")
+            .fetch_one(&app.pool).await.unwrap();
+        if pending == 0 { break; }
+    }
+    let sent: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM mail_logs WHERE template_id = 'study - Event Updated' AND status = 'SENT'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(sent, 5);
+    let sent: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM mail_logs WHERE template_id = 'manual - Event Updated' AND status = 'SENT'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(sent, 1);
+}
+
+#[tokio::test]
+async fn test_overrides_reach_bookings_after_midnight() {
+    let app = TestApp::new().await;
+
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Night Lab", "slug": "night-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": "night", "title_en": "Night", "title_de": "Nacht", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "10", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+        "config": { "monday": [{"start": "22:00", "end": "02:00"}], "tuesday": [{"start": "03:00", "end": "05:00"}] },
+        "access_mode": "OPEN"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let monday = next_mon.date_naive();
+    let tuesday = monday + Duration::days(1);
+    let at = |hour: u32| tuesday.and_hms_opt(hour, 0, 0).unwrap().and_utc().to_rfc3339();
+
+    let book = |date: chrono::NaiveDate, time: String, email: &str| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/night/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": time, "name": "Owl", "email": email}).to_string())).unwrap()
+    };
+    let res = app.router.clone().oneshot(book(monday, at(1), "owl@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let owl = parse_body(res).await["id"].as_str().unwrap().to_string();
+    let res = app.router.clone().oneshot(book(tuesday, at(3), "lark@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let lark = parse_body(res).await["id"].as_str().unwrap().to_string();
+
+    // Moving Monday concerns its window after midnight, not Tuesday's own
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/night/overrides", tid), json!({
+        "date": monday, "is_unavailable": false, "config": null, "location": "Hall", "host_name": null
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let booking = app.state.booking_repo.find_by_id(tid, &owl).await.unwrap().unwrap();
+    assert_eq!(booking.location.as_deref(), Some("Hall"));
+    assert_eq!(queued_changes(&app, &owl).await, vec![json!([{"field": "Location", "old": "Lab", "new": "Hall"}])]);
+    assert!(queued_changes(&app, &lark).await.is_empty());
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_hybrid_reserved_capacity() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Hybrid Lab", "slug": "hybrid-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+    let event = |reserved: i32| json!({
+        "slug": "hybrid", "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "15", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 3, "image_url": ".",
+        "config": { "monday": [{"start":"09:00", "end":"11:00"}] },
+        "access_mode": "HYBRID", "reserved_capacity": reserved, "reserved_release_hours": 48
+    });
+
+    // 2. Validation
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), event(-1))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), event(4))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), event(2))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/events/hybrid", tid), json!({"access_mode": "SECRET"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/events/hybrid", tid), json!({"max_participants": 1}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/hybrid/invitees", tid), json!({"max_uses": 5}))).await.unwrap();
+    let token = parse_body(res).await["token"].as_str().unwrap().to_string();
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let book = |email: &str, token: Option<&str>| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/hybrid/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": "09:00", "name": "Ada", "email": email, "token": token}).to_string())).unwrap()
+    };
+    let slots = |token: Option<&str>| {
+        let query = token.map(|t| format!("&token={}", t)).unwrap_or_default();
+        Request::builder().method("GET").uri(format!("/api/v1/{}/events/hybrid/slots?date={}{}", tid, date, query))
+            .body(Body::empty()).unwrap()
+    };
+    let has_nine = |body: &Value| body["slots"].as_array().unwrap().iter().any(|s| s.as_str().unwrap().contains("T09:00:00"));
+
+    // 3. The public can open the event and takes the unreserved seat
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/events/hybrid", tid)).body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(book("public1@test.com", None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 4. The remaining seats are reserved for token holders
+    let res = app.router.clone().oneshot(slots(None)).await.unwrap();
+    assert!(!has_nine(&parse_body(res).await));
+    let res = app.router.clone().oneshot(book("public2@test.com", None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let res = app.router.clone().oneshot(slots(Some("bogus"))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(slots(Some(&token))).await.unwrap();
+    assert!(has_nine(&parse_body(res).await));
+    let res = app.router.clone().oneshot(book("invited1@test.com", Some(&token))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let booking = parse_body(res).await;
+    assert!(booking["invitee_id"].is_string());
+
+    let res = app.router.clone().oneshot(slots(None)).await.unwrap();
+    assert!(!has_nine(&parse_body(res).await));
+
+    // 5. After the release time unclaimed reserved seats open to the public
+    let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/events/hybrid", tid), json!({"reserved_release_hours": 24 * 30}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(slots(None)).await.unwrap();
+    assert!(has_nine(&parse_body(res).await));
+    let res = app.router.clone().oneshot(book("public2@test.com", None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 6. Token holders cannot exceed the total capacity
+    let res = app.router.clone().oneshot(slots(Some(&token))).await.unwrap();
+    assert!(!has_nine(&parse_body(res).await));
+    let res = app.router.clone().oneshot(book("invited2@test.com", Some(&token))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_idempotent_booking_cancel_and_reschedule() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant & Event
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Idempotency Lab", "slug": "idempotency-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let ev_slug = "study";
+    app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "slug": ev_slug, "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+                "location": "Lab", "payout": "15", "host_name": "H", "timezone": "UTC",
+                "active_start": Utc::now().to_rfc3339(),
+                "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+                "duration_min": 60, "interval_min": 60, "max_participants": 5, "image_url": ".",
+                "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+                "access_mode": "OPEN"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let post = |uri: String, key: Option<&str>, body: Value| {
+        let mut builder = Request::builder().method("POST").uri(uri)
+            .header("Content-Type", "application/json");
+        if let Some(key) = key {
+            builder = builder.header("Idempotency-Key", key);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    };
+    let book_uri = format!("/api/v1/{}/events/{}/book", tid, ev_slug);
+    let booking = json!({"date": date, "time": "09:00", "name": "Ada", "email": "ada@test.com"});
+
+    // 2. A double submit creates one booking and replays the first response
+    let first = app.router.clone().oneshot(post(book_uri.clone(), Some("book-1"), booking.clone())).await.unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+    assert!(first.headers().get("Idempotent-Replayed").is_none());
+    let first = parse_body(first).await;
+
+    let second = app.router.clone().oneshot(post(book_uri.clone(), Some("book-1"), booking.clone())).await.unwrap();
+    assert_eq!(second.status(), StatusCode::OK);
+    assert_eq!(second.headers().get("Idempotent-Replayed").unwrap(), "true");
+    let second = parse_body(second).await;
+    assert_eq!(first["id"], second["id"]);
+
+    let bookings = app.state.booking_repo.list_by_tenant(tid).await.unwrap();
+    assert_eq!(bookings.len(), 1);
+
+    // 3. Reusing the key for a different request is rejected
+    let other = json!({"date": date, "time": "10:00", "name": "Ada", "email": "ada@test.com"});
+    let res = app.router.clone().oneshot(post(book_uri.clone(), Some("book-1"), other.clone())).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    assert_eq!(app.state.booking_repo.list_by_tenant(tid).await.unwrap().len(), 1);
+
+    // 4. Without a key every request is processed
+    for _ in 0..2 {
+        app.router.clone().oneshot(post(book_uri.clone(), None, other.clone())).await.unwrap();
+    }
+    assert_eq!(app.state.booking_repo.list_by_tenant(tid).await.unwrap().len(), 3);
+
+    // 5. Reschedule replays return the original result
+    let token = first["management_token"].as_str().unwrap();
+    let reschedule_uri = format!("/api/v1/bookings/manage/{}/reschedule", token);
+    let move_to = json!({"date": date, "time": "12:00"});
+    let res = app.router.clone().oneshot(post(reschedule_uri.clone(), Some("move-1"), move_to.clone())).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let moved = parse_body(res).await;
+
+    let res = app.router.clone().oneshot(post(reschedule_uri.clone(), Some("move-1"), move_to.clone())).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get("Idempotent-Replayed").unwrap(), "true");
+    assert_eq!(parse_body(res).await["start_time"], moved["start_time"]);
+
+    let res = app.router.clone().oneshot(post(reschedule_uri, Some("move-1"), json!({"date": date, "time": "13:00"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    // 6. Cancel replays, and the same key on another endpoint is independent
+    let cancel_uri = format!("/api/v1/bookings/manage/{}/cancel", token);
+    let res = app.router.clone().oneshot(post(cancel_uri.clone(), Some("move-1"), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(parse_body(res).await["status"], "CANCELLED");
+
+    let res = app.router.clone().oneshot(post(cancel_uri, Some("move-1"), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get("Idempotent-Replayed").unwrap(), "true");
+
+    // 7. Expired and abandoned keys no longer bind the request before they are purged
+    sqlx::query("UPDATE idempotency_keys SET created_at = ? WHERE idempotency_key = 'book-1'")
+        .bind(Utc::now() - Duration::hours(25))
+        .execute(&app.pool).await.unwrap();
+    let later = json!({"date": date, "time": "14:00", "name": "Ada", "email": "ada@test.com"});
+    let res = app.router.clone().oneshot(post(book_uri.clone(), Some("book-1"), later)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers().get("Idempotent-Replayed").is_none());
+
+    sqlx::query("INSERT INTO idempotency_keys (scope, idempotency_key, request_hash, created_at) VALUES (?, 'crashed', 'sha256:lost', ?)")
+        .bind(format!("POST {}", book_uri)).bind(Utc::now() - Duration::minutes(10))
+        .execute(&app.pool).await.unwrap();
+    let retry = json!({"date": date, "time": "15:00", "name": "Ada", "email": "ada@test.com"});
+    let res = app.router.clone().oneshot(post(book_uri.clone(), Some("crashed"), retry)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(app.state.booking_repo.list_by_tenant(tid).await.unwrap().len(), 5);
+
+    // 8. Expired keys are purged by the retention run
+    sqlx::query("UPDATE idempotency_keys SET created_at = ?")
+        .bind(Utc::now() - Duration::hours(25))
+        .execute(&app.pool).await.unwrap();
+    booking_backend::background::run_retention(&app.state).await;
+    let (remaining,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM idempotency_keys").fetch_one(&app.pool).await.unwrap();
+    assert_eq!(remaining, 0);
+
+    // 9. Invalid keys are rejected
+    let res = app.router.clone().oneshot(post(book_uri, Some(&"x".repeat(300)), booking)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
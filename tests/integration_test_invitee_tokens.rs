@@ -0,0 +1,131 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_invitee_token_quota_expiry_and_events() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Wave Lab", "slug": "wave-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    for slug in ["study-a", "study-b", "study-c"] {
+        let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+            "slug": slug, "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+            "location": "Lab", "payout": "15", "host_name": "H", "timezone": "UTC",
+            "active_start": Utc::now().to_rfc3339(),
+            "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+            "duration_min": 60, "interval_min": 60, "max_participants": 5, "image_url": ".",
+            "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+            "access_mode": "RESTRICTED"
+        }))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let book = |slug: &str, time: &str, email: &str, token: &str| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, slug))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": time, "name": "Ada", "email": email, "token": token}).to_string())).unwrap()
+    };
+
+    // 2. Quotas must allow at least one booking and unknown events are rejected
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/study-a/invitees", tid), json!({"max_uses": 0}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/study-a/invitees", tid), json!({"event_slugs": ["missing"]}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    // 3. A token with two uses books twice, a cancellation frees a use
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/study-a/invitees", tid), json!({"max_uses": 2}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let invitee = parse_body(res).await;
+    let token = invitee["token"].as_str().unwrap();
+    assert_eq!(invitee["max_uses"], 2);
+
+    let res = app.router.clone().oneshot(book("study-a", "09:00", "one@test.com", token)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let first = parse_body(res).await;
+    let res = app.router.clone().oneshot(book("study-a", "10:00", "two@test.com", token)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(book("study-a", "11:00", "three@test.com", token)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/bookings/manage/{}/cancel", first["management_token"].as_str().unwrap()))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(book("study-a", "11:00", "three@test.com", token)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 4. Raising the quota reactivates a used token
+    let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/invitees/{}", tid, invitee["id"].as_str().unwrap()), json!({"max_uses": 3}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let updated = parse_body(res).await;
+    assert_eq!(updated["status"], "ACTIVE");
+    assert_eq!(updated["use_count"], 2);
+
+    // 5. Expired tokens are rejected
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/study-a/invitees", tid), json!({
+        "expires_at": (Utc::now() + Duration::hours(1)).to_rfc3339()
+    }))).await.unwrap();
+    let expiring = parse_body(res).await;
+    sqlx::query("UPDATE invitees SET expires_at = ? WHERE id = ?")
+        .bind(Utc::now() - Duration::minutes(1)).bind(expiring["id"].as_str().unwrap())
+        .execute(&app.pool).await.unwrap();
+    let res = app.router.clone().oneshot(book("study-a", "12:00", "late@test.com", expiring["token"].as_str().unwrap())).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    // 6. Tenant-wide tokens cover exactly the listed events
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/invitees", tid), json!({"email": "wave@test.com"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/invitees", tid), json!({
+        "email": "wave@test.com", "max_uses": 2, "event_slugs": ["study-a", "study-b"]
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let wave = parse_body(res).await;
+    let wave_token = wave["token"].as_str().unwrap();
+
+    let res = app.router.clone().oneshot(book("study-c", "09:00", "wave@test.com", wave_token)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(book("study-b", "09:00", "wave@test.com", wave_token)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(book("study-a", "13:00", "wave@test.com", wave_token)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/events/study-b/invitees", tid), Value::Null)).await.unwrap();
+    let listed = parse_body(res).await;
+    assert!(listed.as_array().unwrap().iter().any(|i| i["token"] == wave_token));
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_repeated_noshows_block_self_service_booking() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant & Event
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Noshow Lab", "slug": "noshow-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let res = app.router.clone().oneshot(admin("PUT", "/api/v1/tenants".to_string(), json!({
+        "noshow_block_threshold": 2, "noshow_window_months": 12, "noshow_block_months": 6
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(parse_body(res).await["noshow_block_threshold"], 2);
+
+    let ev_slug = "lab";
+    app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": ev_slug, "title_en": "Lab", "title_de": "Lab", "desc_en": ".", "desc_de": ".",
+        "location": "Room 1", "payout": "10", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 5, "image_url": ".",
+        "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+        "access_mode": "OPEN"
+    }))).await.unwrap();
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let book = |time: &str| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, ev_slug))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": time, "name": "Flaky", "email": "flaky@test.com"}).to_string())).unwrap()
+    };
+
+    // 2. Two past sessions labelled as no-show
+    let tenant = app.state.tenant_repo.find_by_id(tid).await.unwrap().unwrap();
+    let noshow_id = tenant.noshow_label_id.clone().unwrap();
+    for (i, time) in ["09:00", "10:00"].iter().enumerate() {
+        let res = app.router.clone().oneshot(book(time)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let id = parse_body(res).await["id"].as_str().unwrap().to_string();
+
+        let past = Utc::now() - Duration::days(30 * (i as i64 + 1));
+        sqlx::query("UPDATE bookings SET start_time = ?, end_time = ?, label_id = ? WHERE id = ?")
+            .bind(past).bind(past + Duration::hours(1)).bind(&noshow_id).bind(&id)
+            .execute(&app.pool).await.unwrap();
+    }
+
+    // 3. Further self-service bookings are rejected (email matching is case-insensitive)
+    let res = app.router.clone().oneshot(book("11:00")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    assert!(parse_body(res).await["error"].as_str().unwrap().contains("blocked until"));
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, ev_slug))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": "12:00", "name": "Flaky", "email": "FLAKY@test.com"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    // Concurrent attempts do not slip past the check either
+    let (a, b, c) = tokio::join!(
+        app.router.clone().oneshot(book("13:00")),
+        app.router.clone().oneshot(book("14:00")),
+        app.router.clone().oneshot(book("15:00")),
+    );
+    for res in [a, b, c] {
+        assert_eq!(res.unwrap().status(), StatusCode::FORBIDDEN);
+    }
+
+    // 4. The participant is told about the block once
+    for _ in 0..15 {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let pending: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'BOOKING_BLOCKED' AND status IN ('PENDING', 'PROCESSING')")
+            .fetch_one(&app.pool).await.unwrap();
+        if pending == 0 { break; }
+    }
+    let sent: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM mail_logs WHERE recipient = 'flaky@test.com' AND template_id = 'lab - Booking Blocked' AND status = 'SENT'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(sent, 1);
+    let queued: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'BOOKING_BLOCKED'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(queued, 1);
+
+    // 5. Staff may still book the participant when overriding the block explicitly, without another notice
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/{}/bookings", tid, ev_slug),
+        json!({"date": date, "time": "12:00", "name": "Flaky", "email": "flaky@test.com"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/{}/bookings", tid, ev_slug),
+        json!({"date": date, "time": "12:00", "name": "Flaky", "email": "flaky@test.com", "overrides": {"ignore_block": true}}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let audited = app.state.audit_repo.list(tid).await.unwrap().into_iter()
+        .filter(|l| l.action == "BOOKING_ON_BEHALF")
+        .collect::<Vec<_>>();
+    assert_eq!(audited.len(), 1);
+    assert_eq!(audited[0].details.0["overrides"]["ignore_block"], true);
+    let queued: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'BOOKING_BLOCKED'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(queued, 1);
+
+    // 6. Admins see the block and can exempt the participant
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/participants", tid), Value::Null)).await.unwrap();
+    let participants = parse_body(res).await;
+    let flaky = &participants[0];
+    assert_eq!(flaky["stats"]["no_shows"], 2);
+    assert!(!flaky["stats"]["blocked_until"].is_null());
+
+    let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/participants/{}", tid, flaky["id"].as_str().unwrap()),
+        json!({"noshow_exempt": true}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.router.clone().oneshot(book("11:00")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use booking_backend::domain::models::booking::Booking;
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_participant_profiles_dedup_and_merge() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant & Event
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Participant Lab", "slug": "participant-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let ev_slug = "study";
+    app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": ev_slug, "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "15", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 5, "image_url": ".",
+        "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+        "access_mode": "OPEN"
+    }))).await.unwrap();
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let book = |time: &str, email: &str| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, ev_slug))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": time, "name": "Ada", "email": email}).to_string())).unwrap()
+    };
+
+    // 2. Same email in different casing resolves to one participant
+    let mut booking_ids = Vec::new();
+    for (time, email) in [("09:00", "ada@test.com"), ("10:00", " ADA@Test.com "), ("11:00", "ada.l@other.com")] {
+        let res = app.router.clone().oneshot(book(time, email)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        booking_ids.push(parse_body(res).await["id"].as_str().unwrap().to_string());
+    }
+
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/participants?search=ada", tid), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let list = parse_body(res).await;
+    assert_eq!(list.as_array().unwrap().len(), 2);
+
+    let main = list.as_array().unwrap().iter().find(|p| p["email"] == "ada@test.com").unwrap();
+    let dup = list.as_array().unwrap().iter().find(|p| p["email"] == "ada.l@other.com").unwrap();
+    let main_id = main["id"].as_str().unwrap().to_string();
+    let dup_id = dup["id"].as_str().unwrap().to_string();
+    assert_eq!(main["stats"]["bookings"], 2);
+    assert_eq!(main["stats"]["total_payout"], 30);
+
+    // 3. Attendance feeds into the stats
+    let tenant = app.state.tenant_repo.find_by_id(tid).await.unwrap().unwrap();
+    app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/bookings/{}", tid, booking_ids[1]),
+        json!({"label_id": tenant.noshow_label_id.clone().unwrap()}))).await.unwrap();
+    app.state.booking_repo.check_in(tid, &booking_ids[0], Utc::now(), tenant.show_label_id.as_deref()).await.unwrap();
+
+    // 4. Annotate both profiles
+    for (id, notes) in [(&main_id, "Prefers mornings"), (&dup_id, "Left-handed")] {
+        let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/participants/{}", tid, id), json!({"notes": notes}))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    // 5. Merging into itself is rejected
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/participants/{}/merge", tid, main_id),
+        json!({"source_ids": [main_id]}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    // 6. Merge the duplicate into the main profile
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/participants/{}/merge", tid, main_id),
+        json!({"source_ids": [dup_id]}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let merged = parse_body(res).await;
+    assert_eq!(merged["stats"]["bookings"], 3);
+    assert_eq!(merged["stats"]["attended"], 1);
+    assert_eq!(merged["stats"]["no_shows"], 1);
+    assert_eq!(merged["emails"].as_array().unwrap().len(), 2);
+    assert_eq!(merged["notes"], "Prefers mornings\n\nLeft-handed");
+
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/participants/{}", tid, dup_id), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    // 7. Later bookings with the merged email resolve to the survivor
+    let res = app.router.clone().oneshot(book("12:00", "Ada.L@other.com")).await.unwrap();
+    let booking = parse_body(res).await;
+    assert_eq!(booking["participant_id"], main_id.as_str());
+
+    // 8. A booking failing inside its transaction registers nobody
+    let mut booking: Booking = serde_json::from_value(booking).unwrap();
+    booking.id = uuid::Uuid::new_v4().to_string();
+    booking.customer_email = "grace@test.com".to_string();
+    booking.participant_id = None;
+    let res = app.state.booking_repo.create_with_token(&booking, Some("no-such-token".to_string()), Default::default(), None).await;
+    assert!(res.is_err());
+    assert!(app.state.participant_repo.find_by_email(tid, "grace@test.com").await.unwrap().is_none());
+}
@@ -0,0 +1,225 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_payout_ledger_hierarchy_reports_and_settlement() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Payout Corp", "slug": "payout-corp"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+
+    // 2. Event with a free-text payout
+    let ev_slug = "study";
+    app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "slug": ev_slug, "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+                "location": "Lab", "payout": "20€", "host_name": "H", "timezone": "UTC",
+                "active_start": chrono::Utc::now().to_rfc3339(),
+                "active_end": (chrono::Utc::now() + chrono::Duration::days(30)).to_rfc3339(),
+                "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+                "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+                "access_mode": "OPEN"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+
+    let mut next_mon = chrono::Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += chrono::Duration::days(1); }
+    next_mon += chrono::Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let mut booking_ids = Vec::new();
+    for (time, name, email) in [("10:00", "Alice", "alice@test.com"), ("11:00", "Bob", "bob@test.com"), ("12:00", "Alice", "ALICE@test.com")] {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, ev_slug))
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({"date": date, "time": time, "name": name, "email": email}).to_string())).unwrap()
+        ).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        booking_ids.push(parse_body(res).await["id"].as_str().unwrap().to_string());
+    }
+
+    // 3. Bob gets the default "Show" label (15), Alice's second booking an explicit override (40)
+    let labels = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/labels", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    let labels = parse_body(labels).await;
+    let show_id = labels.as_array().unwrap().iter()
+        .find(|l| l["name"] == "Show").unwrap()["id"].as_str().unwrap().to_string();
+
+    for (booking_id, payload) in [(&booking_ids[1], json!({"label_id": show_id})), (&booking_ids[2], json!({"payout": 40}))] {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("PUT").uri(format!("/api/v1/{}/bookings/{}", tid, booking_id))
+                .header(header::COOKIE, format!("access_token={}", auth.access_token))
+                .header("X-CSRF-Token", &auth.csrf_token)
+                .header("Content-Type", "application/json")
+                .body(Body::from(payload.to_string())).unwrap()
+        ).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    // 4. Ledger resolves Booking > Label > Event
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/payouts", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let ledger = parse_body(res).await;
+    let lines = ledger.as_array().unwrap();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["amount"], 20);
+    assert_eq!(lines[0]["source"], "EVENT");
+    assert_eq!(lines[1]["amount"], 15);
+    assert_eq!(lines[1]["source"], "LABEL");
+    assert_eq!(lines[2]["amount"], 40);
+    assert_eq!(lines[2]["source"], "BOOKING");
+    assert!(lines.iter().all(|l| l["status"] == "UNPAID"));
+
+    // 5. Report per participant merges emails case-insensitively
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/payouts/report?group_by=participant", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let report = parse_body(res).await;
+    assert_eq!(report["totals"]["total"], 75);
+    let groups = report["groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 2);
+    let alice = groups.iter().find(|g| g["key"] == "alice@test.com").unwrap();
+    assert_eq!(alice["bookings"], 2);
+    assert_eq!(alice["total"], 60);
+
+    // 6. Mark Alice's bookings as paid
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/payouts/mark-paid", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "booking_ids": [booking_ids[0], booking_ids[2]],
+                "payment_reference": "TRANSFER-2024-01"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(parse_body(res).await["total"], 60);
+
+    // Paying twice is rejected
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/payouts/mark-paid", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "booking_ids": [booking_ids[0]],
+                "payment_reference": "TRANSFER-2024-02"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    // 7. Settled amounts are frozen even if the override changes later
+    app.router.clone().oneshot(
+        Request::builder().method("PUT").uri(format!("/api/v1/{}/bookings/{}", tid, booking_ids[2]))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"payout": 99}).to_string())).unwrap()
+    ).await.unwrap();
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/payouts/report?group_by=event", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    let report = parse_body(res).await;
+    assert_eq!(report["totals"]["paid"], 60);
+    assert_eq!(report["totals"]["unpaid"], 15);
+
+    // 8. Filters & CSV export
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/payouts?status=UNPAID", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    let unpaid = parse_body(res).await;
+    assert_eq!(unpaid.as_array().unwrap().len(), 1);
+    assert_eq!(unpaid[0]["customer_name"], "Bob");
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/payouts/report?group_by=period&format=csv", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("text/csv"));
+    let bytes = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let csv = String::from_utf8(bytes.to_vec()).unwrap();
+    assert!(csv.starts_with("key,name,bookings,total,paid,exported,unpaid\n"));
+    assert!(csv.contains(&next_mon.format("%Y-%m").to_string()));
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/payouts/report?group_by=weekday", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    // 6. Payouts the ledger cannot book in whole euros are refused
+    for (payout, status) in [("12,50€", StatusCode::BAD_REQUEST), ("15 or 20 EUR", StatusCode::BAD_REQUEST), ("25,00 €", StatusCode::OK)] {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("PUT").uri(format!("/api/v1/{}/events/{}", tid, ev_slug))
+                .header(header::COOKIE, format!("access_token={}", auth.access_token))
+                .header("X-CSRF-Token", &auth.csrf_token)
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({"payout": payout}).to_string())).unwrap()
+        ).await.unwrap();
+        assert_eq!(res.status(), status, "payout {}", payout);
+    }
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/payouts", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    let ledger = parse_body(res).await;
+    // The settled line keeps its amount
+    let alice = ledger.as_array().unwrap().iter().find(|l| l["booking_id"] == booking_ids[0].as_str()).unwrap();
+    assert_eq!(alice["amount"], 20);
+
+    // Events stored with such a payout before can still be edited
+    sqlx::query("UPDATE events SET payout = '15 or 20 EUR' WHERE slug = ?").bind(ev_slug).execute(&app.pool).await.unwrap();
+    let res = app.router.clone().oneshot(
+        Request::builder().method("PUT").uri(format!("/api/v1/{}/events/{}", tid, ev_slug))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"payout": "15 or 20 EUR", "location": "Room 2"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
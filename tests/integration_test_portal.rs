@@ -0,0 +1,133 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_participant_portal_magic_link() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant & Events
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Portal Lab", "slug": "portal-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    for (slug, allow_cancel) in [("flexible", true), ("fixed", false)] {
+        app.router.clone().oneshot(
+            Request::builder().method("POST").uri(format!("/api/v1/{}/events", tid))
+                .header(header::COOKIE, format!("access_token={}", auth.access_token))
+                .header("X-CSRF-Token", &auth.csrf_token)
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({
+                    "slug": slug, "title_en": slug, "title_de": slug, "desc_en": ".", "desc_de": ".",
+                    "location": "Lab", "payout": "15", "host_name": "H", "timezone": "UTC",
+                    "active_start": Utc::now().to_rfc3339(),
+                    "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+                    "duration_min": 60, "interval_min": 60, "max_participants": 5, "image_url": ".",
+                    "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+                    "access_mode": "OPEN", "allow_customer_cancel": allow_cancel
+                }).to_string())).unwrap()
+        ).await.unwrap();
+    }
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let post = |uri: String, body: Value| {
+        Request::builder().method("POST").uri(uri)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+    let mut booking_ids = Vec::new();
+    for (slug, time, email) in [("flexible", "09:00", "ada@test.com"), ("fixed", "10:00", "ada@test.com"), ("flexible", "11:00", "bob@test.com")] {
+        let res = app.router.clone().oneshot(post(format!("/api/v1/{}/events/{}/book", tid, slug),
+            json!({"date": date, "time": time, "name": "Ada", "email": email}))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        booking_ids.push(parse_body(res).await["id"].as_str().unwrap().to_string());
+    }
+
+    // 2. Unknown addresses get the same answer but no link
+    let res = app.router.clone().oneshot(post(format!("/api/v1/{}/portal/link", tid), json!({"email": "nobody@test.com"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(parse_body(res).await["status"], "sent");
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM portal_tokens").fetch_one(&app.pool).await.unwrap();
+    assert_eq!(count, 0);
+
+    // 3. A known address gets one link, repeated and concurrent requests are throttled
+    let link = || post(format!("/api/v1/{}/portal/link", tid), json!({"email": " ADA@test.com"}));
+    let (a, b, c) = tokio::join!(app.router.clone().oneshot(link()), app.router.clone().oneshot(link()), app.router.clone().oneshot(link()));
+    for res in [a, b, c] {
+        assert_eq!(res.unwrap().status(), StatusCode::OK);
+    }
+    let res = app.router.clone().oneshot(link()).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let tokens: Vec<(String,)> = sqlx::query_as("SELECT token FROM portal_tokens").fetch_all(&app.pool).await.unwrap();
+    assert_eq!(tokens.len(), 1);
+    let token = tokens[0].0.clone();
+
+    for _ in 0..15 {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let pending: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'PORTAL_LINK' AND status IN ('PENDING', 'PROCESSING')")
+            .fetch_one(&app.pool).await.unwrap();
+        if pending == 0 { break; }
+    }
+    let sent: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM mail_logs WHERE recipient = 'ada@test.com' AND template_id = 'portal_link' AND status = 'SENT'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(sent, 1);
+
+    // 4. The portal lists the participant's bookings across events
+    let res = app.router.clone().oneshot(
+        Request::builder().uri(format!("/api/v1/portal/{}", token)).body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let portal = parse_body(res).await;
+    let upcoming = portal["upcoming"].as_array().unwrap();
+    assert_eq!(upcoming.len(), 2);
+    assert!(portal["past"].as_array().unwrap().is_empty());
+    let fixed = upcoming.iter().find(|b| b["event_slug"] == "fixed").unwrap();
+    assert_eq!(fixed["can_cancel"], false);
+    assert_eq!(fixed["can_reschedule"], true);
+
+    // 5. Actions respect ownership and each event's policy
+    let res = app.router.clone().oneshot(post(format!("/api/v1/portal/{}/bookings/{}/cancel", token, booking_ids[2]), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    let res = app.router.clone().oneshot(post(format!("/api/v1/portal/{}/bookings/{}/cancel", token, booking_ids[1]), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let res = app.router.clone().oneshot(post(format!("/api/v1/portal/{}/bookings/{}/reschedule", token, booking_ids[0]),
+        json!({"date": date, "time": "13:00"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(parse_body(res).await["start_time"].as_str().unwrap().contains("13:00"));
+
+    let res = app.router.clone().oneshot(post(format!("/api/v1/portal/{}/bookings/{}/cancel", token, booking_ids[0]), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(parse_body(res).await["status"], "CANCELLED");
+
+    // 6. Expired links stop working
+    sqlx::query("UPDATE portal_tokens SET expires_at = ?")
+        .bind(Utc::now() - Duration::minutes(1))
+        .execute(&app.pool).await.unwrap();
+    let res = app.router.clone().oneshot(
+        Request::builder().uri(format!("/api/v1/portal/{}", token)).body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
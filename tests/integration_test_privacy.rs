@@ -0,0 +1,178 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_data_subject_export_and_erasure() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant & Event
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Privacy Lab", "slug": "privacy-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let ev_slug = "survey";
+    app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": ev_slug, "title_en": "Survey", "title_de": "Umfrage", "desc_en": ".", "desc_de": ".",
+        "location": "Room 2", "payout": "12", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 5, "image_url": ".",
+        "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+        "access_mode": "OPEN"
+    }))).await.unwrap();
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let mut bookings = Vec::new();
+    for (time, email) in [("09:00", "erika@test.com"), ("10:00", "Erika@Test.com"), ("11:00", "other@test.com")] {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, ev_slug))
+                .header("Content-Type", "application/json")
+                .header("Idempotency-Key", format!("key-{}", time))
+                .body(Body::from(json!({
+                    "date": date, "time": time, "name": "Erika Mustermann", "email": email, "notes": "Vegetarian",
+                    "consents": ["privacy_notice", " privacy_notice ", "reminders"]
+                }).to_string())).unwrap()
+        ).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        bookings.push(parse_body(res).await);
+    }
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, ev_slug))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": "12:00", "name": "Erika", "email": "erika@test.com", "consents": [" "]}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let past_id = bookings[0]["id"].as_str().unwrap().to_string();
+    let future_id = bookings[1]["id"].as_str().unwrap().to_string();
+
+    // The first session already took place and was a no-show
+    let tenant = app.state.tenant_repo.find_by_id(tid).await.unwrap().unwrap();
+    let past = Utc::now() - Duration::days(3);
+    sqlx::query("UPDATE bookings SET start_time = ?, end_time = ?, label_id = ? WHERE id = ?")
+        .bind(past).bind(past + Duration::hours(1)).bind(tenant.noshow_label_id.as_deref()).bind(&past_id)
+        .execute(&app.pool).await.unwrap();
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("PUT").uri(format!("/api/v1/bookings/manage/{}/bank-details", bookings[0]["management_token"].as_str().unwrap()))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"account_holder": "Erika Mustermann", "iban": "DE89 3704 0044 0532 0130 00"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/{}/invitees", tid, ev_slug), json!({"email": "ERIKA@test.com"}))).await.unwrap();
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/portal/link", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"email": "erika@test.com"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 2. JSON export covers bookings, bank details and invitations of every casing
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/privacy/export", tid), json!({"email": "erika@test.com"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let export = parse_body(res).await;
+    assert_eq!(export["bookings"].as_array().unwrap().len(), 2);
+    assert_eq!(export["bank_details"].as_array().unwrap().len(), 1);
+    assert!(export["bank_details"][0].get("iban_encrypted").is_none());
+    assert_eq!(export["invitees"].as_array().unwrap().len(), 1);
+    assert_eq!(export["participant"]["email"], "erika@test.com");
+    assert_eq!(export["consents"].as_array().unwrap().len(), 4);
+    assert!(export["consents"].as_array().unwrap().iter().any(|c| c["purpose"] == "reminders"));
+
+    // 3. ZIP export
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/privacy/export", tid), json!({"email": "erika@test.com", "format": "zip"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "application/zip");
+    let bytes = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    assert!(bytes.starts_with(b"PK"));
+
+    // 4. Erasure anonymizes personal data and frees future seats
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/privacy/erase", tid), json!({"email": "ERIKA@test.com"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let summary = parse_body(res).await;
+    assert_eq!(summary["bookings_anonymized"], 2);
+    assert_eq!(summary["bookings_cancelled"], 1);
+    assert_eq!(summary["invitees_anonymized"], 1);
+    assert_eq!(summary["bank_details_deleted"], 1);
+    assert_eq!(summary["idempotency_records_deleted"], 2);
+    assert_eq!(summary["portal_tokens_deleted"], 1);
+
+    // Neither replayable responses nor queued portal links keep the address
+    let replays: Vec<(String, Option<String>)> = sqlx::query_as("SELECT response_body, booking_id FROM idempotency_keys")
+        .fetch_all(&app.pool).await.unwrap();
+    assert_eq!(replays.len(), 1);
+    assert_eq!(replays[0].1.as_deref(), bookings[2]["id"].as_str());
+    let replays: Vec<String> = replays.into_iter().map(|(body, _)| body).collect();
+    assert!(replays.iter().all(|body| !body.to_lowercase().contains("erika@test.com")));
+    let payloads: Vec<String> = sqlx::query_scalar("SELECT payload FROM jobs")
+        .fetch_all(&app.pool).await.unwrap();
+    assert!(payloads.iter().all(|payload| !payload.to_lowercase().contains("erika@test.com")));
+
+    // Nothing queued for the subject is sent anymore, while other participants keep their jobs
+    let pending: Vec<String> = sqlx::query_scalar("SELECT json_extract(payload, '$.booking_id') FROM jobs WHERE status = 'PENDING'")
+        .fetch_all(&app.pool).await.unwrap();
+    assert!(!pending.is_empty());
+    assert!(pending.iter().all(|id| id == bookings[2]["id"].as_str().unwrap()));
+
+    let erased = app.state.booking_repo.find_by_id(tid, &past_id).await.unwrap().unwrap();
+    assert_eq!(erased.customer_name, "Erased");
+    assert!(erased.customer_note.is_none());
+    assert_eq!(erased.label_id, tenant.noshow_label_id);
+    let cancelled = app.state.booking_repo.find_by_id(tid, &future_id).await.unwrap().unwrap();
+    assert_eq!(cancelled.status, "CANCELLED");
+    let other = app.state.booking_repo.find_by_id(tid, bookings[2]["id"].as_str().unwrap()).await.unwrap().unwrap();
+    assert_eq!(other.customer_email, "other@test.com");
+
+    // Aggregate statistics survive
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/participants?search=erased", tid), Value::Null)).await.unwrap();
+    let participants = parse_body(res).await;
+    assert_eq!(participants[0]["stats"]["no_shows"], 1);
+    assert_eq!(participants[0]["stats"]["cancelled"], 1);
+
+    // Nothing is left to export
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/privacy/export", tid), json!({"email": "erika@test.com"}))).await.unwrap();
+    let export = parse_body(res).await;
+    assert_eq!(export["bookings"].as_array().unwrap().len(), 0);
+    assert!(export["participant"].is_null());
+
+    // 5. Every request is audited without storing the email itself
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/audit-logs", tid), Value::Null)).await.unwrap();
+    let logs = parse_body(res).await;
+    let actions: Vec<&str> = logs.as_array().unwrap().iter().map(|l| l["action"].as_str().unwrap()).collect();
+    assert_eq!(actions.iter().filter(|a| **a == "DATA_EXPORT").count(), 3);
+    assert_eq!(actions.iter().filter(|a| **a == "DATA_ERASURE").count(), 1);
+    assert!(!logs.to_string().to_lowercase().contains("erika@test.com"));
+}
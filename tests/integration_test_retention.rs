@@ -0,0 +1,144 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use booking_backend::background::run_retention;
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_retention_policy_dry_run_and_scheduled_run() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant & Event
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Retention Lab", "slug": "retention-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let ev_slug = "archive";
+    app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": ev_slug, "title_en": "Archive", "title_de": "Archiv", "desc_en": ".", "desc_de": ".",
+        "location": "Room 3", "payout": "10", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 5, "image_url": ".",
+        "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+        "access_mode": "OPEN"
+    }))).await.unwrap();
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let mut ids = Vec::new();
+    for (time, email) in [("09:00", "old@test.com"), ("10:00", "recent@test.com")] {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, ev_slug))
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({"date": date, "time": time, "name": "Someone", "email": email}).to_string())).unwrap()
+        ).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        ids.push(parse_body(res).await["id"].as_str().unwrap().to_string());
+    }
+
+    // 2. Age the data: a session 30 months ago, an old invitation, an old mail log, an expired refresh token
+    let long_ago = Utc::now() - Duration::days(30 * 30);
+    sqlx::query("UPDATE bookings SET start_time = ?, end_time = ? WHERE id = ?")
+        .bind(long_ago).bind(long_ago + Duration::hours(1)).bind(&ids[0])
+        .execute(&app.pool).await.unwrap();
+    sqlx::query("UPDATE participants SET created_at = ? WHERE email = 'old@test.com'")
+        .bind(long_ago).execute(&app.pool).await.unwrap();
+
+    app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/{}/invitees", tid, ev_slug), json!({"email": "invited@test.com"}))).await.unwrap();
+    sqlx::query("UPDATE invitees SET created_at = ? WHERE tenant_id = ?")
+        .bind(long_ago).bind(tid).execute(&app.pool).await.unwrap();
+
+    let job = app.state.job_repo.list_jobs(tid).await.unwrap().into_iter().next().unwrap();
+    sqlx::query("INSERT INTO mail_logs (id, job_id, recipient, template_id, context_hash, sent_at, status) VALUES ('old-log', ?, 'old@test.com', 't', 'h', ?, 'SENT')")
+        .bind(&job.id).bind(long_ago).execute(&app.pool).await.unwrap();
+
+    let admin_user = app.state.user_repo.find_by_username(tid, "admin").await.unwrap().unwrap();
+    sqlx::query("INSERT INTO refresh_tokens (token_hash, user_id, tenant_id, family_id, generation_id, expires_at) VALUES ('expired', ?, ?, 'family', 1, ?)")
+        .bind(&admin_user.id).bind(tid).bind(Utc::now() - Duration::days(1))
+        .execute(&app.pool).await.unwrap();
+
+    // 3. Configure the policy
+    let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/retention", tid), json!({
+        "anonymize_after_months": 0
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/retention", tid), json!({
+        "anonymize_after_months": 24, "mail_logs_after_months": 6, "invitees_after_months": 12
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 4. The dry run reports without touching anything
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/retention/report", tid), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let report = parse_body(res).await;
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["bookings_anonymized"], 1);
+    assert_eq!(report["participants_anonymized"], 1);
+    assert_eq!(report["mail_logs_deleted"], 1);
+    assert_eq!(report["invitees_deleted"], 1);
+
+    let old = app.state.booking_repo.find_by_id(tid, &ids[0]).await.unwrap().unwrap();
+    assert_eq!(old.customer_email, "old@test.com");
+    let invitees: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM invitees WHERE tenant_id = ?")
+        .bind(tid).fetch_one(&app.pool).await.unwrap();
+    assert_eq!(invitees, 1);
+    let tokens: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM refresh_tokens WHERE token_hash = 'expired'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(tokens, 1);
+
+    // 5. The scheduled run applies the policy
+    run_retention(&app.state).await;
+
+    let old = app.state.booking_repo.find_by_id(tid, &ids[0]).await.unwrap().unwrap();
+    assert_eq!(old.customer_email, "erased@invalid");
+    assert_eq!(old.status, "CONFIRMED");
+    let recent = app.state.booking_repo.find_by_id(tid, &ids[1]).await.unwrap().unwrap();
+    assert_eq!(recent.customer_email, "recent@test.com");
+    assert!(app.state.participant_repo.find_by_email(tid, "old@test.com").await.unwrap().is_none());
+    assert!(app.state.participant_repo.find_by_email(tid, "recent@test.com").await.unwrap().is_some());
+
+    let tokens: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM refresh_tokens WHERE token_hash = 'expired'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(tokens, 0);
+
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/retention", tid), Value::Null)).await.unwrap();
+    assert!(!parse_body(res).await["last_run_at"].is_null());
+
+    let res = app.router.clone().oneshot(admin("GET", format!("/api/v1/{}/retention/report", tid), Value::Null)).await.unwrap();
+    let report = parse_body(res).await;
+    assert_eq!(report["bookings_anonymized"], 0);
+    assert_eq!(report["invitees_deleted"], 0);
+
+    let logs = app.state.audit_repo.list(tid).await.unwrap();
+    assert!(logs.iter().any(|l| l.action == "RETENTION_RUN" && l.actor == "system"));
+}
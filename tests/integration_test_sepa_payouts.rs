@@ -0,0 +1,180 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_bank_details_and_sepa_export() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant & Event
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Sepa Lab", "slug": "sepa-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+
+    let ev_slug = "paid-study";
+    app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "slug": ev_slug, "title_en": "Paid Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+                "location": "Lab", "payout": "25 EUR", "host_name": "H", "timezone": "UTC",
+                "active_start": chrono::Utc::now().to_rfc3339(),
+                "active_end": (chrono::Utc::now() + chrono::Duration::days(30)).to_rfc3339(),
+                "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+                "config": { "monday": [{"start":"08:00", "end":"18:00"}] },
+                "access_mode": "OPEN"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+
+    let mut next_mon = chrono::Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += chrono::Duration::days(1); }
+    next_mon += chrono::Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+
+    let mut bookings = Vec::new();
+    for (time, name, email) in [("10:00", "Jürgen Müller", "j@test.com"), ("11:00", "Eve", "eve@test.com")] {
+        let res = app.router.clone().oneshot(
+            Request::builder().method("POST").uri(format!("/api/v1/{}/events/{}/book", tid, ev_slug))
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({"date": date, "time": time, "name": name, "email": email}).to_string())).unwrap()
+        ).await.unwrap();
+        bookings.push(parse_body(res).await);
+    }
+    let token = bookings[0]["management_token"].as_str().unwrap();
+    let booking_id = bookings[0]["id"].as_str().unwrap();
+    let other_id = bookings[1]["id"].as_str().unwrap();
+
+    // 2. Invalid IBAN checksum is rejected
+    let res = app.router.clone().oneshot(
+        Request::builder().method("PUT").uri(format!("/api/v1/bookings/manage/{}/bank-details", token))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"account_holder": "Jürgen Müller", "iban": "DE89 3704 0044 0532 0130 01"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    // 3. Valid details are stored encrypted, only the last digits are exposed
+    let res = app.router.clone().oneshot(
+        Request::builder().method("PUT").uri(format!("/api/v1/bookings/manage/{}/bank-details", token))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "account_holder": "Jürgen Müller", "iban": "de89 3704 0044 0532 0130 00", "bic": "COBADEFFXXX"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let saved = parse_body(res).await;
+    assert_eq!(saved["iban_last4"], "3000");
+    assert!(saved.get("iban_encrypted").is_none());
+
+    let (stored,): (String,) = sqlx::query_as("SELECT iban_encrypted FROM bank_details WHERE booking_id = ?")
+        .bind(booking_id)
+        .fetch_one(&app.pool).await.unwrap();
+    assert!(!stored.contains("DE89370400440532013000"));
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/bookings/manage/{}", token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(parse_body(res).await["bank_details"]["account_holder"], "Jürgen Müller");
+
+    // 4. Export fails when a selected booking has no bank details
+    let export = |ids: Vec<&str>| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/payouts/sepa-export", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "booking_ids": ids,
+                "debtor_name": "Sepa Lab e.V.",
+                "debtor_iban": "DE02120300000000202051",
+                "debtor_bic": "BYLADEM1001"
+            }).to_string())).unwrap()
+    };
+
+    let res = app.router.clone().oneshot(export(vec![booking_id, other_id])).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    // 5. Export generates pain.001 and marks the payout as exported
+    let res = app.router.clone().oneshot(export(vec![booking_id])).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("application/xml"));
+    let bytes = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let xml = String::from_utf8(bytes.to_vec()).unwrap();
+
+    assert!(xml.contains("urn:iso:std:iso:20022:tech:xsd:pain.001.001.03"));
+    assert!(xml.contains("<NbOfTxs>1</NbOfTxs><CtrlSum>25.00</CtrlSum>"));
+    assert!(xml.contains("<IBAN>DE89370400440532013000</IBAN>"));
+    assert!(xml.contains("<BIC>COBADEFFXXX</BIC>"));
+    assert!(xml.contains("<Nm>Juergen Mueller</Nm>"));
+    assert!(xml.contains(&format!("<EndToEndId>{}</EndToEndId>", booking_id.replace('-', ""))));
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/payouts?status=EXPORTED", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    let exported = parse_body(res).await;
+    assert_eq!(exported.as_array().unwrap().len(), 1);
+    let message_id = exported[0]["payment_reference"].as_str().unwrap().to_string();
+    assert!(xml.contains(&format!("<MsgId>{}</MsgId>", message_id)));
+
+    // 6. The same booking cannot be exported twice, but can be confirmed as paid
+    let res = app.router.clone().oneshot(export(vec![booking_id])).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/payouts/mark-paid", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"booking_ids": [booking_id], "payment_reference": message_id}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 7. Of two concurrent exports of the same booking only one gets a file
+    let other_token = bookings[1]["management_token"].as_str().unwrap();
+    let res = app.router.clone().oneshot(
+        Request::builder().method("PUT").uri(format!("/api/v1/bookings/manage/{}/bank-details", other_token))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"account_holder": "Eve", "iban": "DE89370400440532013000"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let (first, second) = tokio::join!(
+        app.router.clone().oneshot(export(vec![other_id])),
+        app.router.clone().oneshot(export(vec![other_id])),
+    );
+    let mut statuses = vec![first.unwrap().status(), second.unwrap().status()];
+    statuses.sort();
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::CONFLICT]);
+
+    // Likewise only one of two concurrent confirmations settles it
+    let mark_paid = || Request::builder().method("POST").uri(format!("/api/v1/{}/payouts/mark-paid", tid))
+        .header(header::COOKIE, format!("access_token={}", auth.access_token))
+        .header("X-CSRF-Token", &auth.csrf_token)
+        .header("Content-Type", "application/json")
+        .body(Body::from(json!({"booking_ids": [other_id], "payment_reference": "TRANSFER-1"}).to_string())).unwrap();
+    let (first, second) = tokio::join!(app.router.clone().oneshot(mark_paid()), app.router.clone().oneshot(mark_paid()));
+    let mut statuses = vec![first.unwrap().status(), second.unwrap().status()];
+    statuses.sort();
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::CONFLICT]);
+}
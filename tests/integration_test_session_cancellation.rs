@@ -0,0 +1,142 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_cancel_session_with_bookings() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant and a HYBRID event with two sessions
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Sick Lab", "slug": "sick-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": "hybrid", "title_en": "Session", "title_de": "Sitzung", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "10", "host_name": "H", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+        "config": {}, "access_mode": "HYBRID", "schedule_type": "MANUAL",
+        "reserved_capacity": 1, "reserved_release_hours": 1
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let monday = next_mon.date_naive();
+
+    let sessions_uri = format!("/api/v1/{}/events/hybrid/sessions", tid);
+    let mut session_ids = Vec::new();
+    for (date, capacity) in [(monday, 3), (monday + Duration::days(1), 1)] {
+        let res = app.router.clone().oneshot(admin("POST", sessions_uri.clone(), json!({
+            "date": date, "start_time": "10:00", "end_time": "11:00", "max_participants": capacity
+        }))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        session_ids.push(parse_body(res).await["id"].as_str().unwrap().to_string());
+    }
+
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/hybrid/invitees", tid), json!({"email": "tina@test.com"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let invitee = parse_body(res).await;
+    let own_token = invitee["token"].as_str().unwrap().to_string();
+
+    let book = |date: chrono::NaiveDate, email: &str, token: Option<&str>| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/hybrid/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": "10:00", "name": "P", "email": email, "token": token}).to_string())).unwrap()
+    };
+    let res = app.router.clone().oneshot(book(monday, "tina@test.com", Some(&own_token))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(book(monday, "paul@test.com", None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 2. Deleting refuses, cancelling takes the bookings along
+    let res = app.router.clone().oneshot(admin("DELETE", format!("{}/{}", sessions_uri, session_ids[0]), Value::Null)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let cancel_uri = format!("{}/{}/cancel", sessions_uri, session_ids[0]);
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(&cancel_uri)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    // A cancellation missing a booking made in the meantime writes nothing
+    let session = app.state.session_repo.find_by_id(&session_ids[0]).await.unwrap().unwrap();
+    let listed = app.state.booking_repo.list_by_tenant(tid).await.unwrap();
+    assert!(app.state.session_repo.cancel_with_bookings(&session, &listed[..1], &[], Vec::new()).await.is_err());
+    let bookings = app.state.booking_repo.list_by_tenant(tid).await.unwrap();
+    assert!(bookings.iter().all(|b| b.status != "CANCELLED"));
+    assert!(app.state.session_repo.find_by_id(&session_ids[0]).await.unwrap().is_some());
+
+    let res = app.router.clone().oneshot(admin("POST", cancel_uri.clone(), json!({"reason": "The experimenter is ill."}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = parse_body(res).await;
+    let cancelled = body["cancelled_bookings"].as_array().unwrap();
+    assert_eq!(cancelled.len(), 2);
+    assert!(cancelled.iter().all(|b| b["status"] == "CANCELLED"));
+
+    let res = app.router.clone().oneshot(admin("GET", sessions_uri.clone(), Value::Null)).await.unwrap();
+    assert_eq!(parse_body(res).await.as_array().unwrap().len(), 1);
+
+    let res = app.router.clone().oneshot(admin("POST", cancel_uri.clone(), json!({}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    // 3. Reminders are dropped, the participants are told with a rebooking token
+    let pending_reminders: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'REMINDER' AND status = 'PENDING'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(pending_reminders, 0);
+
+    let invitees = app.state.invitee_repo.list_by_event(tid, invitee["event_id"].as_str().unwrap()).await.unwrap();
+    assert_eq!(invitees.len(), 2);
+    let released = invitees.iter().find(|i| i.token == own_token).unwrap();
+    assert_eq!((released.use_count, released.status.as_str()), (0, "ACTIVE"));
+    let issued = invitees.iter().find(|i| i.token != own_token).unwrap();
+    assert_eq!(issued.email.as_deref(), Some("paul@test.com"));
+
+    for _ in 0..15 {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let pending: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'SESSION_CANCELLED' AND status IN ('PENDING', 'PROCESSING')")
+            .fetch_one(&app.pool).await.unwrap();
+        if pending == 0 { break; }
+    }
+    let sent: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM mail_logs WHERE template_id = 'hybrid - Session Cancelled' AND status = 'SENT'")
+        .fetch_one(&app.pool).await.unwrap();
+    assert_eq!(sent, 2);
+
+    // 4. The token opens the seat reserved for invitees in the other session
+    let tuesday = monday + Duration::days(1);
+    let res = app.router.clone().oneshot(book(tuesday, "anna@test.com", None)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    let res = app.router.clone().oneshot(book(tuesday, "paul@test.com", Some(&issued.token))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
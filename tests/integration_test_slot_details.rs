@@ -0,0 +1,144 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_slot_details_show_seats_location_and_host() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Detail Lab", "slug": "detail-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    // 2. An in-person morning and an online afternoon with fewer seats
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": "mixed", "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "10", "host_name": "Dr. Lab", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 3, "image_url": ".",
+        "config": { "monday": [
+            {"start": "09:00", "end": "10:00"},
+            {"start": "14:00", "end": "15:00", "max_participants": 1, "location": "Online", "host_name": "Dr. Remote"}
+        ] },
+        "access_mode": "OPEN"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+    let following = (next_mon + Duration::days(7)).format("%Y-%m-%d").to_string();
+
+    let slots = |date: &str| {
+        Request::builder().method("GET").uri(format!("/api/v1/{}/events/mixed/slots?date={}", tid, date))
+            .body(Body::empty()).unwrap()
+    };
+    let book = |time: &str, email: &str| {
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/mixed/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": time, "name": "Ada", "email": email}).to_string())).unwrap()
+    };
+
+    let res = app.router.clone().oneshot(slots(&date)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = parse_body(res).await;
+    let details = body["details"].as_array().unwrap();
+    assert_eq!(details.len(), 2);
+    assert_eq!(details[0]["capacity"], 3);
+    assert_eq!(details[0]["remaining_seats"], 3);
+    assert_eq!(details[0]["location"], "Lab");
+    assert_eq!(details[0]["host_name"], "Dr. Lab");
+    assert_eq!(details[1]["location"], "Online");
+    assert_eq!(details[1]["host_name"], "Dr. Remote");
+    let start = chrono::DateTime::parse_from_rfc3339(details[1]["start_time"].as_str().unwrap()).unwrap();
+    let end = chrono::DateTime::parse_from_rfc3339(details[1]["end_time"].as_str().unwrap()).unwrap();
+    assert_eq!(end - start, Duration::minutes(60));
+
+    // 3. Bookings count down the seats, the full slot stays listed
+    let res = app.router.clone().oneshot(book("09:00", "ada@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(parse_body(res).await["location"].is_null());
+
+    let res = app.router.clone().oneshot(book("14:00", "bob@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(parse_body(res).await["location"], "Online");
+
+    let res = app.router.clone().oneshot(slots(&date)).await.unwrap();
+    let body = parse_body(res).await;
+    assert_eq!(body["slots"].as_array().unwrap().len(), 1);
+    let details = body["details"].as_array().unwrap();
+    assert_eq!(details[0]["remaining_seats"], 2);
+    assert_eq!(details[1]["remaining_seats"], 0);
+    assert!(details[1].get("waitlist_available").is_none());
+
+    // 4. Override location and host apply to windows without their own
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/mixed/overrides", tid), json!({
+        "date": following, "is_unavailable": false, "config": null, "override_max_participants": 5,
+        "location": "Annex", "host_name": "Dr. Guest"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.router.clone().oneshot(slots(&following)).await.unwrap();
+    let details = parse_body(res).await["details"].clone();
+    assert_eq!(details[0]["capacity"], 5);
+    assert_eq!(details[0]["location"], "Annex");
+    assert_eq!(details[0]["host_name"], "Dr. Guest");
+    assert_eq!(details[1]["capacity"], 1);
+    assert_eq!(details[1]["location"], "Online");
+
+    // 5. Manual sessions report their own end, seats and location
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+        "slug": "manual", "title_en": "Session", "title_de": "Sitzung", "desc_en": ".", "desc_de": ".",
+        "location": "Lab", "payout": "10", "host_name": "Dr. Lab", "timezone": "UTC",
+        "active_start": Utc::now().to_rfc3339(),
+        "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+        "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+        "config": {}, "access_mode": "OPEN", "schedule_type": "MANUAL"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/manual/sessions", tid), json!({
+        "date": date, "start_time": "10:00", "end_time": "11:30", "max_participants": 4,
+        "location": "Room 5", "host_name": "Dr. Five"
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.router.clone().oneshot(
+        Request::builder().method("GET").uri(format!("/api/v1/{}/events/manual/slots?date={}", tid, date))
+            .body(Body::empty()).unwrap()
+    ).await.unwrap();
+    let details = parse_body(res).await["details"].clone();
+    assert_eq!(details[0]["capacity"], 4);
+    assert_eq!(details[0]["remaining_seats"], 4);
+    assert_eq!(details[0]["location"], "Room 5");
+    assert_eq!(details[0]["host_name"], "Dr. Five");
+    assert_eq!(details[0]["end_time"], format!("{}T11:30:00Z", date));
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_search_slots_across_events() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Search Lab", "slug": "search-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let admin = |method: &str, uri: String, body: Value| {
+        Request::builder().method(method).uri(uri)
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())).unwrap()
+    };
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    next_mon += Duration::days(7);
+    let date = next_mon.format("%Y-%m-%d").to_string();
+    let tuesday = (next_mon + Duration::days(1)).format("%Y-%m-%d").to_string();
+
+    for (slug, access_mode, schedule_type, start, end, location) in [
+        ("morning", "OPEN", "RECURRING", "09:00", "11:00", "Room 1"),
+        ("afternoon", "OPEN", "RECURRING", "14:00", "16:00", "Room 2"),
+        ("invited", "RESTRICTED", "RECURRING", "08:00", "09:00", "Room 3"),
+        ("manual", "OPEN", "MANUAL", "00:00", "00:00", "Room 4"),
+    ] {
+        let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events", tid), json!({
+            "slug": slug, "title_en": format!("Study {}", slug), "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+            "location": location, "payout": "15", "host_name": "H", "timezone": "UTC",
+            "active_start": Utc::now().to_rfc3339(),
+            "active_end": (Utc::now() + Duration::days(30)).to_rfc3339(),
+            "duration_min": 60, "interval_min": 60, "max_participants": 1, "image_url": ".",
+            "config": { "monday": [{"start": start, "end": end}] },
+            "access_mode": access_mode, "schedule_type": schedule_type
+        }))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+    let res = app.router.clone().oneshot(admin("POST", format!("/api/v1/{}/events/manual/sessions", tid), json!({
+        "date": tuesday, "start_time": "10:00", "end_time": "11:30", "max_participants": 2
+    }))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let session_id = parse_body(res).await["id"].as_str().unwrap().to_string();
+    let res = app.router.clone().oneshot(admin("PUT", format!("/api/v1/{}/events/manual/sessions/{}", tid, session_id), json!({"location": "Room 5"}))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let search = |query: String| {
+        Request::builder().method("GET").uri(format!("/api/v1/{}/slots/search?{}", tid, query)).body(Body::empty()).unwrap()
+    };
+
+    // 2. Range validation
+    let res = app.router.clone().oneshot(search(format!("start={}&end={}", tuesday, date))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let far = (next_mon + Duration::days(40)).format("%Y-%m-%d").to_string();
+    let res = app.router.clone().oneshot(search(format!("start={}&end={}", date, far))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    // 3. Earliest slots of all OPEN events, in chronological order
+    let res = app.router.clone().oneshot(search(format!("start={}&end={}", date, tuesday))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let slots = parse_body(res).await;
+    let found: Vec<(String, String)> = slots.as_array().unwrap().iter()
+        .map(|s| (s["event_slug"].as_str().unwrap().to_string(), s["start_time"].as_str().unwrap().to_string()))
+        .collect();
+    assert_eq!(found, vec![
+        ("morning".to_string(), format!("{}T09:00:00Z", date)),
+        ("morning".to_string(), format!("{}T10:00:00Z", date)),
+        ("afternoon".to_string(), format!("{}T14:00:00Z", date)),
+        ("afternoon".to_string(), format!("{}T15:00:00Z", date)),
+        ("manual".to_string(), format!("{}T10:00:00Z", tuesday)),
+    ]);
+    assert_eq!(slots[0]["title_en"], "Study morning");
+    assert_eq!(slots[0]["location"], "Room 1");
+    assert_eq!(slots[0]["payout"], "15");
+    assert_eq!(slots[4]["location"], "Room 5");
+    assert_eq!(slots[4]["end_time"], format!("{}T11:30:00Z", tuesday));
+
+    // 4. Booked slots disappear and the limit applies
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/morning/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"date": date, "time": "09:00", "name": "Ada", "email": "ada@test.com"}).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.router.clone().oneshot(search(format!("start={}&end={}&limit=2", date, tuesday))).await.unwrap();
+    let slots = parse_body(res).await;
+    assert_eq!(slots.as_array().unwrap().len(), 2);
+    assert_eq!(slots[0]["start_time"], format!("{}T10:00:00Z", date));
+    assert_eq!(slots[1]["event_slug"], "afternoon");
+}
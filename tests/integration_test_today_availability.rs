@@ -71,7 +71,7 @@ async fn test_today_availability_logic() {
     let body = parse_body(res).await;
     let slots = body["slots"].as_array().unwrap();
 
-    if Utc::now().format("%H").to_string() < "22".to_string() {
+    if Utc::now().format("%H").to_string().as_str() < "22" {
         assert!(!slots.is_empty());
     }
 }
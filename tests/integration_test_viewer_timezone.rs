@@ -0,0 +1,112 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::{America::New_York, Europe::Berlin};
+use common::TestApp;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn parse_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+fn berlin(date: NaiveDate, hour: u32, minute: u32) -> DateTime<Utc> {
+    Berlin.from_local_datetime(&date.and_hms_opt(hour, minute, 0).unwrap()).single().unwrap().with_timezone(&Utc)
+}
+
+#[tokio::test]
+async fn test_slots_and_bookings_in_viewer_timezone() {
+    let app = TestApp::new().await;
+
+    // 1. Setup Tenant
+    let t_res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri("/api/v1/tenants")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({"name": "Berlin Lab", "slug": "berlin-lab"}).to_string())).unwrap()
+    ).await.unwrap();
+    let t_data = parse_body(t_res).await;
+    let tid = t_data["tenant_id"].as_str().unwrap();
+    let sec = t_data["admin_secret"].as_str().unwrap();
+
+    let auth = app.login(tid, "admin", sec).await;
+    let res = app.router.clone().oneshot(
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events", tid))
+            .header(header::COOKIE, format!("access_token={}", auth.access_token))
+            .header("X-CSRF-Token", &auth.csrf_token)
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "slug": "remote", "title_en": "Study", "title_de": "Studie", "desc_en": ".", "desc_de": ".",
+                "location": "Online", "payout": "0", "host_name": "H", "timezone": "Europe/Berlin",
+                "active_start": Utc::now().to_rfc3339(),
+                "active_end": (Utc::now() + Duration::days(40)).to_rfc3339(),
+                "duration_min": 30, "interval_min": 30, "max_participants": 1, "image_url": ".",
+                "config": {
+                    "monday": [{"start": "09:00", "end": "10:00"}],
+                    "tuesday": [{"start": "00:00", "end": "00:30"}],
+                    "wednesday": [{"start": "23:30", "end": "00:30"}]
+                },
+                "access_mode": "OPEN"
+            }).to_string())).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let mut next_mon = Utc::now();
+    while next_mon.format("%A").to_string() != "Monday" { next_mon += Duration::days(1); }
+    let monday = (next_mon + Duration::days(7)).date_naive();
+    let tuesday = monday + Duration::days(1);
+    let wednesday = monday + Duration::days(2);
+    let thursday = monday + Duration::days(3);
+
+    let get = |uri: String| Request::builder().method("GET").uri(uri).body(Body::empty()).unwrap();
+
+    // 2. Berlin's Tuesday midnight slot is still Monday evening in New York
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/remote/dates?start={}&end={}", tid, monday, tuesday))).await.unwrap();
+    assert_eq!(parse_body(res).await, json!([monday.to_string(), tuesday.to_string()]));
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/remote/dates?start={}&end={}&tz=America/New_York", tid, monday, tuesday))).await.unwrap();
+    assert_eq!(parse_body(res).await, json!([monday.to_string()]));
+
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/remote/slots?date={}&tz=America/New_York", tid, monday))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = parse_body(res).await;
+    assert_eq!(body["timezone"], "America/New_York");
+    let expected: Vec<String> = [berlin(monday, 9, 0), berlin(monday, 9, 30), berlin(tuesday, 0, 0)]
+        .iter().map(|t| t.with_timezone(&New_York).to_rfc3339()).collect();
+    assert_eq!(body["slots"], json!(expected));
+    assert_eq!(body["details"].as_array().unwrap().len(), 3);
+
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/remote/slots?date={}", tid, monday))).await.unwrap();
+    assert_eq!(parse_body(res).await["timezone"], "Europe/Berlin");
+
+    // 3. Unknown zones are rejected
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/remote/slots?date={}&tz=Mars/Olympus", tid, monday))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    // 4. Bookings take the viewer's date and time
+    let book = |date: NaiveDate, slot: DateTime<Utc>, email: &str| {
+        let local = slot.with_timezone(&New_York);
+        assert_eq!(local.date_naive(), date);
+        Request::builder().method("POST").uri(format!("/api/v1/{}/events/remote/book", tid))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({
+                "date": date.to_string(), "time": local.format("%H:%M").to_string(),
+                "timezone": "America/New_York", "name": "Nora", "email": email
+            }).to_string())).unwrap()
+    };
+    let res = app.router.clone().oneshot(book(monday, berlin(tuesday, 0, 0), "nora@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(parse_body(res).await["start_time"], berlin(tuesday, 0, 0).to_rfc3339().replace("+00:00", "Z"));
+
+    // Even for the part of a window that runs past Berlin midnight
+    let res = app.router.clone().oneshot(book(wednesday, berlin(thursday, 0, 0), "late@test.com")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(parse_body(res).await["start_time"], berlin(thursday, 0, 0).to_rfc3339().replace("+00:00", "Z"));
+
+    let res = app.router.clone().oneshot(get(format!("/api/v1/{}/events/remote/slots?date={}&tz=America/New_York", tid, wednesday))).await.unwrap();
+    let body = parse_body(res).await;
+    assert_eq!(body["slots"], json!([berlin(wednesday, 23, 30).with_timezone(&New_York).to_rfc3339()]));
+}